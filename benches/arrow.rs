@@ -0,0 +1,46 @@
+//! Measures conversion throughput between `Pxid` and Arrow arrays.
+//!
+//! Run with `cargo bench --features arrow --bench arrow`.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use arrow2::array::{FixedSizeBinaryArray, Utf8Array};
+use pxid::arrow::PxidArray;
+use pxid::{Factory, Pxid};
+
+const TRYOUTS: usize = 1_000_000;
+
+fn bench<T, F: FnMut() -> T>(name: &str, mut f: F) -> T {
+    let start = Instant::now();
+    let value = f();
+    let elapsed = start.elapsed();
+
+    println!(
+        "{name}: {elapsed:?} ({:.0} IDs/s)",
+        TRYOUTS as f64 / elapsed.max(Duration::from_nanos(1)).as_secs_f64()
+    );
+    value
+}
+
+fn main() {
+    let factory = Factory::new().expect("Failed to create Factory");
+    let ids = (0..TRYOUTS)
+        .map(|_| factory.new_id("acct").expect("Failed to create Pxid"))
+        .collect::<Vec<Pxid>>();
+
+    let binary = bench("Vec<Pxid> -> FixedSizeBinaryArray", || {
+        <FixedSizeBinaryArray as From<PxidArray>>::from(PxidArray::from(ids.clone()))
+    });
+
+    bench("FixedSizeBinaryArray -> Vec<Pxid>", || {
+        black_box(PxidArray::try_from(&binary).expect("Failed to convert array"))
+    });
+
+    let utf8 = bench("Vec<Pxid> -> Utf8Array<i32>", || {
+        <Utf8Array<i32> as From<PxidArray>>::from(PxidArray::from(ids.clone()))
+    });
+
+    bench("Utf8Array<i32> -> Vec<Pxid>", || {
+        black_box(PxidArray::try_from(&utf8).expect("Failed to convert array"))
+    });
+}
@@ -0,0 +1,69 @@
+//! Measures how bulk decoding, encoding and validation scale with the
+//! amount of rayon threads, against the sequential implementations.
+//!
+//! Run with `cargo bench --features rayon --bench bulk`.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use pxid::{bulk, Factory, Pxid};
+use rayon::ThreadPoolBuilder;
+
+const TRYOUTS: usize = 4_000_000;
+
+fn bench<T, F: FnMut() -> T>(name: &str, mut f: F) -> Duration {
+    let start = Instant::now();
+
+    black_box(f());
+
+    let elapsed = start.elapsed();
+
+    println!(
+        "{name}: {elapsed:?} ({:.0} IDs/s)",
+        TRYOUTS as f64 / elapsed.max(Duration::from_nanos(1)).as_secs_f64()
+    );
+    elapsed
+}
+
+fn main() {
+    let factory = Factory::new().expect("Failed to create Factory");
+    let ids = (0..TRYOUTS)
+        .map(|_| factory.new_id("acct").expect("Failed to create Pxid"))
+        .collect::<Vec<Pxid>>();
+    let encoded = bulk::encode(&ids);
+
+    let decode = bench("decode", || bulk::decode(&encoded));
+    let encode = bench("encode", || bulk::encode(&ids));
+    let validate = bench("validate", || bulk::validate(&encoded));
+
+    let max_threads = std::thread::available_parallelism().map_or(1, usize::from);
+    let threads = (0..)
+        .map(|exp| 1 << exp)
+        .take_while(|threads| *threads < max_threads)
+        .chain([max_threads]);
+
+    for threads in threads {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("Failed to build thread pool");
+
+        pool.install(|| {
+            let par_decode = bench(&format!("par_decode ({threads} threads)"), || {
+                bulk::par_decode(&encoded)
+            });
+            let par_encode = bench(&format!("par_encode ({threads} threads)"), || {
+                bulk::par_encode(&ids)
+            });
+            let par_validate = bench(&format!("par_validate ({threads} threads)"), || {
+                bulk::par_validate(&encoded)
+            });
+
+            println!(
+                "speedup with {threads} threads: decode {:.2}x, encode {:.2}x, validate {:.2}x",
+                decode.as_secs_f64() / par_decode.as_secs_f64(),
+                encode.as_secs_f64() / par_encode.as_secs_f64(),
+                validate.as_secs_f64() / par_validate.as_secs_f64()
+            );
+        });
+    }
+}
@@ -0,0 +1,65 @@
+//! Measures `Display` throughput, as used on high-volume logging, along with
+//! the cost of writing an encoded ID one character at a time, as `Display`
+//! did previously, against a single `write_str` call.
+//!
+//! Run with `cargo bench --bench display`.
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use pxid::{Factory, Pxid};
+
+const TRYOUTS: usize = 1_000_000;
+
+fn bench<F: FnMut()>(name: &str, mut f: F) -> Duration {
+    let start = Instant::now();
+
+    f();
+
+    let elapsed = start.elapsed();
+    println!(
+        "{name}: {elapsed:?} ({:.0} IDs/s)",
+        TRYOUTS as f64 / elapsed.max(Duration::from_nanos(1)).as_secs_f64()
+    );
+    elapsed
+}
+
+fn main() {
+    let factory = Factory::new().expect("Failed to create Factory");
+    let ids = (0..TRYOUTS)
+        .map(|_| factory.new_id("acct").expect("Failed to create Pxid"))
+        .collect::<Vec<Pxid>>();
+    let encoded = ids.iter().map(Pxid::to_string).collect::<Vec<String>>();
+    let mut out = String::with_capacity(64);
+
+    let per_char = bench("Encoded IDs written per char", || {
+        for s in encoded.iter() {
+            out.clear();
+            s.chars()
+                .try_for_each(|c| out.write_char(c))
+                .expect("Failed to write");
+            black_box(&out);
+        }
+    });
+
+    let whole = bench("Encoded IDs written with write_str", || {
+        for s in encoded.iter() {
+            out.clear();
+            out.write_str(s).expect("Failed to write");
+            black_box(&out);
+        }
+    });
+
+    println!(
+        "write_str speedup: {:.2}x",
+        per_char.as_secs_f64() / whole.as_secs_f64().max(f64::EPSILON)
+    );
+
+    bench("Display", || {
+        for id in ids.iter() {
+            out.clear();
+            write!(out, "{id}").expect("Failed to write");
+            black_box(&out);
+        }
+    });
+}
@@ -0,0 +1,80 @@
+//! Compares the ways of encoding a `Pxid` into its string form, reporting
+//! the time and heap allocations per ID. Results are kept in
+//! `BENCHMARKS.md` as a regression baseline.
+//!
+//! Run with `cargo bench --bench encode`.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use pxid::{Factory, Pxid};
+
+const TRYOUTS: usize = 1_000_000;
+
+/// Allocator counting the allocations made through it
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn bench<F: FnMut(&Pxid)>(name: &str, ids: &[Pxid], mut f: F) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+
+    for id in ids {
+        f(id);
+    }
+
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    println!(
+        "{name}: {:.1} ns/op, {:.2} allocations/op",
+        elapsed.as_nanos() as f64 / ids.len() as f64,
+        allocations as f64 / ids.len() as f64
+    );
+}
+
+fn main() {
+    let factory = Factory::new().expect("Failed to create Factory");
+    let ids = (0..TRYOUTS)
+        .map(|_| factory.new_id("acct").expect("Failed to create Pxid"))
+        .collect::<Vec<Pxid>>();
+    let mut buf = [0_u8; 25];
+    let mut out = Vec::with_capacity(64);
+
+    bench("id.to_string()", &ids, |id| {
+        black_box(id.to_string());
+    });
+
+    bench("id.encode_into(&mut buf)", &ids, |id| {
+        black_box(id.encode_into(&mut buf));
+        black_box(&buf);
+    });
+
+    bench("write!(out, \"{}\", id)", &ids, |id| {
+        out.clear();
+        write!(out, "{}", id).expect("Failed to write");
+        black_box(&out);
+    });
+}
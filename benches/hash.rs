@@ -0,0 +1,52 @@
+//! Compares `FxPxidHasher` against the default `HashMap` hasher.
+//!
+//! Run with `cargo bench --features fast-hash --bench hash`.
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use pxid::hash::PxidMap;
+use pxid::{Factory, Pxid};
+
+const TRYOUTS: usize = 1_000_000;
+
+fn bench<F: FnMut()>(name: &str, mut f: F) -> Duration {
+    let start = Instant::now();
+
+    f();
+
+    let elapsed = start.elapsed();
+    println!("{name}: {elapsed:?}");
+    elapsed
+}
+
+fn main() {
+    let factory = Factory::new().expect("Failed to create Factory");
+    let ids = (0..TRYOUTS)
+        .map(|_| factory.new_id("acct").expect("Failed to create Pxid"))
+        .collect::<Vec<Pxid>>();
+
+    bench("std::collections::HashMap (SipHash)", || {
+        let mut map = HashMap::with_capacity(TRYOUTS);
+
+        for (idx, id) in ids.iter().enumerate() {
+            map.insert(*id, idx);
+        }
+
+        for id in ids.iter() {
+            black_box(map.get(id));
+        }
+    });
+
+    bench("pxid::hash::PxidMap (FxPxidHasher)", || {
+        let mut map = PxidMap::with_capacity_and_hasher(TRYOUTS, Default::default());
+
+        for (idx, id) in ids.iter().enumerate() {
+            map.insert(*id, idx);
+        }
+
+        for id in ids.iter() {
+            black_box(map.get(id));
+        }
+    });
+}
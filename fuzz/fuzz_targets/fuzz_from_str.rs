@@ -0,0 +1,11 @@
+//! Decodes arbitrary input, which may fail but must never panic.
+#![no_main]
+
+use std::str::FromStr;
+
+use libfuzzer_sys::fuzz_target;
+use pxid::Pxid;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Pxid::from_str(&String::from_utf8_lossy(data));
+});
@@ -0,0 +1,24 @@
+//! Encodes arbitrary packed IDs and decodes them back.
+//!
+//! Byte arrays rejected by `Pxid::from_packed` have no string form to round
+//! trip. `FromStr` lowercases prefixes, so IDs holding uppercase prefixes
+//! only round trip through `Pxid::from_str_preserve_case`.
+#![no_main]
+
+use std::str::FromStr;
+
+use libfuzzer_sys::fuzz_target;
+use pxid::Pxid;
+
+fuzz_target!(|bytes: [u8; 16]| {
+    let Ok(id) = Pxid::from_packed(&bytes) else {
+        return;
+    };
+    let encoded = id.to_string();
+
+    assert_eq!(Pxid::from_str_preserve_case(&encoded), Ok(id));
+
+    if !id.prefix_bytes().iter().any(u8::is_ascii_uppercase) {
+        assert_eq!(Pxid::from_str(&encoded), Ok(id));
+    }
+});
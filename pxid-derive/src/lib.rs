@@ -0,0 +1,193 @@
+//! # pxid-derive
+//!
+//! Derive macros for newtypes around `pxid::Pxid`.
+//!
+//! This crate is re-exported by `pxid` behind the `derive` feature, prefer
+//! depending on `pxid` directly.
+//!
+//! ```ignore
+//! use pxid::{Pxid, PxidWrapper};
+//!
+//! #[derive(PxidWrapper)]
+//! #[pxid(prefix = "user")]
+//! pub struct UserId(Pxid);
+//! ```
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr};
+
+/// Pxid max prefix length
+const PREFIX_LENGTH: usize = 4;
+
+/// Implements `Display`, `FromStr`, `TryFrom<&str>` and conversions from and
+/// into `Pxid` for tuple newtypes around `Pxid`.
+///
+/// When the `#[pxid(prefix = "...")]` attribute is provided, the prefix is
+/// enforced when parsing and converting from `Pxid`, and a `new` associated
+/// function is generated to create IDs using such prefix.
+///
+/// Serde and GraphQL implementations are generated when the `serde` and
+/// `async-graphql` features of `pxid` are enabled.
+#[proc_macro_derive(PxidWrapper, attributes(pxid))]
+pub fn derive_pxid_wrapper(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    validate_newtype(&input)?;
+
+    let prefix = parse_prefix(&input)?;
+    let name = &input.ident;
+
+    let graphql = match &prefix {
+        Some(_) => quote! { ::pxid::__pxid_wrapper_graphql!(#name); },
+        None => quote! { ::pxid::__pxid_wrapper_graphql!(#name, unprefixed); },
+    };
+
+    let conversions = match &prefix {
+        Some(prefix) => quote! {
+            impl #name {
+                /// Prefix enforced on IDs of this type
+                pub const PREFIX: &'static str = #prefix;
+
+                /// Creates a new ID using the current timestamp and `PREFIX`
+                pub fn new() -> ::pxid::Result<Self> {
+                    ::pxid::Pxid::new(Self::PREFIX).map(Self)
+                }
+            }
+
+            impl ::core::convert::TryFrom<::pxid::Pxid> for #name {
+                type Error = ::pxid::Error;
+
+                fn try_from(value: ::pxid::Pxid) -> ::core::result::Result<Self, Self::Error> {
+                    ::pxid::__private::check_prefix(&value, Self::PREFIX)?;
+
+                    ::core::result::Result::Ok(Self(value))
+                }
+            }
+
+            impl ::core::str::FromStr for #name {
+                type Err = ::pxid::Error;
+
+                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                    let value = <::pxid::Pxid as ::core::str::FromStr>::from_str(s)?;
+
+                    <Self as ::core::convert::TryFrom<::pxid::Pxid>>::try_from(value)
+                }
+            }
+        },
+        None => quote! {
+            impl ::core::convert::From<::pxid::Pxid> for #name {
+                fn from(value: ::pxid::Pxid) -> Self {
+                    Self(value)
+                }
+            }
+
+            impl ::core::str::FromStr for #name {
+                type Err = ::pxid::Error;
+
+                fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                    <::pxid::Pxid as ::core::str::FromStr>::from_str(s).map(Self)
+                }
+            }
+        },
+    };
+
+    Ok(quote! {
+        #conversions
+
+        impl ::core::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<&'a str> for #name {
+            type Error = ::pxid::Error;
+
+            fn try_from(value: &'a str) -> ::core::result::Result<Self, Self::Error> {
+                <Self as ::core::str::FromStr>::from_str(value)
+            }
+        }
+
+        impl ::core::convert::From<#name> for ::pxid::Pxid {
+            fn from(value: #name) -> Self {
+                value.0
+            }
+        }
+
+        ::pxid::__pxid_wrapper_serde!(#name);
+        #graphql
+    })
+}
+
+/// Checks the input is a non generic tuple struct with a single field
+fn validate_newtype(input: &DeriveInput) -> syn::Result<()> {
+    let message = "PxidWrapper can only be derived for tuple structs with a single Pxid field";
+
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {}
+            _ => return Err(Error::new_spanned(&input.ident, message)),
+        },
+        _ => return Err(Error::new_spanned(&input.ident, message)),
+    }
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "PxidWrapper cannot be derived for generic structs",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Retrieves the prefix from the `#[pxid(prefix = "...")]` attribute
+fn parse_prefix(input: &DeriveInput) -> syn::Result<Option<LitStr>> {
+    let mut prefix: Option<LitStr> = None;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("pxid"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("prefix") {
+                return Err(meta.error("unsupported pxid attribute, expected `prefix`"));
+            }
+
+            if prefix.is_some() {
+                return Err(meta.error("duplicated `prefix` attribute"));
+            }
+
+            let value: LitStr = meta.value()?.parse()?;
+            let len = value.value().len();
+
+            if len == 0 || len > PREFIX_LENGTH {
+                return Err(Error::new_spanned(
+                    &value,
+                    format!("prefix must have between 1 and {PREFIX_LENGTH} bytes, found {len}"),
+                ));
+            }
+
+            if value.value().bytes().any(|b| b.is_ascii_uppercase()) {
+                return Err(Error::new_spanned(
+                    &value,
+                    "prefix must be lowercase, given `FromStr` lowercases decoded prefixes",
+                ));
+            }
+
+            prefix = Some(value);
+
+            Ok(())
+        })?;
+    }
+
+    Ok(prefix)
+}
@@ -0,0 +1,316 @@
+//! Custom character sets for the encoded XID of the string form.
+//!
+//! IDs are encoded with `XID_CHARSET` by `Display` and `FromStr`, deployments
+//! needing a different set of 32 characters, for instance to avoid spelling
+//! words on user facing IDs, encode them with an `Alphabet` instead.
+//!
+//! ```no_run
+//! use pxid::{Alphabet, Pxid};
+//!
+//! # fn main() -> pxid::Result<()> {
+//! let id = Pxid::new("acct")?;
+//! let encoded = id.to_string_with_alphabet(&Alphabet::NO_VOWELS);
+//!
+//! assert_eq!(Pxid::parse_with_alphabet(&encoded, &Alphabet::NO_VOWELS)?, id);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The alphabet is not recorded on the string form, so IDs must be parsed
+//! with the alphabet used to encode them. Strings holding characters outside
+//! the alphabet fail to parse, but strings only holding characters shared by
+//! two alphabets parse under both into different IDs.
+use std::fmt;
+use std::str::from_utf8;
+
+use crate::error::{DecodeError, Error};
+use crate::id::{Pxid, SEPARATOR, XID_BINARY_LENGTH, XID_ENCODED_LENGTH};
+use crate::Result;
+
+/// Marks bytes outside the alphabet on the decoding table
+const INVALID: u8 = u8::MAX;
+
+/// Set of 32 characters used to encode the XID of the string form, along
+/// with the table used to decode them
+#[derive(Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    encode: [u8; 32],
+    decode: [u8; 256],
+}
+
+impl Alphabet {
+    /// Alphabet used by `Display` and `FromStr`, which is `XID_CHARSET`
+    pub const STANDARD: Alphabet = Alphabet::build(*b"0123456789abcdefghijklmnopqrstuv");
+
+    /// Alphabet without vowels, so encoded IDs don't spell words.
+    ///
+    /// Characters are in ASCII order, so encoded IDs sort as their binary
+    /// form.
+    ///
+    /// ```text
+    /// -0123456789bcdfghjklmnpqrstvwxyz
+    /// ```
+    pub const NO_VOWELS: Alphabet = Alphabet::build(*b"-0123456789bcdfghjklmnpqrstvwxyz");
+
+    /// Creates an `Alphabet` encoding each 5 bits value as the character at
+    /// its position on `chars`.
+    ///
+    /// Characters must be printable ASCII characters other than `SEPARATOR`,
+    /// and appear only once.
+    pub fn new(chars: [u8; 32]) -> Result<Self> {
+        for (pos, &c) in chars.iter().enumerate() {
+            if !c.is_ascii_graphic() || char::from(c) == SEPARATOR {
+                return Err(Error::InvalidAlphabetChar(c, pos));
+            }
+
+            if chars.iter().take(pos).any(|&prev| prev == c) {
+                return Err(Error::DuplicateAlphabetChar(char::from(c), pos));
+            }
+        }
+
+        Ok(Self::build(chars))
+    }
+
+    /// Retrieves the characters of this `Alphabet` in encoding order
+    pub fn chars(&self) -> &str {
+        from_utf8(&self.encode).unwrap_or_default()
+    }
+
+    // Indexes are bounded by the array lengths: `idx` is below 32, and the
+    // decoding table holds an entry for every `u8`
+    #[allow(
+        clippy::indexing_slicing,
+        clippy::cast_possible_truncation,
+        clippy::large_stack_arrays
+    )]
+    const fn build(chars: [u8; 32]) -> Self {
+        let mut decode = [INVALID; 256];
+        let mut idx = 0;
+
+        while idx < chars.len() {
+            decode[chars[idx] as usize] = idx as u8;
+            idx += 1;
+        }
+
+        Self {
+            encode: chars,
+            decode,
+        }
+    }
+
+    /// Encodes the XID bytes into the characters of this `Alphabet`
+    pub(crate) fn encode_xid(&self, bytes: &[u8; XID_BINARY_LENGTH]) -> [u8; XID_ENCODED_LENGTH] {
+        Pxid::encode_xid_bytes_with(bytes, |value| {
+            self.encode
+                .get(usize::from(value & 31))
+                .copied()
+                .unwrap_or(self.encode[0])
+        })
+    }
+
+    /// Decodes an encoded XID written with the characters of this
+    /// `Alphabet`, failing as `Pxid::decode_xid` does
+    pub(crate) fn decode_xid(
+        &self,
+        s: &str,
+    ) -> std::result::Result<[u8; XID_BINARY_LENGTH], DecodeError> {
+        if s.len() != XID_ENCODED_LENGTH {
+            return Err(DecodeError::InvalidXidLength(s.to_string(), s.len()));
+        }
+
+        let mut dec_bytes = [0_u8; XID_ENCODED_LENGTH];
+
+        for ((dst, src), (pos, c)) in dec_bytes.iter_mut().zip(s.bytes()).zip(s.char_indices()) {
+            let value = self
+                .decode
+                .get(usize::from(src))
+                .copied()
+                .unwrap_or(INVALID);
+
+            if !c.is_ascii() || value == INVALID {
+                return Err(DecodeError::InvalidChar(s.to_string(), c, pos));
+            }
+
+            *dst = value;
+        }
+
+        Ok(Pxid::decode_xid_quintets(&dec_bytes))
+    }
+
+    /// Checks whether `s` is the encoded XID of the nil Pxid
+    pub(crate) fn is_nil_xid(&self, s: &str) -> bool {
+        s.len() == XID_ENCODED_LENGTH && s.bytes().all(|b| b == self.encode[0])
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+impl fmt::Debug for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Alphabet").field(&self.chars()).finish()
+    }
+}
+
+impl Pxid {
+    /// Encodes this Pxid as done by `Display`, using the characters of
+    /// `alphabet` for the encoded XID
+    pub fn to_string_with_alphabet(&self, alphabet: &Alphabet) -> String {
+        let xid = alphabet.encode_xid(&self.xid_bytes());
+
+        format!(
+            "{}{SEPARATOR}{}",
+            self.display_prefix_lossy(),
+            from_utf8(&xid).unwrap_or_default()
+        )
+    }
+
+    /// Decodes a Pxid as done by `FromStr` from a string whose encoded XID
+    /// is written with the characters of `alphabet`
+    pub fn parse_with_alphabet(s: &str, alphabet: &Alphabet) -> Result<Self> {
+        Self::decode_normalized(s, SEPARATOR, alphabet).map_err(Error::Decode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::factory::Factory;
+    use crate::id::XID_CHARSET;
+
+    use super::*;
+
+    #[test]
+    fn standard_alphabet_matches_display() {
+        let factory = Factory::deterministic(7);
+        let id = factory.new_id("acct").unwrap();
+
+        assert_eq!(Alphabet::STANDARD.chars(), XID_CHARSET);
+        assert_eq!(Alphabet::default(), Alphabet::STANDARD);
+        assert_eq!(
+            id.to_string_with_alphabet(&Alphabet::STANDARD),
+            id.to_string()
+        );
+        assert_eq!(
+            Pxid::parse_with_alphabet("ACCT_9m4e2mr0ui3e8a215n4g", &Alphabet::STANDARD),
+            Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g")
+        );
+    }
+
+    #[test]
+    fn round_trips_custom_alphabets() {
+        let factory = Factory::deterministic(7);
+        let mut reversed = *b"0123456789abcdefghijklmnopqrstuv";
+
+        reversed.reverse();
+
+        let reversed = Alphabet::new(reversed).unwrap();
+
+        for alphabet in [&Alphabet::NO_VOWELS, &reversed] {
+            for prefix in ["acct", "dog", "a"] {
+                let id = factory.new_id(prefix).unwrap();
+                let encoded = id.to_string_with_alphabet(alphabet);
+
+                assert_ne!(encoded, id.to_string());
+                assert_eq!(Pxid::parse_with_alphabet(&encoded, alphabet), Ok(id));
+            }
+
+            let nil = Pxid::nil().to_string_with_alphabet(alphabet);
+
+            assert_eq!(Pxid::parse_with_alphabet(&nil, alphabet), Ok(Pxid::nil()));
+        }
+    }
+
+    #[test]
+    fn no_vowels_keeps_binary_order() {
+        let factory = Factory::deterministic(7);
+        let mut ids = (0..100)
+            .map(|_| factory.new_id("acct").unwrap())
+            .collect::<Vec<Pxid>>();
+
+        ids.reverse();
+
+        let mut encoded = ids
+            .iter()
+            .map(|id| id.to_string_with_alphabet(&Alphabet::NO_VOWELS))
+            .collect::<Vec<String>>();
+
+        ids.sort();
+        encoded.sort();
+
+        assert_eq!(
+            encoded,
+            ids.iter()
+                .map(|id| id.to_string_with_alphabet(&Alphabet::NO_VOWELS))
+                .collect::<Vec<String>>()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_alphabets() {
+        let mut chars = *Alphabet::NO_VOWELS
+            .chars()
+            .as_bytes()
+            .first_chunk()
+            .unwrap();
+
+        chars[7] = b'b';
+        assert_eq!(
+            Alphabet::new(chars),
+            Err(Error::DuplicateAlphabetChar('b', 11))
+        );
+
+        chars[7] = b'_';
+        assert_eq!(
+            Alphabet::new(chars),
+            Err(Error::InvalidAlphabetChar(b'_', 7))
+        );
+
+        chars[7] = b' ';
+        assert_eq!(
+            Alphabet::new(chars),
+            Err(Error::InvalidAlphabetChar(b' ', 7))
+        );
+
+        chars[7] = 0xc3;
+        assert_eq!(
+            Alphabet::new(chars),
+            Err(Error::InvalidAlphabetChar(0xc3, 7))
+        );
+    }
+
+    #[test]
+    fn fails_to_parse_under_another_alphabet() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let no_vowels = id.to_string_with_alphabet(&Alphabet::NO_VOWELS);
+
+        assert!(matches!(
+            Pxid::from_str(&no_vowels),
+            Err(Error::Decode(DecodeError::InvalidChar(_, _, _)))
+        ));
+        assert!(matches!(
+            Pxid::parse_with_alphabet(&id.to_string(), &Alphabet::NO_VOWELS),
+            Err(Error::Decode(DecodeError::InvalidChar(_, 'e', _)))
+        ));
+        assert_eq!(
+            Pxid::parse_with_alphabet("acct_9m4z2mr0zz3z8b215né", &Alphabet::NO_VOWELS),
+            Err(Error::Decode(DecodeError::InvalidChar(
+                String::from("9m4z2mr0zz3z8b215né"),
+                'é',
+                18
+            )))
+        );
+    }
+
+    #[test]
+    fn decodes_shared_characters_into_another_id() {
+        let nil = Pxid::parse_with_alphabet(&Pxid::nil().to_string(), &Alphabet::NO_VOWELS);
+
+        assert_ne!(nil, Ok(Pxid::nil()));
+        assert!(nil.is_ok());
+    }
+}
@@ -0,0 +1,217 @@
+//! [Apache Arrow][1] arrays of IDs through [arrow2][2], for `Pxid` columns on
+//! Polars `DataFrames` and Arrow Flight services.
+//!
+//! Arrow array types are foreign to this crate, so conversions are provided
+//! on the `PxidArray` newtype around `Vec<Pxid>`:
+//!
+//! | Arrow Array                  | Layout                                |
+//! | ---------------------------- | ------------------------------------- |
+//! | `FixedSizeBinaryArray`       | Packed binary form, 16 bytes per ID   |
+//! | `Utf8Array<i32>`             | Encoded form, as written by `Display` |
+//!
+//! Null slots are not IDs, arrays holding them fail to convert with
+//! `Error::NullArrayValue`.
+//!
+//! Arrow arrays have inherent `from` constructors taking slices of optional
+//! values, which `FixedSizeBinaryArray::from(..)` resolves to instead of the
+//! `From<PxidArray>` implementations, so convert with `into` instead.
+//!
+//! ```no_run
+//! use arrow2::array::FixedSizeBinaryArray;
+//! use pxid::arrow::PxidArray;
+//!
+//! # fn main() -> pxid::Result<()> {
+//! # let ids = vec![pxid::Pxid::nil()];
+//! let array: FixedSizeBinaryArray = PxidArray::from(ids).into();
+//! let ids = PxidArray::try_from(&array)?.into_inner();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [1]: https://arrow.apache.org
+//! [2]: https://github.com/jorgecarleitao/arrow2
+use arrow2::array::{FixedSizeBinaryArray, Utf8Array};
+use arrow2::datatypes::DataType;
+
+use crate::error::Error;
+use crate::id::{Bytes, Pxid, BINARY_LENGTH};
+
+/// Collection of IDs convertible from and into Arrow arrays
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PxidArray(pub Vec<Pxid>);
+
+impl PxidArray {
+    /// Retrieves the IDs of this array
+    #[inline]
+    pub fn into_inner(self) -> Vec<Pxid> {
+        self.0
+    }
+}
+
+impl From<Vec<Pxid>> for PxidArray {
+    fn from(value: Vec<Pxid>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<PxidArray> for Vec<Pxid> {
+    fn from(value: PxidArray) -> Self {
+        value.0
+    }
+}
+
+impl From<PxidArray> for FixedSizeBinaryArray {
+    /// Builds a `FixedSizeBinaryArray` with element size 16 holding the
+    /// packed binary form of each ID
+    fn from(value: PxidArray) -> Self {
+        let values = value
+            .0
+            .iter()
+            .flat_map(|id| Bytes::from(*id))
+            .collect::<Vec<u8>>();
+
+        FixedSizeBinaryArray::new(
+            DataType::FixedSizeBinary(BINARY_LENGTH),
+            values.into(),
+            None,
+        )
+    }
+}
+
+impl TryFrom<&FixedSizeBinaryArray> for PxidArray {
+    type Error = Error;
+
+    /// Decodes the packed binary form of each element, validated as done by
+    /// `Pxid::from_packed`. Arrays with an element size other than 16 fail
+    /// with `Error::InvalidBinaryLength`.
+    fn try_from(value: &FixedSizeBinaryArray) -> Result<Self, Self::Error> {
+        if value.size() != BINARY_LENGTH {
+            return Err(Error::InvalidBinaryLength(value.size()));
+        }
+
+        value
+            .iter()
+            .enumerate()
+            .map(|(idx, bytes)| {
+                let bytes: Bytes = bytes
+                    .ok_or(Error::NullArrayValue(idx))?
+                    .try_into()
+                    .map_err(|_| Error::InvalidBinaryLength(BINARY_LENGTH))?;
+
+                Pxid::from_packed(&bytes)
+            })
+            .collect::<Result<Vec<Pxid>, Error>>()
+            .map(Self)
+    }
+}
+
+impl TryFrom<FixedSizeBinaryArray> for PxidArray {
+    type Error = Error;
+
+    fn try_from(value: FixedSizeBinaryArray) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+impl From<PxidArray> for Utf8Array<i32> {
+    /// Builds a `Utf8Array` holding the encoded form of each ID
+    fn from(value: PxidArray) -> Self {
+        Utf8Array::<i32>::from_iter_values(value.0.iter().map(Pxid::to_string))
+    }
+}
+
+impl TryFrom<&Utf8Array<i32>> for PxidArray {
+    type Error = Error;
+
+    /// Decodes each element as done by `FromStr`
+    fn try_from(value: &Utf8Array<i32>) -> Result<Self, Self::Error> {
+        value
+            .iter()
+            .enumerate()
+            .map(|(idx, s)| s.ok_or(Error::NullArrayValue(idx)).and_then(str::parse))
+            .collect::<Result<Vec<Pxid>, Error>>()
+            .map(Self)
+    }
+}
+
+impl TryFrom<Utf8Array<i32>> for PxidArray {
+    type Error = Error;
+
+    fn try_from(value: Utf8Array<i32>) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow2::array::{MutableFixedSizeBinaryArray, MutableUtf8Array};
+
+    use crate::error::DecodeError;
+    use crate::factory::Factory;
+
+    use super::*;
+
+    fn ids() -> Vec<Pxid> {
+        let factory = Factory::deterministic(7);
+
+        ["acct", "dog", "a", "user"]
+            .into_iter()
+            .map(|prefix| factory.new_id(prefix).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_fixed_size_binary_arrays() {
+        let array: FixedSizeBinaryArray = PxidArray::from(ids()).into();
+        let empty: FixedSizeBinaryArray = PxidArray::default().into();
+
+        assert_eq!(array.size(), 16);
+        assert_eq!(array.len(), 4);
+        assert_eq!(array.value(1), Bytes::from(ids()[1]));
+        assert_eq!(PxidArray::try_from(&array).unwrap().into_inner(), ids());
+        assert_eq!(empty.len(), 0);
+    }
+
+    #[test]
+    fn round_trips_utf8_arrays() {
+        let array: Utf8Array<i32> = PxidArray::from(ids()).into();
+
+        assert_eq!(array.value(0), ids()[0].to_string());
+        assert_eq!(PxidArray::try_from(array).unwrap(), PxidArray(ids()));
+    }
+
+    #[test]
+    fn rejects_invalid_arrays() {
+        let array =
+            FixedSizeBinaryArray::new(DataType::FixedSizeBinary(12), vec![0; 24].into(), None);
+
+        assert_eq!(
+            PxidArray::try_from(&array),
+            Err(Error::InvalidBinaryLength(12))
+        );
+
+        let mut array = MutableFixedSizeBinaryArray::new(16);
+
+        array.push(Some(Bytes::from(ids()[0])));
+        array.push::<Bytes>(None);
+
+        let array: FixedSizeBinaryArray = array.into();
+
+        assert_eq!(PxidArray::try_from(array), Err(Error::NullArrayValue(1)));
+
+        let mut array = MutableUtf8Array::<i32>::new();
+
+        array.push(Some("acct_9m4e2mr0ui3e8a215n4g"));
+        array.push(Some("acct"));
+
+        let array: Utf8Array<i32> = array.into();
+
+        assert_eq!(
+            PxidArray::try_from(array),
+            Err(Error::Decode(DecodeError::InvalidLength(
+                String::from("acct"),
+                4
+            )))
+        );
+    }
+}
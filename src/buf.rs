@@ -0,0 +1,72 @@
+//! Reading and writing IDs from [bytes][1] buffers, as used by network code
+//! built on `tokio`.
+//!
+//! [1]: https://github.com/tokio-rs/bytes
+use bytes::{Buf, BufMut};
+
+use crate::error::Error;
+use crate::id::{Bytes, Pxid, BINARY_LENGTH};
+use crate::Result;
+
+impl Pxid {
+    /// Writes the 16 bytes packed binary form of this Pxid into `buf`
+    #[inline]
+    pub fn put(&self, buf: &mut impl BufMut) {
+        buf.put_slice(&self.0);
+    }
+
+    /// Reads a Pxid from the next 16 bytes of `buf`, validated as done by
+    /// `Pxid::from_packed`.
+    ///
+    /// Nothing is consumed when `buf` holds less than 16 bytes, which fails
+    /// with `Error::InvalidBinaryLength`, otherwise the 16 bytes are
+    /// consumed even if validation fails.
+    pub fn get(buf: &mut impl Buf) -> Result<Pxid> {
+        if buf.remaining() < BINARY_LENGTH {
+            return Err(Error::InvalidBinaryLength(buf.remaining()));
+        }
+
+        let mut bytes: Bytes = [0; BINARY_LENGTH];
+
+        buf.copy_to_slice(&mut bytes);
+        Self::from_packed(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bytes::BytesMut;
+
+    use super::*;
+
+    #[test]
+    fn round_trips_through_bytes_mut() {
+        let account = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let dog = Pxid::from_str("dog_9m4e2mr0ui3e8a215n4g").unwrap();
+        let mut buf = BytesMut::new();
+
+        account.put(&mut buf);
+        dog.put(&mut buf);
+        buf.put_u8(0xff);
+
+        assert_eq!(buf.len(), 33);
+        assert_eq!(buf[..16], account.0);
+
+        let mut buf = buf.freeze();
+
+        assert_eq!(Pxid::get(&mut buf), Ok(account));
+        assert_eq!(Pxid::get(&mut buf), Ok(dog));
+        assert_eq!(Pxid::get(&mut buf), Err(Error::InvalidBinaryLength(1)));
+        assert_eq!(buf.remaining(), 1);
+    }
+
+    #[test]
+    fn rejects_invalid_prefixes() {
+        let mut buf = &[0xff_u8; 16][..];
+
+        assert!(Pxid::get(&mut buf).is_err());
+        assert_eq!(buf.remaining(), 0);
+    }
+}
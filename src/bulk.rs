@@ -0,0 +1,289 @@
+//! Decoding, encoding and validation of large batches of IDs.
+//!
+//! With the `rayon` feature enabled, `par_decode`, `par_encode` and
+//! `par_validate` spread the work across the [rayon][1] global thread pool.
+//! Inputs are split in chunks of at least `DEFAULT_MIN_CHUNK_SIZE` items, so
+//! small batches stay on a single thread, use the `*_with_chunk_size`
+//! variants to tune it. Results keep the order of the inputs.
+//!
+//! ```no_run
+//! use pxid::bulk;
+//!
+//! # let inputs: Vec<String> = Vec::new();
+//! let summary = bulk::validate(&inputs);
+//!
+//! println!("{} of {} IDs are valid", summary.valid, summary.total);
+//! ```
+//!
+//! [1]: https://github.com/rayon-rs/rayon
+use std::collections::HashMap;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::error::{DecodeError, DecodeErrorKind};
+use crate::id::Pxid;
+
+/// Min amount of items handled by each parallel task unless configured
+/// otherwise
+pub const DEFAULT_MIN_CHUNK_SIZE: usize = 4096;
+
+/// Counts of valid and invalid IDs on a batch, the latter grouped by the
+/// `DecodeErrorKind` of their failure
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationSummary {
+    /// Amount of inputs checked
+    pub total: usize,
+
+    /// Amount of inputs decoded into a Pxid
+    pub valid: usize,
+
+    /// Amount of inputs failing to decode for each `DecodeErrorKind`
+    pub errors: HashMap<DecodeErrorKind, usize>,
+}
+
+impl ValidationSummary {
+    /// Retrieves the amount of inputs failing to decode
+    pub fn invalid(&self) -> usize {
+        self.total - self.valid
+    }
+
+    /// Retrieves the amount of inputs failing to decode with `kind`
+    pub fn count(&self, kind: DecodeErrorKind) -> usize {
+        self.errors.get(&kind).copied().unwrap_or_default()
+    }
+
+    fn record(mut self, result: Result<Pxid, DecodeError>) -> Self {
+        self.total += 1;
+
+        match result {
+            Ok(_) => self.valid += 1,
+            Err(err) => *self.errors.entry(err.kind()).or_default() += 1,
+        }
+
+        self
+    }
+
+    #[cfg_attr(not(feature = "rayon"), allow(dead_code))]
+    fn merge(mut self, other: Self) -> Self {
+        self.total += other.total;
+        self.valid += other.valid;
+
+        for (kind, count) in other.errors {
+            *self.errors.entry(kind).or_default() += count;
+        }
+
+        self
+    }
+}
+
+/// Decodes each input as done by `FromStr`.
+///
+/// Every failure is retrieved along with the index of its input, so a
+/// single pass reports all invalid inputs.
+pub fn decode<S: AsRef<str>>(inputs: &[S]) -> Result<Vec<Pxid>, Vec<(usize, DecodeError)>> {
+    collect_decoded(inputs.iter().map(|input| decode_one(input.as_ref())))
+}
+
+/// Encodes each ID into its string form, as written by `Display`
+pub fn encode(ids: &[Pxid]) -> Vec<String> {
+    ids.iter().map(Pxid::to_string).collect()
+}
+
+/// Decodes each input as done by `FromStr` and counts the failures by kind
+pub fn validate<S: AsRef<str>>(inputs: &[S]) -> ValidationSummary {
+    inputs
+        .iter()
+        .map(|input| decode_one(input.as_ref()))
+        .fold(ValidationSummary::default(), ValidationSummary::record)
+}
+
+/// Parallel version of `decode`, using chunks of at least
+/// `DEFAULT_MIN_CHUNK_SIZE` inputs
+#[cfg(feature = "rayon")]
+pub fn par_decode<S: AsRef<str> + Sync>(
+    inputs: &[S],
+) -> Result<Vec<Pxid>, Vec<(usize, DecodeError)>> {
+    par_decode_with_chunk_size(inputs, DEFAULT_MIN_CHUNK_SIZE)
+}
+
+/// Parallel version of `decode`, using chunks of at least `min_chunk_size`
+/// inputs
+#[cfg(feature = "rayon")]
+pub fn par_decode_with_chunk_size<S: AsRef<str> + Sync>(
+    inputs: &[S],
+    min_chunk_size: usize,
+) -> Result<Vec<Pxid>, Vec<(usize, DecodeError)>> {
+    let results: Vec<Result<Pxid, DecodeError>> = inputs
+        .par_iter()
+        .with_min_len(min_chunk_size.max(1))
+        .map(|input| decode_one(input.as_ref()))
+        .collect();
+
+    collect_decoded(results)
+}
+
+/// Parallel version of `encode`, using chunks of at least
+/// `DEFAULT_MIN_CHUNK_SIZE` IDs
+#[cfg(feature = "rayon")]
+pub fn par_encode(ids: &[Pxid]) -> Vec<String> {
+    par_encode_with_chunk_size(ids, DEFAULT_MIN_CHUNK_SIZE)
+}
+
+/// Parallel version of `encode`, using chunks of at least `min_chunk_size`
+/// IDs
+#[cfg(feature = "rayon")]
+pub fn par_encode_with_chunk_size(ids: &[Pxid], min_chunk_size: usize) -> Vec<String> {
+    ids.par_iter()
+        .with_min_len(min_chunk_size.max(1))
+        .map(Pxid::to_string)
+        .collect()
+}
+
+/// Parallel version of `validate`, using chunks of at least
+/// `DEFAULT_MIN_CHUNK_SIZE` inputs
+#[cfg(feature = "rayon")]
+pub fn par_validate<S: AsRef<str> + Sync>(inputs: &[S]) -> ValidationSummary {
+    par_validate_with_chunk_size(inputs, DEFAULT_MIN_CHUNK_SIZE)
+}
+
+/// Parallel version of `validate`, using chunks of at least
+/// `min_chunk_size` inputs
+#[cfg(feature = "rayon")]
+pub fn par_validate_with_chunk_size<S: AsRef<str> + Sync>(
+    inputs: &[S],
+    min_chunk_size: usize,
+) -> ValidationSummary {
+    inputs
+        .par_iter()
+        .with_min_len(min_chunk_size.max(1))
+        .map(|input| decode_one(input.as_ref()))
+        .fold(ValidationSummary::default, ValidationSummary::record)
+        .reduce(ValidationSummary::default, ValidationSummary::merge)
+}
+
+#[inline]
+fn decode_one(input: &str) -> Result<Pxid, DecodeError> {
+    Pxid::decode_str(input)
+}
+
+/// Retrieves the decoded IDs, or every failure along with its index if any
+/// input failed to decode
+fn collect_decoded(
+    results: impl IntoIterator<Item = Result<Pxid, DecodeError>>,
+) -> Result<Vec<Pxid>, Vec<(usize, DecodeError)>> {
+    let results = results.into_iter();
+    let mut ids = Vec::with_capacity(results.size_hint().0);
+    let mut errors = Vec::new();
+
+    for (idx, result) in results.enumerate() {
+        match result {
+            Ok(id) if errors.is_empty() => ids.push(id),
+            Ok(_) => {}
+            Err(err) => errors.push((idx, err)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(ids)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::factory::Factory;
+
+    use super::*;
+
+    /// Encoded IDs with an invalid input every 7 items, cycling through
+    /// failures of different kinds
+    fn mixed_inputs(count: usize) -> Vec<String> {
+        let factory = Factory::deterministic(7);
+        let invalid = [
+            "acct",
+            "acct-9m4e2mr0ui3e8a215n4g",
+            "acct_9m4e2mr0ui3e8a215n4!",
+            "accounts_9m4e2mr0ui3e8a2",
+        ];
+
+        (0..count)
+            .map(|idx| match idx % 7 {
+                3 => invalid[(idx / 7) % invalid.len()].to_string(),
+                _ => factory.new_id("acct").unwrap().to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn decodes_in_order() {
+        let factory = Factory::deterministic(7);
+        let ids = (0..100)
+            .map(|_| factory.new_id("dog").unwrap())
+            .collect::<Vec<Pxid>>();
+        let encoded = encode(&ids);
+
+        assert_eq!(encoded[0], ids[0].to_string());
+        assert_eq!(decode(&encoded), Ok(ids));
+        assert_eq!(decode::<String>(&[]), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn collects_every_error_with_its_index() {
+        let inputs = mixed_inputs(70);
+        let errors = decode(&inputs).unwrap_err();
+
+        assert_eq!(
+            errors.iter().map(|(idx, _)| *idx).collect::<Vec<usize>>(),
+            (0..70).filter(|idx| idx % 7 == 3).collect::<Vec<usize>>()
+        );
+
+        for (idx, err) in &errors {
+            assert_eq!(Err(err), Pxid::decode_str(&inputs[*idx]).as_ref());
+        }
+    }
+
+    #[test]
+    fn summarizes_errors_by_kind() {
+        let summary = validate(&mixed_inputs(70));
+
+        assert_eq!(summary.total, 70);
+        assert_eq!(summary.valid, 60);
+        assert_eq!(summary.invalid(), 10);
+        assert_eq!(summary.count(DecodeErrorKind::BadLength), 3);
+        assert_eq!(summary.count(DecodeErrorKind::MissingPrefix), 3);
+        assert_eq!(summary.count(DecodeErrorKind::BadChar), 2);
+        assert_eq!(summary.count(DecodeErrorKind::BadPrefixLength), 2);
+        assert_eq!(summary.count(DecodeErrorKind::BadChecksum), 0);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_results_match_sequential_ones() {
+        let inputs = mixed_inputs(100_000);
+        let valid = inputs
+            .iter()
+            .filter_map(|input| Pxid::decode_str(input).ok())
+            .collect::<Vec<Pxid>>();
+
+        for chunk_size in [1, 64, DEFAULT_MIN_CHUNK_SIZE, 1_000_000] {
+            assert_eq!(
+                par_decode_with_chunk_size(&inputs, chunk_size),
+                decode(&inputs)
+            );
+            assert_eq!(
+                par_validate_with_chunk_size(&inputs, chunk_size),
+                validate(&inputs)
+            );
+            assert_eq!(
+                par_encode_with_chunk_size(&valid, chunk_size),
+                encode(&valid)
+            );
+        }
+
+        assert_eq!(par_decode(&encode(&valid)), Ok(valid.clone()));
+        assert_eq!(par_encode(&valid), encode(&valid));
+        assert_eq!(par_validate(&inputs).valid, valid.len());
+    }
+}
@@ -0,0 +1,287 @@
+//! Collision probability estimates for capacity planning, answering how many
+//! IDs per second can be safely generated on each host.
+//!
+//! # Model
+//!
+//! IDs can only collide when they share the Prefix and Timestamp, so
+//! estimates are computed for IDs with the same Prefix generated within the
+//! same second, assuming:
+//!
+//! 1. Counters are drawn uniformly from the `2^24` Counter values, so IDs
+//!    from the same host collide when they draw the same Counter.
+//! 2. Each host uses a uniformly distributed Machine ID and Process ID out of
+//!    `2^40` values, so IDs from different hosts collide when they share
+//!    Machine ID, Process ID and Counter.
+//! 3. Seconds are independent from each other.
+//!
+//! The expected amount of colliding pairs `λ` follows the birthday bound
+//! over these spaces and the collision probability is `1 - e^-λ`. Rates
+//! above `2^24` IDs per second on a host exceed the Counter space and always
+//! collide.
+//!
+//! Counters are not drawn uniformly in practice: `Pxid::new` and every
+//! non-deterministic `Factory` take consecutive values from the Counter
+//! shared by the whole process, starting at a random seed. IDs from the same
+//! process never collide while the process generates at most `2^24` IDs per
+//! second across all of them, and IDs from different processes sharing
+//! Machine ID and Process ID collide less often than with uniform Counters,
+//! given consecutive ranges only overlap at their ends. Estimates are
+//! therefore an upper bound for IDs generated through the shared Counter,
+//! with `ids_per_second` being the rate of the whole process rather than
+//! the one of each `Factory`. Deterministic factories hold a Counter of
+//! their own and are left out of these estimates.
+//!
+//! ```no_run
+//! use pxid::capacity::{collision_probability, CapacityReport};
+//!
+//! assert!(collision_probability(1_000, 20, 86_400) < 0.01);
+//! println!("{}", CapacityReport::new(1_000, 20, 86_400));
+//! ```
+// Rates and durations are far below 2^52 in practice, and results are
+// estimates anyway
+#![allow(clippy::cast_precision_loss)]
+
+use std::fmt::{self, Display};
+
+use crate::factory::COUNTER_MAX;
+
+/// Amount of distinct Counter values
+const COUNTER_SPACE: u64 = COUNTER_MAX as u64 + 1;
+
+/// Amount of distinct Machine ID and Process ID pairs
+const ORIGIN_SPACE: f64 = (1_u64 << 40) as f64;
+
+/// Retrieves the probability of at least one collision among IDs generated
+/// at `ids_per_second` on each of the `hosts` during `seconds`.
+///
+/// The probability is computed as `-expm1(-λ)`, which stays accurate for
+/// tiny probabilities where `1 - e^-λ` rounds to `0`.
+pub fn collision_probability(ids_per_second: u64, hosts: u32, seconds: u64) -> f64 {
+    if hosts == 0 || seconds == 0 {
+        return 0.0;
+    }
+
+    if ids_per_second > COUNTER_SPACE {
+        return 1.0;
+    }
+
+    let expected = expected_collisions(ids_per_second, hosts, seconds);
+
+    if expected <= 0.0 {
+        return 0.0;
+    }
+
+    -(-expected).exp_m1()
+}
+
+/// Retrieves the probability of at least one collision among IDs generated
+/// at `ids_per_second` on a single host within one second, the birthday
+/// bound over the `2^24` Counter values.
+///
+/// Shorthand for `collision_probability(ids_per_second, 1, 1)`.
+#[inline]
+pub fn collision_estimate(ids_per_second: u64) -> f64 {
+    collision_probability(ids_per_second, 1, 1)
+}
+
+/// Retrieves the expected amount of colliding pairs among IDs generated at
+/// `ids_per_second` on each of the `hosts` during `seconds`
+pub fn expected_collisions(ids_per_second: u64, hosts: u32, seconds: u64) -> f64 {
+    let rate = ids_per_second as f64;
+    let hosts = f64::from(hosts);
+    let same_host_pairs = hosts * rate * (rate - 1.0).max(0.0) / 2.0;
+    let cross_host_pairs = hosts * (hosts - 1.0).max(0.0) / 2.0 * rate * rate;
+    let counter_space = COUNTER_SPACE as f64;
+
+    (same_host_pairs / counter_space + cross_host_pairs / (counter_space * ORIGIN_SPACE))
+        * seconds as f64
+}
+
+/// Retrieves the max amount of IDs per second each of the `hosts` can
+/// generate keeping the probability of a collision within a second at or
+/// below `target_p`.
+///
+/// Rates are capped at `2^24`, the amount of distinct Counter values.
+pub fn max_rate_for_probability(target_p: f64, hosts: u32) -> u64 {
+    let within_target = |rate: u64| collision_probability(rate, hosts, 1) <= target_p;
+
+    if within_target(COUNTER_SPACE) {
+        return COUNTER_SPACE;
+    }
+
+    if !within_target(0) {
+        return 0;
+    }
+
+    // `low` is always within target and `high` never is
+    let mut low = 0;
+    let mut high = COUNTER_SPACE;
+
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+
+        if within_target(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    low
+}
+
+/// Collision estimate for a deployment, rendered by `Display` as a Markdown
+/// table suitable for design documents
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CapacityReport {
+    pub ids_per_second: u64,
+    pub hosts: u32,
+    pub seconds: u64,
+    /// Expected amount of colliding pairs
+    pub expected_collisions: f64,
+    /// Probability of at least one collision
+    pub probability: f64,
+}
+
+impl CapacityReport {
+    /// Estimates collisions among IDs generated at `ids_per_second` on each
+    /// of the `hosts` during `seconds`
+    pub fn new(ids_per_second: u64, hosts: u32, seconds: u64) -> Self {
+        Self {
+            ids_per_second,
+            hosts,
+            seconds,
+            expected_collisions: expected_collisions(ids_per_second, hosts, seconds),
+            probability: collision_probability(ids_per_second, hosts, seconds),
+        }
+    }
+}
+
+impl Display for CapacityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "| Parameter | Value |")?;
+        writeln!(f, "| --- | --- |")?;
+        writeln!(f, "| IDs per second per host | {} |", self.ids_per_second)?;
+        writeln!(f, "| Hosts | {} |", self.hosts)?;
+        writeln!(f, "| Window | {} s |", self.seconds)?;
+        writeln!(
+            f,
+            "| Expected colliding pairs | {:.3e} |",
+            self.expected_collisions
+        )?;
+        writeln!(f, "| Collision probability | {:.3e} |", self.probability)
+    }
+}
+
+#[cfg(test)]
+// Saturated and zero probabilities are returned as exact values
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saturates_when_exceeding_counter_space() {
+        assert_eq!(collision_probability(1 << 24, 1, 1), 1.0);
+        assert_eq!(collision_probability((1 << 24) + 1, 1, 1), 1.0);
+        assert_eq!(collision_probability(u64::MAX, 1, 1), 1.0);
+    }
+
+    #[test]
+    fn matches_birthday_bound() {
+        // 1 - e^-(4096 * 4095 / 2 / 2^24)
+        let expected = 0.393_395_296_381_005_2;
+
+        assert!((collision_probability(4096, 1, 1) - expected).abs() < 1e-12);
+        assert!((expected_collisions(4096, 1, 1) - 0.499_877_929_687_5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn estimates_collisions_within_a_second() {
+        assert_eq!(collision_estimate(0), 0.0);
+        assert_eq!(collision_estimate(1), 0.0);
+        assert!(collision_estimate(10) < 1e-5);
+        assert!(collision_estimate(100) < 1e-3);
+        assert_eq!(collision_estimate(4096), collision_probability(4096, 1, 1));
+        assert!(collision_estimate(100_000) > 0.99);
+        assert!(collision_estimate(1 << 24) > 1.0 - 1e-12);
+        assert_eq!(collision_estimate((1 << 24) + 1), 1.0);
+        assert_eq!(collision_estimate(u64::MAX), 1.0);
+    }
+
+    #[test]
+    fn handles_edge_cases() {
+        assert_eq!(collision_probability(4096, 0, 1), 0.0);
+        assert_eq!(collision_probability(4096, 1, 0), 0.0);
+        assert_eq!(collision_probability(u64::MAX, 0, 1), 0.0);
+        assert_eq!(collision_probability(0, 1_000, 1), 0.0);
+        assert_eq!(collision_probability(1, 1, 1_000), 0.0);
+    }
+
+    #[test]
+    fn keeps_tiny_probabilities() {
+        let probability = collision_probability(1, 2, 1);
+        let expected = 1.0 / (COUNTER_SPACE as f64 * ORIGIN_SPACE);
+
+        assert!(probability > 0.0);
+        assert!((probability - expected).abs() / expected < 1e-9);
+    }
+
+    #[test]
+    fn grows_monotonically() {
+        let rates = [0, 1, 2, 10, 100, 1_000, 4_096, 100_000, 1 << 24];
+        let hosts = [0, 1, 2, 10, 1_000, u32::MAX];
+        let seconds = [0, 1, 60, 86_400, u64::MAX];
+
+        for window in rates.windows(2) {
+            assert!(
+                collision_probability(window[0], 10, 60)
+                    <= collision_probability(window[1], 10, 60)
+            );
+        }
+
+        for window in hosts.windows(2) {
+            assert!(
+                collision_probability(100, window[0], 60)
+                    <= collision_probability(100, window[1], 60)
+            );
+        }
+
+        for window in seconds.windows(2) {
+            assert!(
+                collision_probability(100, 10, window[0])
+                    <= collision_probability(100, 10, window[1])
+            );
+        }
+    }
+
+    #[test]
+    fn finds_max_rate_for_probability() {
+        let rate = max_rate_for_probability(0.5, 1);
+
+        assert_eq!(rate, 4823);
+        assert!(collision_probability(rate, 1, 1) <= 0.5);
+        assert!(collision_probability(rate + 1, 1, 1) > 0.5);
+        assert!(max_rate_for_probability(0.5, 100) <= rate);
+        assert_eq!(max_rate_for_probability(1.0, 1), 1 << 24);
+        assert_eq!(max_rate_for_probability(0.0, 1), 1);
+        assert_eq!(max_rate_for_probability(0.0, 2), 0);
+        assert_eq!(max_rate_for_probability(f64::NAN, 1), 0);
+        assert_eq!(max_rate_for_probability(1e-9, 0), 1 << 24);
+    }
+
+    #[test]
+    fn renders_report_as_markdown_table() {
+        assert_eq!(
+            CapacityReport::new(4096, 1, 1).to_string(),
+            "\
+| Parameter | Value |
+| --- | --- |
+| IDs per second per host | 4096 |
+| Hosts | 1 |
+| Window | 1 s |
+| Expected colliding pairs | 4.999e-1 |
+| Collision probability | 3.934e-1 |
+"
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! Registry of the prefixes used across services along with the entity they
+//! identify and the team owning them, exportable as documentation.
+//!
+//! ```no_run
+//! use pxid::{PrefixCatalog, PrefixEntry};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let id: pxid::Pxid = "acct_9m4e2mr0ui3e8a215n4g".parse()?;
+//! let mut catalog = PrefixCatalog::new();
+//!
+//! catalog.register(PrefixEntry {
+//!     prefix: "acct",
+//!     entity: "Account",
+//!     owner: "billing-team",
+//!     since: "2023-01",
+//! })?;
+//!
+//! if let Some(entry) = catalog.lookup(&id) {
+//!     println!("{} is an {}", id, entry); // acct_... is an Account id owned by billing-team
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Write};
+use std::str::from_utf8;
+
+use thiserror::Error;
+
+use crate::error::{DecodeError, Error};
+use crate::id::{Pxid, PREFIX_LENGTH};
+use crate::Result;
+
+/// Metadata describing a registered prefix
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PrefixEntry {
+    pub prefix: &'static str,
+    /// Name of the entity identified by IDs using this prefix
+    pub entity: &'static str,
+    /// Team or service owning the entity
+    pub owner: &'static str,
+    /// Date when the prefix started being used
+    pub since: &'static str,
+}
+
+impl Display for PrefixEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} id owned by {}", self.entity, self.owner)
+    }
+}
+
+/// Prefixes holding different entries on the catalogs being merged, sorted
+/// by prefix
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error("Cannot merge prefix catalogs. Conflicting prefixes: {}.", .0.join(", "))]
+pub struct ConflictError(pub Vec<String>);
+
+/// Catalog of `PrefixEntry` values by prefix
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PrefixCatalog {
+    entries: BTreeMap<&'static str, PrefixEntry>,
+}
+
+impl PrefixCatalog {
+    /// Creates an empty `PrefixCatalog`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a prefix entry. Registering the same entry again has no
+    /// effect, but registering a different entry for a registered prefix
+    /// fails with `Error::DuplicatePrefix`.
+    pub fn register(&mut self, entry: PrefixEntry) -> Result<()> {
+        if entry.prefix.is_empty() {
+            return Err(Error::Decode(DecodeError::MissingPrefix(
+                entry.prefix.to_string(),
+            )));
+        }
+
+        if entry.prefix.len() > PREFIX_LENGTH {
+            return Err(Error::Decode(DecodeError::InvalidPrefixLength(
+                entry.prefix.to_string(),
+                entry.prefix.len(),
+            )));
+        }
+
+        match self.entries.get(entry.prefix) {
+            Some(registered) if *registered != entry => {
+                Err(Error::DuplicatePrefix(entry.prefix.to_string()))
+            }
+            _ => {
+                self.entries.insert(entry.prefix, entry);
+                Ok(())
+            }
+        }
+    }
+
+    /// Merges the entries from `other` into this catalog. Entries registered
+    /// on both catalogs are only allowed if they are equal, otherwise no
+    /// entry is merged and every conflicting prefix is reported.
+    pub fn merge(&mut self, other: PrefixCatalog) -> std::result::Result<(), ConflictError> {
+        let conflicts = other
+            .entries
+            .iter()
+            .filter(|(prefix, entry)| {
+                self.entries
+                    .get(*prefix)
+                    .is_some_and(|registered| registered != *entry)
+            })
+            .map(|(prefix, _)| prefix.to_string())
+            .collect::<Vec<String>>();
+
+        if !conflicts.is_empty() {
+            return Err(ConflictError(conflicts));
+        }
+
+        self.entries.extend(other.entries);
+
+        Ok(())
+    }
+
+    /// Retrieves the entry for the prefix of the provided `Pxid`
+    pub fn lookup(&self, id: &Pxid) -> Option<&PrefixEntry> {
+        let prefix_bytes = id.prefix_bytes();
+        let prefix = from_utf8(&prefix_bytes).ok()?.trim_end_matches('\0');
+
+        self.entries.get(prefix)
+    }
+
+    /// Retrieves the registered entries sorted by prefix
+    pub fn entries(&self) -> impl Iterator<Item = &PrefixEntry> {
+        self.entries.values()
+    }
+
+    /// Renders the registered entries as a Markdown table sorted by prefix
+    pub fn to_markdown_table(&self) -> String {
+        let mut table =
+            String::from("| Prefix | Entity | Owner | Since |\n| --- | --- | --- | --- |\n");
+
+        for entry in self.entries() {
+            let _ = writeln!(
+                table,
+                "| `{}` | {} | {} | {} |",
+                entry.prefix,
+                escape_cell(entry.entity),
+                escape_cell(entry.owner),
+                escape_cell(entry.since)
+            );
+        }
+
+        table
+    }
+
+    /// Renders the registered entries as a JSON array sorted by prefix
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Value {
+        // Entries only hold strings, which always serialize
+        serde_json::to_value(self.entries().collect::<Vec<&PrefixEntry>>()).unwrap_or_default()
+    }
+}
+
+/// Escapes pipes so cell contents don't split Markdown table columns
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    const ACCOUNT: PrefixEntry = PrefixEntry {
+        prefix: "acct",
+        entity: "Account",
+        owner: "billing-team",
+        since: "2023-01",
+    };
+
+    const USER: PrefixEntry = PrefixEntry {
+        prefix: "u",
+        entity: "User",
+        owner: "identity-team",
+        since: "2022-06",
+    };
+
+    fn catalog(entries: &[PrefixEntry]) -> PrefixCatalog {
+        let mut catalog = PrefixCatalog::new();
+
+        for entry in entries {
+            catalog.register(*entry).unwrap();
+        }
+
+        catalog
+    }
+
+    #[test]
+    fn registers_entries() {
+        let mut catalog = catalog(&[ACCOUNT]);
+
+        assert_eq!(catalog.register(ACCOUNT), Ok(()));
+        assert_eq!(
+            catalog.register(PrefixEntry {
+                owner: "payments-team",
+                ..ACCOUNT
+            }),
+            Err(Error::DuplicatePrefix(String::from("acct")))
+        );
+        assert_eq!(
+            catalog.register(PrefixEntry {
+                prefix: "accounts",
+                ..ACCOUNT
+            }),
+            Err(Error::Decode(DecodeError::InvalidPrefixLength(
+                String::from("accounts"),
+                8
+            )))
+        );
+        assert_eq!(
+            catalog.register(PrefixEntry {
+                prefix: "",
+                ..ACCOUNT
+            }),
+            Err(Error::Decode(DecodeError::MissingPrefix(String::new())))
+        );
+        assert_eq!(catalog.entries().collect::<Vec<_>>(), vec![&ACCOUNT]);
+    }
+
+    #[test]
+    fn merges_catalogs() {
+        let mut merged = catalog(&[ACCOUNT]);
+
+        assert_eq!(merged.merge(catalog(&[ACCOUNT, USER])), Ok(()));
+        assert_eq!(merged, catalog(&[USER, ACCOUNT]));
+    }
+
+    #[test]
+    fn reports_conflicts_on_merge() {
+        let mut merged = catalog(&[ACCOUNT, USER]);
+        let other = catalog(&[
+            PrefixEntry {
+                owner: "payments-team",
+                ..ACCOUNT
+            },
+            PrefixEntry {
+                entity: "Member",
+                ..USER
+            },
+            PrefixEntry {
+                prefix: "ordr",
+                entity: "Order",
+                owner: "checkout-team",
+                since: "2023-03",
+            },
+        ]);
+        let err = merged.merge(other).unwrap_err();
+
+        assert_eq!(
+            err,
+            ConflictError(vec![String::from("acct"), String::from("u")])
+        );
+        assert_eq!(
+            err.to_string(),
+            "Cannot merge prefix catalogs. Conflicting prefixes: acct, u."
+        );
+        assert_eq!(merged, catalog(&[ACCOUNT, USER]));
+    }
+
+    #[test]
+    fn looks_up_entries_by_id() {
+        let catalog = catalog(&[ACCOUNT, USER]);
+        let account = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let user = Pxid::new("u").unwrap();
+        let unknown = Pxid::new("us").unwrap();
+
+        assert_eq!(catalog.lookup(&account), Some(&ACCOUNT));
+        assert_eq!(catalog.lookup(&user), Some(&USER));
+        assert_eq!(catalog.lookup(&unknown), None);
+        assert_eq!(catalog.lookup(&Pxid::nil()), None);
+        assert_eq!(
+            catalog.lookup(&account).unwrap().to_string(),
+            "Account id owned by billing-team"
+        );
+    }
+
+    #[test]
+    fn exports_markdown_table() {
+        let catalog = catalog(&[
+            ACCOUNT,
+            USER,
+            PrefixEntry {
+                prefix: "ordr",
+                entity: "Order",
+                owner: "checkout|orders",
+                since: "2023-03",
+            },
+        ]);
+
+        assert_eq!(
+            catalog.to_markdown_table(),
+            "\
+| Prefix | Entity | Owner | Since |
+| --- | --- | --- | --- |
+| `acct` | Account | billing-team | 2023-01 |
+| `ordr` | Order | checkout\\|orders | 2023-03 |
+| `u` | User | identity-team | 2022-06 |
+"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn exports_json() {
+        let catalog = catalog(&[USER, ACCOUNT]);
+
+        assert_eq!(
+            catalog.to_json(),
+            serde_json::json!([
+                {
+                    "prefix": "acct",
+                    "entity": "Account",
+                    "owner": "billing-team",
+                    "since": "2023-01",
+                },
+                {
+                    "prefix": "u",
+                    "entity": "User",
+                    "owner": "identity-team",
+                    "since": "2022-06",
+                },
+            ])
+        );
+    }
+}
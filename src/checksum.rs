@@ -0,0 +1,103 @@
+use crate::id::ENCODING_CHARS;
+
+/// Amount of characters in the encoding alphabet, used as the Luhn modulus
+// The alphabet holds 32 characters
+#[allow(clippy::cast_possible_truncation)]
+const MODULUS: u32 = ENCODING_CHARS.len() as u32;
+
+/// Retrieves the position of an encoded character in `ENCODING_CHARS`
+#[inline]
+// Positions are bounded by the 32 characters of the alphabet
+#[allow(clippy::cast_possible_truncation)]
+fn code_point(c: u8) -> Option<u32> {
+    ENCODING_CHARS
+        .iter()
+        .position(|&e| e == c)
+        .map(|pos| pos as u32)
+}
+
+/// Computes the Luhn mod 32 check character for an encoded Pxid, made of
+/// the `prefix` bytes, including the separator, and the encoded `xid`.
+///
+/// Prefix bytes are not part of `ENCODING_CHARS`, so each one is fed as two
+/// code points holding its 3 most significant bits and its 5 least
+/// significant bits. Typos on the XID, and typos swapping a prefix letter for
+/// another letter or a prefix digit for another digit, always change the
+/// check character.
+///
+/// Returns `None` if `xid` contains characters outside `ENCODING_CHARS`.
+///
+/// # Reference
+///
+/// Follows the [Luhn mod N algorithm][1] using the Pxid Base32 alphabet.
+///
+/// [1]: https://en.wikipedia.org/wiki/Luhn_mod_N_algorithm
+pub(crate) fn generate(prefix: &[u8], xid: &[u8]) -> Option<u8> {
+    let prefix = prefix
+        .iter()
+        .flat_map(|&b| [u32::from(b >> 5), u32::from(b & 31)])
+        .map(Some);
+    let mut factor = 2;
+    let mut sum = 0;
+
+    for code_point in prefix.chain(xid.iter().map(|&c| code_point(c))).rev() {
+        let mut addend = factor * code_point?;
+
+        factor = if factor == 2 { 1 } else { 2 };
+        addend = (addend / MODULUS) + (addend % MODULUS);
+        sum += addend;
+    }
+
+    let check = (MODULUS - (sum % MODULUS)) % MODULUS;
+
+    ENCODING_CHARS.get(check as usize).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_single_character_substitution() {
+        let encoded = b"9m4e2mr0ui3e8a215n4g";
+        let check = generate(b"acct_", encoded).unwrap();
+
+        for i in 0..encoded.len() {
+            for &c in ENCODING_CHARS {
+                if c == encoded[i] {
+                    continue;
+                }
+
+                let mut corrupted = *encoded;
+                corrupted[i] = c;
+
+                assert_ne!(generate(b"acct_", &corrupted), Some(check));
+            }
+        }
+    }
+
+    #[test]
+    fn detects_prefix_character_substitution() {
+        let xid = b"9m4e2mr0ui3e8a215n4g";
+        let check = generate(b"acct_", xid).unwrap();
+
+        for i in 0..4 {
+            for class in [b"abcdefghijklmnopqrstuvwxyz".as_slice(), b"0123456789"] {
+                for &c in class {
+                    let mut corrupted = *b"acct_";
+
+                    if corrupted[i] == c || !class.contains(&corrupted[i]) {
+                        continue;
+                    }
+
+                    corrupted[i] = c;
+
+                    assert_ne!(generate(&corrupted, xid), Some(check));
+                }
+            }
+        }
+
+        assert_ne!(generate(b"acct-", xid), Some(check));
+        assert_ne!(generate(b"acc\0_", xid), Some(check));
+    }
+}
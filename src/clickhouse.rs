@@ -0,0 +1,274 @@
+//! ClickHouse [RowBinary][1] column support for rows using the
+//! [`clickhouse`][2] crate `Row` derive, which encodes rows through `serde`.
+//!
+//! Two column styles are supported:
+//!
+//! - `FixedString(16)` holding the raw Pxid bytes, through the
+//!   `fixed_string` module used with `#[serde(with = "...")]`
+//! - `FixedString(25)` holding the string form, through `PxidText`. IDs with
+//!   prefixes shorter than 4 bytes are encoded in 22 to 24 characters, the
+//!   remaining bytes are padded with NULs.
+//!
+//! The example is not compiled given `clickhouse` is not a dependency of
+//! this crate.
+//!
+//! ```ignore
+//! #[derive(clickhouse::Row, Deserialize, Serialize)]
+//! struct Event {
+//!     #[serde(with = "pxid::clickhouse::fixed_string")]
+//!     id: Pxid,
+//!     account: PxidText,
+//! }
+//! ```
+//!
+//! [1]: https://clickhouse.com/docs/en/interfaces/formats#rowbinary
+//! [2]: https://crates.io/crates/clickhouse
+use std::fmt::{self, Display};
+use std::ops::Deref;
+use std::str::{from_utf8, FromStr};
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::id::{Pxid, BINARY_LENGTH, ENCODED_LENGTH};
+
+/// Serializes a `Pxid` as the 16 raw bytes of a `FixedString(16)` column.
+///
+/// Deserialization fails when the Prefix bytes are not ASCII alphanumeric
+/// characters followed by NUL padding, unless the value is the nil Pxid.
+pub mod fixed_string {
+    use super::*;
+
+    pub fn serialize<S>(value: &Pxid, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.0.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> std::result::Result<Pxid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = <[u8; BINARY_LENGTH]>::deserialize(deserializer)?;
+
+        Pxid::from_packed(&bytes).map_err(D::Error::custom)
+    }
+}
+
+/// `Pxid` stored in its string form on a `FixedString(25)` column, padded
+/// with trailing NULs
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PxidText(pub Pxid);
+
+impl PxidText {
+    /// Retrieves the column value, the string form with NULs trimmed from
+    /// the Prefix followed by NUL padding
+    pub fn to_column(&self) -> [u8; ENCODED_LENGTH] {
+        let mut column = [0_u8; ENCODED_LENGTH];
+        let text = self.0.to_string().replace('\0', "");
+
+        for (dst, src) in column.iter_mut().zip(text.as_bytes()) {
+            *dst = *src;
+        }
+
+        column
+    }
+
+    /// Decodes a column value produced by `to_column`. NULs are only allowed
+    /// as trailing padding.
+    pub fn from_column(column: &[u8; ENCODED_LENGTH]) -> std::result::Result<Self, String> {
+        let len = column
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(ENCODED_LENGTH);
+
+        if column.iter().skip(len).any(|byte| *byte != 0) {
+            return Err(format!(
+                "Pxid text column has non NUL padding after {len} bytes"
+            ));
+        }
+
+        let text =
+            from_utf8(column.get(..len).unwrap_or_default()).map_err(|err| err.to_string())?;
+
+        Pxid::from_str(text)
+            .map(Self)
+            .map_err(|err| err.to_string())
+    }
+}
+
+impl From<Pxid> for PxidText {
+    fn from(value: Pxid) -> Self {
+        Self(value)
+    }
+}
+
+impl From<PxidText> for Pxid {
+    fn from(value: PxidText) -> Self {
+        value.0
+    }
+}
+
+impl Deref for PxidText {
+    type Target = Pxid;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for PxidText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl Serialize for PxidText {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_column().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PxidText {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let column = <[u8; ENCODED_LENGTH]>::deserialize(deserializer)?;
+
+        PxidText::from_column(&column).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_tokens, Token};
+
+    use crate::id::SEPARATOR;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Event {
+        #[serde(with = "crate::clickhouse::fixed_string")]
+        id: Pxid,
+        account: PxidText,
+    }
+
+    /// Tokens of a `FixedString(N)` column, which RowBinary encodes as the
+    /// `N` bytes as is
+    fn fixed_string_tokens(bytes: &[u8]) -> Vec<Token> {
+        let mut tokens = vec![Token::Tuple { len: bytes.len() }];
+
+        tokens.extend(bytes.iter().map(|byte| Token::U8(*byte)));
+        tokens.push(Token::TupleEnd);
+        tokens
+    }
+
+    fn round_trip(event: &Event) -> Event {
+        serde_json::from_slice(&serde_json::to_vec(event).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn round_trips_rows_with_both_column_styles() {
+        let event = Event {
+            id: Pxid::from_str("evnt_9m4e2mr0ui3e8a215n4g").unwrap(),
+            account: PxidText(Pxid::from_str("acct_crdqga007gvfk4a3t0t0").unwrap()),
+        };
+
+        assert_eq!(round_trip(&event), event);
+    }
+
+    #[test]
+    fn encodes_raw_bytes_on_fixed_string_16() {
+        let id = Pxid::from_str("evnt_9m4e2mr0ui3e8a215n4g").unwrap();
+        let mut tokens = vec![Token::Struct {
+            name: "Event",
+            len: 2,
+        }];
+
+        tokens.push(Token::Str("id"));
+        tokens.extend(fixed_string_tokens(&id.0));
+        tokens.push(Token::Str("account"));
+        tokens.extend(fixed_string_tokens(b"acct_9m4e2mr0ui3e8a215n4g"));
+        tokens.push(Token::StructEnd);
+
+        assert_tokens(
+            &Event {
+                id,
+                account: PxidText(Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap()),
+            },
+            &tokens,
+        );
+    }
+
+    #[test]
+    fn pads_short_prefixes_with_nul() {
+        for prefix in ["a", "ab", "usr"] {
+            let id = Pxid::new(prefix).unwrap();
+            let column = PxidText(id).to_column();
+            let len = prefix.len() + 21;
+
+            assert_eq!(&column[..prefix.len()], prefix.as_bytes());
+            assert_eq!(column[prefix.len()], SEPARATOR as u8);
+            assert!(column[len..].iter().all(|byte| *byte == 0), "{}", prefix);
+            assert_eq!(PxidText::from_column(&column), Ok(PxidText(id)));
+
+            let event = Event {
+                id,
+                account: PxidText(id),
+            };
+
+            assert_eq!(round_trip(&event), event);
+        }
+    }
+
+    #[test]
+    fn round_trips_nil_pxid() {
+        let column = PxidText(Pxid::nil()).to_column();
+
+        assert_eq!(&column[..24], b"nil_00000000000000000000");
+        assert_eq!(PxidText::from_column(&column), Ok(PxidText(Pxid::nil())));
+    }
+
+    #[test]
+    fn complains_on_non_nul_padding() {
+        let mut column = PxidText(Pxid::new("ab").unwrap()).to_column();
+
+        column[ENCODED_LENGTH - 1] = b'x';
+
+        assert_eq!(
+            PxidText::from_column(&column),
+            Err(String::from(
+                "Pxid text column has non NUL padding after 23 bytes"
+            ))
+        );
+
+        let json = serde_json::to_string(&column).unwrap();
+
+        assert!(serde_json::from_str::<PxidText>(&json)
+            .unwrap_err()
+            .to_string()
+            .contains("non NUL padding"));
+    }
+
+    #[test]
+    fn complains_on_bad_prefix_bytes() {
+        let mut bytes = Pxid::from_str("evnt_9m4e2mr0ui3e8a215n4g").unwrap().0;
+
+        bytes[1] = b'-';
+
+        let json = format!(
+            r#"{{ "id": {}, "account": {} }}"#,
+            serde_json::to_string(&bytes).unwrap(),
+            serde_json::to_string(&PxidText(Pxid::new("acct").unwrap()).to_column()).unwrap()
+        );
+        let err = serde_json::from_str::<Event>(&json).unwrap_err();
+
+        assert!(err.to_string().contains("invalid character '-'"), "{}", err);
+    }
+}
@@ -0,0 +1,241 @@
+//! Lenient parsing for IDs sent by legacy clients.
+//!
+//! Only the following fixups are applied, in order:
+//!
+//! | Input                                | Fixup                         |
+//! | ------------------------------------ | ----------------------------- |
+//! | ` acct_9m4e2mr0ui3e8a215n4g\n`       | `TRIMMED_WHITESPACE`          |
+//! | `<acct_9m4e2mr0ui3e8a215n4g>`        | `STRIPPED_ANGLE_BRACKETS`     |
+//! | `[acct_9m4e2mr0ui3e8a215n4g]`        | `STRIPPED_SQUARE_BRACKETS`    |
+//! | `acct%5F9m4e2mr0ui3e8a215n4g`        | `PERCENT_DECODED_SEPARATOR`   |
+//!
+//! ASCII whitespace is trimmed both outside and inside the brackets. Only
+//! one pair of brackets is stripped and only `%5F` and `%5f` are percent
+//! decoded, any other percent-encoded sequence fails parsing.
+use std::ops::{BitOr, BitOrAssign};
+use std::str::FromStr;
+
+use crate::id::{Pxid, SEPARATOR};
+use crate::Result;
+
+/// Set of fixups applied by `Pxid::from_str_compat`, so callers can report
+/// which legacy formats are still in use
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CompatFixups(u8);
+
+impl CompatFixups {
+    /// No fixup was applied, the input is a valid Pxid as is
+    pub const NONE: Self = Self(0);
+
+    /// Leading or trailing ASCII whitespace was trimmed
+    pub const TRIMMED_WHITESPACE: Self = Self(1);
+
+    /// A wrapping pair of `<` and `>` was stripped
+    pub const STRIPPED_ANGLE_BRACKETS: Self = Self(1 << 1);
+
+    /// A wrapping pair of `[` and `]` was stripped
+    pub const STRIPPED_SQUARE_BRACKETS: Self = Self(1 << 2);
+
+    /// A percent-encoded separator, `%5F` or `%5f`, was decoded
+    pub const PERCENT_DECODED_SEPARATOR: Self = Self(1 << 3);
+
+    /// Every fixup along with its name
+    pub const ALL: [(&'static str, Self); 4] = [
+        ("trimmed_whitespace", Self::TRIMMED_WHITESPACE),
+        ("stripped_angle_brackets", Self::STRIPPED_ANGLE_BRACKETS),
+        ("stripped_square_brackets", Self::STRIPPED_SQUARE_BRACKETS),
+        ("percent_decoded_separator", Self::PERCENT_DECODED_SEPARATOR),
+    ];
+
+    /// Retrieves the raw bits of this set
+    #[inline]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Checks whether no fixup is set
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Checks whether every fixup in `other` is set
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Retrieves the names of the fixups set, as listed on `ALL`, useful
+    /// as metric labels
+    pub fn names(self) -> impl Iterator<Item = &'static str> {
+        Self::ALL
+            .into_iter()
+            .filter(move |(_, fixup)| self.contains(*fixup))
+            .map(|(name, _)| name)
+    }
+}
+
+impl BitOr for CompatFixups {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for CompatFixups {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl Pxid {
+    /// Parses a Pxid applying the fixups for legacy clients described on
+    /// the `compat` module, retrieving the fixups applied along with the
+    /// Pxid.
+    ///
+    /// Strict parsing through `FromStr` is not affected.
+    pub fn from_str_compat(s: &str) -> Result<(Pxid, CompatFixups)> {
+        let mut fixups = CompatFixups::NONE;
+        let mut value = trim(s, &mut fixups);
+
+        if let Some(inner) = value.strip_prefix('<').and_then(|v| v.strip_suffix('>')) {
+            fixups |= CompatFixups::STRIPPED_ANGLE_BRACKETS;
+            value = trim(inner, &mut fixups);
+        } else if let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            fixups |= CompatFixups::STRIPPED_SQUARE_BRACKETS;
+            value = trim(inner, &mut fixups);
+        }
+
+        if !value.contains("%5F") && !value.contains("%5f") {
+            return Pxid::from_str(value).map(|id| (id, fixups));
+        }
+
+        let mut separator = [0_u8; 4];
+        let separator = SEPARATOR.encode_utf8(&mut separator);
+        let decoded = value.replace("%5F", separator).replace("%5f", separator);
+
+        fixups |= CompatFixups::PERCENT_DECODED_SEPARATOR;
+        Pxid::from_str(&decoded).map(|id| (id, fixups))
+    }
+}
+
+/// Trims ASCII whitespace from `s`, flagging it on `fixups` when any is found
+fn trim<'a>(s: &'a str, fixups: &mut CompatFixups) -> &'a str {
+    let trimmed = s.trim_matches(|c: char| c.is_ascii_whitespace());
+
+    if trimmed.len() != s.len() {
+        *fixups |= CompatFixups::TRIMMED_WHITESPACE;
+    }
+
+    trimmed
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{DecodeError, Error};
+
+    use super::*;
+
+    const ID: &str = "acct_9m4e2mr0ui3e8a215n4g";
+
+    fn assert_fixed(input: &str, expected: CompatFixups) {
+        assert_eq!(
+            Pxid::from_str_compat(input),
+            Ok((Pxid::from_str(ID).unwrap(), expected)),
+            "{input:?}"
+        );
+    }
+
+    #[test]
+    fn parses_valid_input_without_fixups() {
+        assert_fixed(ID, CompatFixups::NONE);
+        assert!(Pxid::from_str_compat(ID).unwrap().1.is_empty());
+    }
+
+    #[test]
+    fn applies_each_fixup() {
+        assert_fixed(
+            " \tacct_9m4e2mr0ui3e8a215n4g\r\n",
+            CompatFixups::TRIMMED_WHITESPACE,
+        );
+        assert_fixed(
+            "<acct_9m4e2mr0ui3e8a215n4g>",
+            CompatFixups::STRIPPED_ANGLE_BRACKETS,
+        );
+        assert_fixed(
+            "[acct_9m4e2mr0ui3e8a215n4g]",
+            CompatFixups::STRIPPED_SQUARE_BRACKETS,
+        );
+        assert_fixed(
+            "acct%5F9m4e2mr0ui3e8a215n4g",
+            CompatFixups::PERCENT_DECODED_SEPARATOR,
+        );
+        assert_fixed(
+            "acct%5f9m4e2mr0ui3e8a215n4g",
+            CompatFixups::PERCENT_DECODED_SEPARATOR,
+        );
+    }
+
+    #[test]
+    fn combines_fixups() {
+        assert_fixed(
+            " <acct%5F9m4e2mr0ui3e8a215n4g> ",
+            CompatFixups::TRIMMED_WHITESPACE
+                | CompatFixups::STRIPPED_ANGLE_BRACKETS
+                | CompatFixups::PERCENT_DECODED_SEPARATOR,
+        );
+        assert_fixed(
+            "[ acct_9m4e2mr0ui3e8a215n4g ]",
+            CompatFixups::TRIMMED_WHITESPACE | CompatFixups::STRIPPED_SQUARE_BRACKETS,
+        );
+
+        let (_, fixups) = Pxid::from_str_compat("\n[acct%5f9m4e2mr0ui3e8a215n4g]").unwrap();
+
+        assert!(fixups.contains(CompatFixups::TRIMMED_WHITESPACE));
+        assert!(!fixups.contains(CompatFixups::STRIPPED_ANGLE_BRACKETS));
+        assert_eq!(
+            fixups.names().collect::<Vec<&str>>(),
+            vec![
+                "trimmed_whitespace",
+                "stripped_square_brackets",
+                "percent_decoded_separator"
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_inputs_invalid_after_fixups() {
+        for input in [
+            "",
+            "<>",
+            "<<acct_9m4e2mr0ui3e8a215n4g>>",
+            "<acct_9m4e2mr0ui3e8a215n4g]",
+            "[acct_9m4e2mr0ui3e8a215n4g>",
+            "acct%5F%5F9m4e2mr0ui3e8a215n4g",
+            "acct%2D9m4e2mr0ui3e8a215n4g",
+            "acct%5F9m4e2mr0ui3e8a215n4",
+            "\u{a0}acct_9m4e2mr0ui3e8a215n4g",
+        ] {
+            assert!(
+                matches!(Pxid::from_str_compat(input), Err(Error::Decode(_))),
+                "{input:?}"
+            );
+        }
+
+        assert_eq!(
+            Pxid::from_str_compat(" acct%5Fzzzzzzzzzzzzzzzzzzzz "),
+            Err(Error::Decode(DecodeError::InvalidChar(
+                String::from("zzzzzzzzzzzzzzzzzzzz"),
+                'z',
+                0
+            )))
+        );
+    }
+
+    #[test]
+    fn keeps_strict_parsing() {
+        assert!(Pxid::from_str("<acct_9m4e2mr0ui3e8a215n4g>").is_err());
+        assert!(Pxid::from_str("acct%5F9m4e2mr0ui3e8a215n4g").is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! Lenient parsing for Pxid values referenced from configuration files.
+//!
+//! Configuration values are frequently edited by hand, so values are cleaned
+//! up before being decoded and errors carry a hint on common YAML mistakes.
+//!
+//! ```no_run
+//! use pxid::config::parse_config_value;
+//!
+//! # fn main() -> Result<(), pxid::config::ConfigIdError> {
+//! let id = parse_config_value(" 'acct_9m4e2mr0ui3e8a215n4g' ")?;
+//! # Ok(())
+//! # }
+//! ```
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::error::Error;
+use crate::id::{Pxid, ENCODING_CHARS, SEPARATOR, XID_ENCODED_LENGTH};
+
+/// Hint for values holding characters YAML gives special meaning to
+pub const QUOTE_HINT: &str = "did you forget to quote the value?";
+
+/// Hint for values which look cut short, usually because YAML treats
+/// everything after ` #` as a comment
+pub const TRUNCATED_HINT: &str =
+    "the value looks truncated, YAML treats ` #` as a comment, did you forget to quote the value?";
+
+/// Hint for values with an opening quote but no closing one, or the other
+/// way around
+pub const UNBALANCED_QUOTES_HINT: &str = "check the value has matching opening and closing quotes";
+
+/// Characters YAML gives special meaning to, which are never part of a Pxid
+const YAML_INDICATORS: [char; 15] = [
+    '#', ':', '{', '}', '[', ']', ',', '&', '*', '!', '|', '>', '%', '@', '`',
+];
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ConfigIdError {
+    /// The value is empty after trimming whitespaces and quotes
+    #[error("Expected a Pxid but found an empty value")]
+    Empty,
+
+    /// The value has an opening quote without a closing one or viceversa
+    #[error("Value {0} has unbalanced quotes, {UNBALANCED_QUOTES_HINT}")]
+    UnbalancedQuotes(String),
+
+    /// The value contains a character YAML gives special meaning to
+    #[error("Value {0} contains the character {1:?} at position {2}, {QUOTE_HINT}")]
+    YamlArtifact(String, char, usize),
+
+    /// The value looks like a Pxid cut short
+    #[error("Value {0} is not a complete Pxid, {TRUNCATED_HINT}")]
+    Truncated(String),
+
+    /// The value cannot be decoded into a Pxid
+    #[error("Value {0} is not a valid Pxid. {1}")]
+    Invalid(String, Error),
+}
+
+impl ConfigIdError {
+    /// Retrieves a hint on how to fix the configuration value, if any
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            ConfigIdError::Empty | ConfigIdError::Invalid(_, _) => None,
+            ConfigIdError::UnbalancedQuotes(_) => Some(UNBALANCED_QUOTES_HINT),
+            ConfigIdError::YamlArtifact(_, _, _) => Some(QUOTE_HINT),
+            ConfigIdError::Truncated(_) => Some(TRUNCATED_HINT),
+        }
+    }
+}
+
+/// Parses a Pxid from a configuration value, trimming surrounding
+/// whitespaces and single or double quotes.
+pub fn parse_config_value(raw: &str) -> Result<Pxid, ConfigIdError> {
+    let value = unquote(raw.trim())?;
+
+    if value.is_empty() {
+        return Err(ConfigIdError::Empty);
+    }
+
+    if let Some((pos, c)) = value
+        .chars()
+        .enumerate()
+        .find(|(_, c)| YAML_INDICATORS.contains(c))
+    {
+        return Err(ConfigIdError::YamlArtifact(value.to_string(), c, pos));
+    }
+
+    if is_truncated(value) {
+        return Err(ConfigIdError::Truncated(value.to_string()));
+    }
+
+    Pxid::from_str(value).map_err(|err| ConfigIdError::Invalid(value.to_string(), err))
+}
+
+/// Removes a pair of matching surrounding quotes and the whitespaces
+/// within them
+fn unquote(value: &str) -> Result<&str, ConfigIdError> {
+    for quote in ['"', '\''] {
+        let starts = value.starts_with(quote);
+        let ends = value.len() > 1 && value.ends_with(quote);
+
+        if starts && ends {
+            return Ok(value[1..value.len() - 1].trim());
+        }
+
+        if starts || ends {
+            return Err(ConfigIdError::UnbalancedQuotes(value.to_string()));
+        }
+    }
+
+    Ok(value)
+}
+
+/// Checks if the value has a prefix followed by a valid but incomplete
+/// encoded XID
+fn is_truncated(value: &str) -> bool {
+    match value.split_once(SEPARATOR) {
+        Some((prefix, xid)) => {
+            !prefix.is_empty()
+                && xid.len() < XID_ENCODED_LENGTH
+                && xid.bytes().all(|b| ENCODING_CHARS.contains(&b))
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::DecodeError;
+
+    use super::*;
+
+    const ID: &str = "acct_9m4e2mr0ui3e8a215n4g";
+
+    #[test]
+    fn parses_quoted_values() {
+        let expected = Pxid::from_str(ID).unwrap();
+
+        assert_eq!(parse_config_value(&format!("\"{ID}\"")), Ok(expected));
+        assert_eq!(parse_config_value(&format!("'{ID}'")), Ok(expected));
+    }
+
+    #[test]
+    fn parses_whitespace_padded_values() {
+        let expected = Pxid::from_str(ID).unwrap();
+
+        assert_eq!(parse_config_value(&format!("  {ID}\n")), Ok(expected));
+        assert_eq!(parse_config_value(&format!("\t' {ID} ' ")), Ok(expected));
+    }
+
+    #[test]
+    fn complains_on_comment_truncated_values() {
+        let err = parse_config_value("acct_9m4e2mr0").unwrap_err();
+
+        assert_eq!(err, ConfigIdError::Truncated(String::from("acct_9m4e2mr0")));
+        assert_eq!(err.hint(), Some(TRUNCATED_HINT));
+    }
+
+    #[test]
+    fn complains_on_inline_comments() {
+        let err = parse_config_value(&format!("{ID} # production")).unwrap_err();
+
+        assert_eq!(
+            err,
+            ConfigIdError::YamlArtifact(format!("{ID} # production"), '#', 26)
+        );
+        assert_eq!(err.hint(), Some("did you forget to quote the value?"));
+    }
+
+    #[test]
+    fn complains_on_unbalanced_quotes() {
+        let err = parse_config_value(&format!("\"{ID}")).unwrap_err();
+
+        assert_eq!(err, ConfigIdError::UnbalancedQuotes(format!("\"{ID}")));
+        assert_eq!(err.hint(), Some(UNBALANCED_QUOTES_HINT));
+    }
+
+    #[test]
+    fn complains_on_empty_values() {
+        assert_eq!(parse_config_value(" '' "), Err(ConfigIdError::Empty));
+    }
+
+    #[test]
+    fn complains_on_invalid_values_without_hint() {
+        let err = parse_config_value("acct_9m4e2mr0ui3e8a215n4x").unwrap_err();
+
+        assert_eq!(
+            err,
+            ConfigIdError::Invalid(
+                String::from("acct_9m4e2mr0ui3e8a215n4x"),
+                Error::Decode(DecodeError::InvalidChar(
+                    String::from("9m4e2mr0ui3e8a215n4x"),
+                    'x',
+                    19
+                ))
+            )
+        );
+        assert_eq!(err.hint(), None);
+    }
+}
@@ -0,0 +1,123 @@
+//! Counter seeding and the process wide Counter used by `Pxid::new` and
+//! `Factory`.
+//!
+//! IDs generated through `Pxid::new` and every non-deterministic `Factory`
+//! draw consecutive values from `GLOBAL_COUNTER`, which starts at a random
+//! seed on first use, so they share a single Counter space.
+//! `Factory::set_counter` and `Factory::reseed` act on it too.
+//!
+//! `GLOBAL_COUNTER` is private to the crate, monitoring agents can poll
+//! `Pxid::global_counter_value` to compute the rate of generated IDs.
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::LazyLock;
+
+use rand::RngCore;
+
+use crate::id::Pxid;
+
+/// Counter shared by `Pxid::new` and every non-deterministic `Factory`,
+/// seeded with `new_seed` on first use.
+///
+/// Only the 3 least significant bytes are encoded on IDs, so values wrap
+/// past `COUNTER_MAX`.
+pub(crate) static GLOBAL_COUNTER: LazyLock<AtomicU32> =
+    LazyLock::new(|| AtomicU32::new(new_seed()));
+
+/// Retrieves a random Counter seed made of 3 random bytes, so it never
+/// exceeds `COUNTER_MAX`
+pub(crate) fn new_seed() -> u32 {
+    let mut rand_bytes: [u8; 3] = [0; 3];
+
+    rand::thread_rng().fill_bytes(&mut rand_bytes);
+
+    let [first, second, third] = rand_bytes;
+
+    u32::from_be_bytes([0, first, second, third])
+}
+
+/// Retrieves the next value from `GLOBAL_COUNTER`
+///
+/// # Reference
+///
+/// Follows the authors algorithm writen on Golang in the [following source][1].
+///
+/// [1]: https://github.com/rs/xid/blob/e6fb919be3fc74f2b846a6d174e57e076a38b1c1/id.go#L159
+#[inline]
+pub(crate) fn next_counter() -> u32 {
+    GLOBAL_COUNTER.fetch_add(1, Ordering::SeqCst)
+}
+
+/// Reserves `count` consecutive values from `GLOBAL_COUNTER` and retrieves
+/// the first one
+#[inline]
+pub(crate) fn next_counters(count: u32) -> u32 {
+    GLOBAL_COUNTER.fetch_add(count, Ordering::SeqCst)
+}
+
+impl Pxid {
+    /// Retrieves the `GLOBAL_COUNTER` value the next ID generated by
+    /// `Pxid::new` or a non-deterministic `Factory` will hold, before
+    /// truncating it to 3 bytes.
+    ///
+    /// The difference between two reads is the amount of IDs generated in
+    /// between, computed with `u32::wrapping_sub` given the value wraps.
+    #[inline]
+    pub fn global_counter_value() -> u32 {
+        GLOBAL_COUNTER.load(Ordering::Relaxed)
+    }
+
+    /// Stores `seed` as the `GLOBAL_COUNTER` value the next ID generated by
+    /// `Pxid::new` or a non-deterministic `Factory` will hold. Available with
+    /// the `test-util` feature, so production code can't reset it by
+    /// accident.
+    ///
+    /// IDs generated within the same second after a reset may collide with
+    /// the ones generated before it, as described on `Factory::set_counter`.
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn global_counter_reset(seed: u32) {
+        GLOBAL_COUNTER.store(seed, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::factory::COUNTER_MAX;
+
+    use super::*;
+
+    #[test]
+    fn seeds_fit_in_three_bytes() {
+        assert!((0..1_000).all(|_| new_seed() <= COUNTER_MAX));
+    }
+
+    #[test]
+    fn next_counter_advances_global_counter() {
+        let first = next_counter();
+        let current = GLOBAL_COUNTER.load(Ordering::SeqCst);
+
+        // Other tests may generate IDs concurrently, so the Counter is only
+        // known to have moved past `first`
+        assert!(current.wrapping_sub(first) >= 1);
+        assert_ne!(next_counter(), first);
+    }
+
+    #[test]
+    fn exposes_global_counter_value() {
+        let before = Pxid::global_counter_value();
+
+        Pxid::new("acct").unwrap();
+
+        assert!(Pxid::global_counter_value().wrapping_sub(before) >= 1);
+
+        // Other tests generate IDs concurrently, so the Counter is only moved
+        // forward to avoid repeating the Counters they hold
+        let seed = Pxid::global_counter_value().wrapping_add(1 << 20);
+
+        Pxid::global_counter_reset(seed);
+
+        let counter = Pxid::new("acct").unwrap().counter();
+
+        assert!(counter.wrapping_sub(seed & COUNTER_MAX) & COUNTER_MAX < 1 << 16);
+        assert!(Pxid::global_counter_value().wrapping_sub(seed) >= 1);
+    }
+}
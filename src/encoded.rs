@@ -0,0 +1,154 @@
+//! Stack allocated string form of a `Pxid`, useful to attach IDs as fields
+//! on structured logs.
+//!
+//! With the `valuable` feature enabled, `EncodedPxid` implements
+//! `valuable::Valuable` recording the string form, so it can be used as a
+//! `tracing` field (not compiled, `tracing` is not a dependency of this
+//! crate):
+//!
+//! ```ignore
+//! tracing::info!(id = id.encoded().as_value(), "account created");
+//! ```
+use std::fmt::{self, Display, Write};
+use std::ops::Deref;
+use std::str::from_utf8;
+
+use crate::id::{Pxid, ENCODED_LENGTH, PREFIX_LENGTH};
+
+/// Bytes needed to hold the string form written by `Display`, where each
+/// invalid prefix byte takes the 3 bytes of `U+FFFD REPLACEMENT CHARACTER`
+const CAPACITY: usize = ENCODED_LENGTH + 2 * PREFIX_LENGTH;
+
+/// String form of a `Pxid` as written by `Display`, held without allocating
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EncodedPxid {
+    bytes: [u8; CAPACITY],
+    len: usize,
+}
+
+impl EncodedPxid {
+    /// Retrieves the string form
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.bytes
+            .get(..self.len)
+            .and_then(|bytes| from_utf8(bytes).ok())
+            .unwrap_or_default()
+    }
+}
+
+impl Write for EncodedPxid {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        let Some(dst) = self.bytes.get_mut(self.len..end) else {
+            return Err(fmt::Error);
+        };
+
+        dst.copy_from_slice(s.as_bytes());
+        self.len = end;
+
+        Ok(())
+    }
+}
+
+impl Pxid {
+    /// Retrieves the string form of this Pxid without allocating
+    pub fn encoded(&self) -> EncodedPxid {
+        let mut encoded = EncodedPxid {
+            bytes: [0; CAPACITY],
+            len: 0,
+        };
+
+        // `CAPACITY` fits the string form of every Pxid
+        let _ = self.write_encoded_lossy(&mut encoded);
+
+        encoded
+    }
+}
+
+impl Deref for EncodedPxid {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for EncodedPxid {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for EncodedPxid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Display for EncodedPxid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+#[cfg(feature = "valuable")]
+impl valuable::Valuable for EncodedPxid {
+    fn as_value(&self) -> valuable::Value<'_> {
+        valuable::Value::String(self.as_str())
+    }
+
+    fn visit(&self, visit: &mut dyn valuable::Visit) {
+        visit.visit_value(self.as_value());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn holds_string_form() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let encoded = id.encoded();
+
+        assert_eq!(encoded.as_str(), "acct_9m4e2mr0ui3e8a215n4g");
+        assert_eq!(&*encoded, id.to_string());
+        assert_eq!(format!("{encoded}"), id.to_string());
+        assert_eq!(format!("{encoded:?}"), "\"acct_9m4e2mr0ui3e8a215n4g\"");
+        assert_eq!(Pxid::nil().encoded().as_str(), Pxid::nil().to_string());
+    }
+
+    #[cfg(feature = "valuable")]
+    #[test]
+    fn records_valuable_as_encoded_string() {
+        use valuable::{Valuable, Value, Visit};
+
+        #[derive(Default)]
+        struct Recorder(Vec<String>);
+
+        impl Visit for Recorder {
+            fn visit_value(&mut self, value: Value<'_>) {
+                match value {
+                    Value::String(s) => self.0.push(s.to_string()),
+                    other => panic!("Unexpected value {other:?}"),
+                }
+            }
+        }
+
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let encoded = id.encoded();
+        let mut recorder = Recorder::default();
+
+        assert_eq!(
+            encoded.as_value().as_str(),
+            Some("acct_9m4e2mr0ui3e8a215n4g")
+        );
+
+        valuable::visit(&encoded, &mut recorder);
+
+        assert_eq!(recorder.0, vec![String::from("acct_9m4e2mr0ui3e8a215n4g")]);
+    }
+}
@@ -0,0 +1,125 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::Error;
+use crate::Result;
+
+/// Reference time the 4 bytes Timestamp counts seconds from.
+///
+/// Timestamps fit about 136 years after the epoch, the Unix epoch overflows
+/// on 2106-02-07, so deployments needing IDs beyond that date can rebase the
+/// window with a custom epoch. A custom epoch on 2020-01-01 extends the range
+/// up to 2156.
+///
+/// The epoch is not encoded on IDs, so IDs must be read using the same epoch
+/// they were generated with through `Pxid::timestamp_with_epoch`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Epoch {
+    /// Timestamps are seconds since `1970-01-01T00:00:00Z`
+    #[default]
+    Unix,
+
+    /// Timestamps are seconds since the provided time
+    Custom(SystemTime),
+}
+
+impl Epoch {
+    /// Retrieves the time Timestamps count seconds from
+    #[inline]
+    pub fn start(&self) -> SystemTime {
+        match self {
+            Epoch::Unix => UNIX_EPOCH,
+            Epoch::Custom(start) => *start,
+        }
+    }
+
+    /// Retrieves the Timestamp for the provided `time`, failing with
+    /// `Error::TimestampOutOfRange` holding the seconds since this epoch when
+    /// `time` is before the epoch or doesn't fit in 4 bytes.
+    pub fn timestamp(&self, time: SystemTime) -> Result<u32> {
+        let secs = match time.duration_since(self.start()) {
+            Ok(elapsed) => i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX),
+            Err(err) => i64::try_from(err.duration().as_secs()).map_or(i64::MIN, |secs| -secs),
+        };
+
+        u32::try_from(secs).map_err(|_| Error::TimestampOutOfRange(secs))
+    }
+
+    /// Retrieves the time represented by the provided Timestamp, failing
+    /// with `Error::TimestampOutOfRange` when a far future custom epoch
+    /// pushes it past the times `SystemTime` can hold.
+    #[inline]
+    pub fn time(&self, timestamp: u32) -> Result<SystemTime> {
+        self.start()
+            .checked_add(Duration::from_secs(u64::from(timestamp)))
+            .ok_or(Error::TimestampOutOfRange(i64::from(timestamp)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `2020-01-01T00:00:00Z`
+    const EPOCH_2020: u64 = 1_577_836_800;
+
+    fn custom() -> Epoch {
+        Epoch::Custom(UNIX_EPOCH + Duration::from_secs(EPOCH_2020))
+    }
+
+    #[test]
+    fn unix_timestamps_match_seconds_since_unix_epoch() {
+        let time = UNIX_EPOCH + Duration::from_secs(0x4d88_e15b);
+
+        assert_eq!(Epoch::default(), Epoch::Unix);
+        assert_eq!(Epoch::Unix.timestamp(time), Ok(0x4d88_e15b));
+        assert_eq!(Epoch::Unix.time(0x4d88_e15b), Ok(time));
+    }
+
+    #[test]
+    fn custom_epoch_round_trips() {
+        let time = UNIX_EPOCH + Duration::from_secs(EPOCH_2020 + 42);
+
+        assert_eq!(custom().timestamp(time), Ok(42));
+        assert_eq!(custom().time(42), Ok(time));
+    }
+
+    #[test]
+    fn custom_epoch_extends_range() {
+        let time = UNIX_EPOCH + Duration::from_secs(u64::from(u32::MAX) + 1);
+
+        assert_eq!(
+            Epoch::Unix.timestamp(time),
+            Err(Error::TimestampOutOfRange(i64::from(u32::MAX) + 1))
+        );
+        assert_eq!(
+            custom().timestamp(time),
+            Ok(u32::try_from(u64::from(u32::MAX) + 1 - EPOCH_2020).unwrap())
+        );
+        assert_eq!(
+            custom().timestamp(custom().time(u32::MAX).unwrap()),
+            Ok(u32::MAX)
+        );
+    }
+
+    #[test]
+    fn rejects_timestamps_past_far_future_epochs() {
+        // Latest second `SystemTime` holds on Unix platforms
+        let start = UNIX_EPOCH + Duration::from_secs(i64::MAX.unsigned_abs() - 10);
+        let epoch = Epoch::Custom(start);
+
+        assert_eq!(epoch.time(10), Ok(start + Duration::from_secs(10)));
+        assert_eq!(epoch.time(11), Err(Error::TimestampOutOfRange(11)));
+        assert_eq!(
+            epoch.time(u32::MAX),
+            Err(Error::TimestampOutOfRange(i64::from(u32::MAX)))
+        );
+    }
+
+    #[test]
+    fn rejects_times_before_epoch() {
+        assert_eq!(
+            custom().timestamp(UNIX_EPOCH + Duration::from_secs(EPOCH_2020 - 5)),
+            Err(Error::TimestampOutOfRange(-5))
+        );
+    }
+}
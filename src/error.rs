@@ -2,7 +2,10 @@ use std::str::Utf8Error;
 
 use thiserror::Error;
 
+use crate::factory::COUNTER_MAX;
 use crate::id::{ENCODED_LENGTH, PREFIX_LENGTH, XID_ENCODED_LENGTH};
+use crate::policy::TimestampViolation;
+use crate::validate::{display_all, ValidationError};
 
 #[derive(Clone, Debug, Error, PartialEq, Eq)]
 pub enum DecodeError {
@@ -22,9 +25,10 @@ pub enum DecodeError {
     InvalidPrefixLength(String, usize),
 
     /// The provided `String` contains an invalid character and cannot be decoded
-    /// into an instance of PXID
-    #[error("String cannot be decoded into a PXID instance. {0} length is not valid. Found invalid char {1}.")]
-    InvalidChar(String, char),
+    /// into an instance of PXID. Holds the byte offset of the character on
+    /// the provided `String`.
+    #[error("String cannot be decoded into a PXID instance. {0} found invalid char '{1}' at position {2}.")]
+    InvalidChar(String, char, usize),
 
     /// Invalid UTF-8 character encountered
     #[error("Invalid UTF-8 character encountered")]
@@ -34,6 +38,65 @@ pub enum DecodeError {
     /// into an instance of PXID
     #[error("String cannot be decoded into a PXID instance. {0} XID length is not valid. Expected length {XID_ENCODED_LENGTH}, but received {1}")]
     InvalidXidLength(String, usize),
+
+    /// The provided `String` check character doesn't match the one computed
+    /// from its encoded XID
+    #[error("String cannot be decoded into a PXID instance. {0} checksum is not valid. Expected check character {1}.")]
+    InvalidChecksum(String, char),
+
+    /// The provided ULID encodes a value wider than 96 bits, so it cannot
+    /// hold an encoded XID
+    #[error(
+        "String cannot be decoded into a PXID instance. {0} is not a valid XID encoded as ULID."
+    )]
+    InvalidUlid(String),
+}
+
+/// Fieldless classification of `DecodeError` values, suitable for metric
+/// tags
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DecodeErrorKind {
+    MissingPrefix,
+    BadLength,
+    BadPrefixLength,
+    BadChar,
+    BadUtf8,
+    BadXidLength,
+    BadChecksum,
+    BadUlid,
+}
+
+impl DecodeErrorKind {
+    /// Retrieves a stable `snake_case` label for this kind
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DecodeErrorKind::MissingPrefix => "missing_prefix",
+            DecodeErrorKind::BadLength => "bad_length",
+            DecodeErrorKind::BadPrefixLength => "bad_prefix_length",
+            DecodeErrorKind::BadChar => "bad_char",
+            DecodeErrorKind::BadUtf8 => "bad_utf8",
+            DecodeErrorKind::BadXidLength => "bad_xid_length",
+            DecodeErrorKind::BadChecksum => "bad_checksum",
+            DecodeErrorKind::BadUlid => "bad_ulid",
+        }
+    }
+}
+
+impl DecodeError {
+    /// Retrieves the `DecodeErrorKind` of this error without inspecting the
+    /// offending input
+    pub fn kind(&self) -> DecodeErrorKind {
+        match self {
+            DecodeError::MissingPrefix(_) => DecodeErrorKind::MissingPrefix,
+            DecodeError::InvalidLength(_, _) => DecodeErrorKind::BadLength,
+            DecodeError::InvalidPrefixLength(_, _) => DecodeErrorKind::BadPrefixLength,
+            DecodeError::InvalidChar(_, _, _) => DecodeErrorKind::BadChar,
+            DecodeError::InvalidUtf8(_) => DecodeErrorKind::BadUtf8,
+            DecodeError::InvalidXidLength(_, _) => DecodeErrorKind::BadXidLength,
+            DecodeError::InvalidChecksum(_, _) => DecodeErrorKind::BadChecksum,
+            DecodeError::InvalidUlid(_) => DecodeErrorKind::BadUlid,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Error, PartialEq, Eq)]
@@ -47,6 +110,191 @@ pub enum Error {
     MachineID(String),
 
     /// Prefix is too long
+    #[deprecated(
+        since = "1.1.0",
+        note = "prefixes longer than 4 bytes are reported as `DecodeError::InvalidPrefixLength`"
+    )]
     #[error("Provided prefix: {0} is too long. Max allowed characters are 4.")]
     PrefixExceedsMaxLength(String),
+
+    /// Prefix is shorter than 4 characters while using strict padding
+    #[error("Provided prefix: {0} is too short. Strict padding requires 4 characters.")]
+    PrefixTooShort(String),
+
+    /// Prefix is not registered in the `FactoryPool`
+    #[error("Provided prefix: {0} is not registered.")]
+    UnregisteredPrefix(String),
+
+    /// The `FactoryPool` reached the max amount of registered prefixes
+    #[error("Cannot register prefix: {0}. Max allowed prefixes are {1}.")]
+    PrefixLimitReached(String, usize),
+
+    /// Prefix doesn't match the one expected by a `PxidWrapper` newtype
+    #[error("Expected prefix: {expected}, but received {found}.")]
+    PrefixMismatch {
+        /// Prefix expected by the caller
+        expected: String,
+        /// Prefix held by the decoded Pxid
+        found: String,
+    },
+
+    /// Batch size exceeds the amount of distinct Counter values
+    #[error("Cannot generate a batch of {0} IDs. Max allowed batch size is {}.", COUNTER_MAX as usize + 1)]
+    BatchTooLarge(usize),
+
+    /// `Factory::self_test` found the `Factory` unable to generate valid IDs
+    #[error("Factory self test failed. {0}")]
+    SelfTest(String),
+
+    /// Prefix holds a character other than ASCII alphanumeric characters and
+    /// trailing NUL padding
+    #[error("Pxid {0} prefix contains the invalid character {1:?}.")]
+    InvalidPrefixChar(String, char),
+
+    /// Timestamp is zero, which happens on nil or corrupted IDs
+    #[error("Pxid {0} has a zero timestamp.")]
+    ZeroTimestamp(String),
+
+    /// Every Machine ID byte is zero, which happens on nil or corrupted IDs
+    #[error("Pxid {0} has a zero Machine ID.")]
+    ZeroMachineId(String),
+
+    /// The nil Pxid was provided where a non nil one is required
+    #[error("Expected a non nil Pxid.")]
+    NilPxid,
+
+    /// Prefix is already registered in the `PrefixCatalog` with a different
+    /// entry
+    #[error("Provided prefix: {0} is already registered with a different entry.")]
+    DuplicatePrefix(String),
+
+    /// Time doesn't fit in the 4 bytes Timestamp, holds the seconds since the
+    /// `Epoch`, which are negative for times before it
+    #[error(
+        "Timestamp {0} is out of range. Timestamps must be between 0 and {}.",
+        u32::MAX
+    )]
+    TimestampOutOfRange(i64),
+
+    /// Prefix bytes are not valid UTF-8, so the Pxid has no string form
+    #[error("Pxid prefix bytes {0:02x?} are not valid UTF-8.")]
+    InvalidBinaryPrefix([u8; PREFIX_LENGTH]),
+
+    /// Binary form doesn't hold exactly 16 bytes
+    #[error("Pxid binary form must have 16 bytes, but received {0}.")]
+    InvalidBinaryLength(usize),
+
+    /// Array holds a null value where an ID is expected, holds the index of
+    /// the null value
+    #[error("Expected a Pxid, but found a null value at index {0}.")]
+    NullArrayValue(usize),
+
+    /// Timestamp is out of the bounds of a `TimestampPolicy`
+    #[error("Pxid {0} timestamp is skewed. {1}")]
+    SkewedTimestamp(String, TimestampViolation),
+
+    /// Pxid failed one or more `Validator` checks, holds every failure
+    /// collected
+    #[error("Pxid failed validation. {}", display_all(.0))]
+    Validation(Vec<ValidationError>),
+
+    /// Fixed width string only holds padding, as read from empty fixed width
+    /// columns
+    #[error("Fixed width string only holds padding.")]
+    PaddingOnly,
+
+    /// Fixed width string holds padding before its tail, holds the byte
+    /// offset of the first padding character
+    #[error("Fixed width string {0} holds padding at position {1}, padding is only allowed at the tail.")]
+    InteriorPadding(String, usize),
+
+    /// `Alphabet` holds a character other than printable ASCII characters,
+    /// or the `SEPARATOR`. Holds the offending byte and its position.
+    #[error("Alphabet holds the invalid character {0:#04x} at position {1}. Characters must be printable ASCII other than the separator.")]
+    InvalidAlphabetChar(u8, usize),
+
+    /// `Alphabet` holds a character more than once, holds the repeated
+    /// character and the position of its second occurrence
+    #[error("Alphabet holds the character {0:?} more than once, repeated at position {1}.")]
+    DuplicateAlphabetChar(char, usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::from_utf8;
+
+    use super::*;
+
+    /// Fails to compile when a `DecodeError` variant is added without being
+    /// listed on `samples`
+    fn listed(err: &DecodeError) {
+        match err {
+            DecodeError::MissingPrefix(_)
+            | DecodeError::InvalidLength(_, _)
+            | DecodeError::InvalidPrefixLength(_, _)
+            | DecodeError::InvalidChar(_, _, _)
+            | DecodeError::InvalidUtf8(_)
+            | DecodeError::InvalidXidLength(_, _)
+            | DecodeError::InvalidChecksum(_, _)
+            | DecodeError::InvalidUlid(_) => {}
+        }
+    }
+
+    #[allow(invalid_from_utf8)]
+    fn samples() -> Vec<(DecodeError, DecodeErrorKind, &'static str)> {
+        let utf8 = from_utf8(&[0xff]).unwrap_err();
+
+        vec![
+            (
+                DecodeError::MissingPrefix(String::new()),
+                DecodeErrorKind::MissingPrefix,
+                "missing_prefix",
+            ),
+            (
+                DecodeError::InvalidLength(String::new(), 0),
+                DecodeErrorKind::BadLength,
+                "bad_length",
+            ),
+            (
+                DecodeError::InvalidPrefixLength(String::new(), 0),
+                DecodeErrorKind::BadPrefixLength,
+                "bad_prefix_length",
+            ),
+            (
+                DecodeError::InvalidChar(String::new(), 'x', 0),
+                DecodeErrorKind::BadChar,
+                "bad_char",
+            ),
+            (
+                DecodeError::InvalidUtf8(utf8),
+                DecodeErrorKind::BadUtf8,
+                "bad_utf8",
+            ),
+            (
+                DecodeError::InvalidXidLength(String::new(), 0),
+                DecodeErrorKind::BadXidLength,
+                "bad_xid_length",
+            ),
+            (
+                DecodeError::InvalidChecksum(String::new(), '0'),
+                DecodeErrorKind::BadChecksum,
+                "bad_checksum",
+            ),
+            (
+                DecodeError::InvalidUlid(String::new()),
+                DecodeErrorKind::BadUlid,
+                "bad_ulid",
+            ),
+        ]
+    }
+
+    #[test]
+    fn maps_every_decode_error_to_a_kind() {
+        for (err, kind, label) in samples() {
+            listed(&err);
+
+            assert_eq!(err.kind(), kind);
+            assert_eq!(kind.as_str(), label);
+        }
+    }
 }
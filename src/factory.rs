@@ -1,74 +1,615 @@
-use std::process;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, Ordering};
-use std::time::{SystemTime, UNIX_EPOCH};
-
-use rand::RngCore;
+use std::sync::{Mutex, PoisonError};
+use std::time::SystemTime;
 
+use crate::counter;
+use crate::epoch::Epoch;
+use crate::error::Error;
 use crate::host_id::{machine_id, MachineIdBytes};
-use crate::id::Pxid;
+use crate::id::{Pxid, PREFIX_LENGTH, SEPARATOR};
+use crate::validate::Validator;
 use crate::Result;
 
+/// Strategy used to fit prefixes shorter than 4 bytes into the prefix bytes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PrefixPadding {
+    /// Short prefixes are padded with trailing NUL bytes
+    #[default]
+    Nul,
+
+    /// Short prefixes are rejected, prefixes must be exactly 4 bytes long
+    Strict,
+}
+
+/// Max value for the 3 bytes Counter
+pub const COUNTER_MAX: u32 = 0x00FF_FFFF;
+
+/// Counter value after which the Counter is considered close to exhaustion
+pub const COUNTER_WARNING_THRESHOLD: u32 = COUNTER_MAX - 65536;
+
+/// Timestamp used by deterministic factories, `2020-01-01T00:00:00Z`
+pub const DETERMINISTIC_TIMESTAMP: u32 = 1_577_836_800;
+
+/// Bit flipped on the first Machine ID byte of backfilled IDs, unless a
+/// dedicated Machine ID is set with `Factory::backfill_machine_id`
+pub const BACKFILL_MARKER: u8 = 0x80;
+
+/// Prefix used for the IDs generated by `Factory::self_test`
+pub const SELF_TEST_PREFIX: &str = "test";
+
+/// Amount of IDs generated by `Factory::self_test`
+const SELF_TEST_IDS: usize = 3;
+
+/// State for factories created with `Factory::deterministic`
+struct Deterministic {
+    counter: AtomicU32,
+    counter_seed: u32,
+    sequences: Mutex<HashMap<String, u32>>,
+}
+
 /// Factory of XID instances. Initializes dependencies once to avoid
 /// reallocating them on each ID generation.
 ///
+/// Counters are drawn from `GLOBAL_COUNTER`, shared with `Pxid::new` and
+/// every other `Factory` in the process, so IDs generated through any of
+/// them never repeat a Counter within the same second. Deterministic
+/// factories derive Counters from their seed instead.
+///
 /// # Design Pattern
 ///
 /// You can read more on the _Factory_ design pattern here on [Refactoring.guru][1]
 ///
 /// [1]: https://refactoring.guru/design-patterns/factory-comparison
 pub struct Factory {
-    counter: AtomicU32,
     process_id: u16,
     machine_id: MachineIdBytes,
+    separator: char,
+    padding: PrefixPadding,
+    epoch: Epoch,
+    deterministic: Option<Deterministic>,
+    validators: Vec<Box<dyn Validator + Send + Sync>>,
+    backfill_machine_id: Option<MachineIdBytes>,
 }
 
 impl Factory {
     pub fn new() -> Result<Self> {
-        let process_id = process::id() as u16;
-        let machine_id = machine_id()?;
-        let counter = AtomicU32::new(Self::new_counter_seed());
+        Self::with_separator(SEPARATOR)
+    }
 
-        Ok(Self {
-            counter,
-            process_id,
+    /// Creates a new `Factory` which encodes and decodes IDs using the
+    /// provided `separator` between the prefix and the encoded XID
+    pub fn with_separator(separator: char) -> Result<Self> {
+        Ok(Self::with_machine_id(machine_id()?, separator))
+    }
+
+    /// Creates a new `Factory` as done by `Factory::new`. `Factory` can't
+    /// implement `Default` given reading the Machine ID may fail, this is the
+    /// conventional entry point instead.
+    #[inline]
+    pub fn try_default() -> Result<Self> {
+        Self::new()
+    }
+
+    /// Creates a new `Factory` as done by `Factory::new`, falling back to the
+    /// zero Machine ID when it can't be read. Useful for tests and sandboxes
+    /// without access to the host ID.
+    ///
+    /// # Degraded Uniqueness
+    ///
+    /// Every host falling back shares the zero Machine ID, so IDs generated
+    /// by them only differ on Process ID and Counter and are far more likely
+    /// to collide. Use `Factory::new` or `Factory::try_default` on
+    /// production hosts.
+    pub fn new_or_nil() -> Self {
+        Self::new_or_nil_from(machine_id())
+    }
+
+    /// Creates a new `Factory` with the Machine ID read, or the zero Machine
+    /// ID when reading it failed
+    fn new_or_nil_from(machine_id: Result<MachineIdBytes>) -> Self {
+        #[cfg(feature = "log")]
+        let machine_id = machine_id.inspect_err(|err| {
+            log::warn!("Pxid factory falling back to zero Machine ID: {}", err);
+        });
+
+        Self::with_machine_id(machine_id.unwrap_or_default(), SEPARATOR)
+    }
+
+    /// Creates a new `Factory` with the provided `machine_id` and `separator`
+    fn with_machine_id(machine_id: MachineIdBytes, separator: char) -> Self {
+        Self {
+            process_id: Pxid::read_process_id(),
             machine_id,
-        })
+            separator,
+            padding: PrefixPadding::default(),
+            epoch: Epoch::default(),
+            deterministic: None,
+            validators: Vec::new(),
+            backfill_machine_id: None,
+        }
     }
 
-    pub(crate) fn new_counter_seed() -> u32 {
-        let mut rand_bytes: [u8; 3] = [0; 3];
+    /// Creates a new `Factory` where every ID is derived from the provided
+    /// `seed`, so the Nth ID generated for a given prefix is the same across
+    /// machines and runs. Useful for snapshot testing.
+    ///
+    /// Machine ID, Process ID and Counter seed are derived from the first
+    /// three outputs of [SplitMix64][1] seeded with `seed`:
+    ///
+    /// | Part         | Derivation                         |
+    /// | ------------ | ---------------------------------- |
+    /// | Machine ID   | 3 most significant bytes of output 1 |
+    /// | Process ID   | 2 most significant bytes of output 2 |
+    /// | Counter Seed | 3 most significant bytes of output 3 |
+    /// | Timestamp    | `DETERMINISTIC_TIMESTAMP`          |
+    ///
+    /// Each prefix holds its own sequence, the Counter for the Nth ID of a
+    /// prefix is `Counter Seed + N`.
+    ///
+    /// [1]: https://prng.di.unimi.it/splitmix64.c
+    pub fn deterministic(seed: u64) -> Self {
+        let mut state = seed;
+        let machine_id = splitmix64(&mut state).to_be_bytes();
+        let process_id = (splitmix64(&mut state) >> 48) as u16;
+        let counter_seed = (splitmix64(&mut state) >> 40) as u32;
+
+        Self {
+            process_id,
+            machine_id: [machine_id[0], machine_id[1], machine_id[2]],
+            separator: SEPARATOR,
+            padding: PrefixPadding::default(),
+            epoch: Epoch::default(),
+            deterministic: Some(Deterministic {
+                counter: AtomicU32::new(counter_seed),
+                counter_seed,
+                sequences: Mutex::new(HashMap::new()),
+            }),
+            validators: Vec::new(),
+            backfill_machine_id: None,
+        }
+    }
+
+    /// Checks whether the 3 bytes Counter has wrapped, which means IDs
+    /// generated within the same second by this `Factory` could collide.
+    pub fn counter_exhausted(&self) -> bool {
+        match &self.deterministic {
+            Some(deterministic) => deterministic
+                .sequences
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .values()
+                .any(|sequence| deterministic.counter_seed.saturating_add(*sequence) > COUNTER_MAX),
+            None => self.shared_counter().load(Ordering::SeqCst) > COUNTER_MAX,
+        }
+    }
+
+    /// Retrieves the Counter value to be used by the next generated ID.
+    ///
+    /// Only the 3 least significant bytes are encoded on IDs, values above
+    /// `COUNTER_MAX` mean the Counter wrapped, see `counter_exhausted`.
+    #[inline]
+    pub fn counter(&self) -> u32 {
+        self.shared_counter().load(Ordering::Relaxed)
+    }
+
+    /// Pins the Counter value to be used by the next generated ID, meant for
+    /// tests and recovery.
+    ///
+    /// # Concurrency
+    ///
+    /// The Counter is shared with `Pxid::new` and every other `Factory`, so
+    /// pinning it affects every ID generated in the process. The value is
+    /// stored atomically, but IDs being generated concurrently may already
+    /// hold a previous Counter value, and setting a value lower than the
+    /// current one repeats Counters, so IDs generated within the same second
+    /// may collide. Deterministic factories derive Counters from their seed
+    /// and ignore this value.
+    #[inline]
+    pub fn set_counter(&self, value: u32) {
+        self.shared_counter().store(value, Ordering::SeqCst);
+    }
+
+    /// Replaces the Counter with a fresh random seed, drawn as done when
+    /// creating the `Factory`, so long-running services can rotate the
+    /// Counter space.
+    ///
+    /// # Concurrency
+    ///
+    /// This is a best-effort operation on the Counter shared with
+    /// `Pxid::new` and every other `Factory`. IDs being generated
+    /// concurrently may still hold Counters from the previous seed, and the
+    /// new seed may fall right below Counters already used within the
+    /// current second, so IDs generated within it may collide as described
+    /// on `set_counter`. Deterministic factories derive Counters from their
+    /// seed and ignore this value.
+    #[inline]
+    pub fn reseed(&self) {
+        self.shared_counter()
+            .store(counter::new_seed(), Ordering::SeqCst);
+    }
+
+    /// Checks this `Factory` is able to generate valid IDs, useful as a
+    /// startup probe for long-running services.
+    ///
+    /// Machine ID resolution is retried so failures are surfaced, then a few
+    /// IDs with `SELF_TEST_PREFIX` are generated, and checked to round-trip
+    /// through `encode` and `decode` and to hold distinct Counters.
+    /// Deterministic factories only advance the `SELF_TEST_PREFIX` sequence.
+    pub fn self_test(&self) -> Result<()> {
+        if self.deterministic.is_none() {
+            machine_id()?;
+        }
+
+        let mut counters = Vec::with_capacity(SELF_TEST_IDS);
+
+        for _ in 0..SELF_TEST_IDS {
+            let id = self.new_id(SELF_TEST_PREFIX)?;
+            let encoded = self.encode(&id);
+
+            if self.decode(&encoded)? != id {
+                return Err(Error::SelfTest(format!("{encoded} doesn't round-trip")));
+            }
+
+            if id.machine_id() != self.machine_id || id.process_id() != self.process_id {
+                return Err(Error::SelfTest(format!(
+                    "{encoded} doesn't hold the Factory Machine ID and Process ID"
+                )));
+            }
+
+            if counters.contains(&id.counter()) {
+                return Err(Error::SelfTest(format!(
+                    "{} repeats Counter {}",
+                    encoded,
+                    id.counter()
+                )));
+            }
+
+            counters.push(id.counter());
+        }
+
+        Ok(())
+    }
+
+    /// Restarts the sequence of every prefix on deterministic factories,
+    /// has no effect otherwise.
+    pub fn reset(&self) {
+        if let Some(deterministic) = &self.deterministic {
+            deterministic
+                .sequences
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clear();
+        }
+    }
 
-        rand::thread_rng().fill_bytes(&mut rand_bytes);
-        u32::from_be_bytes([0, rand_bytes[0], rand_bytes[1], rand_bytes[2]])
+    /// Sets the `PrefixPadding` strategy used when generating IDs
+    #[must_use]
+    pub fn padding(mut self, padding: PrefixPadding) -> Self {
+        self.padding = padding;
+        self
     }
 
-    pub(crate) fn current_timestamp() -> u32 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Failed to retrive time")
-            .as_secs() as u32
+    /// Sets the `Epoch` Timestamps count seconds from, IDs generated with a
+    /// custom epoch must be read using `Pxid::timestamp_with_epoch`.
+    /// Deterministic factories keep using `DETERMINISTIC_TIMESTAMP`.
+    #[must_use]
+    pub fn epoch(mut self, epoch: Epoch) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    /// Adds a `Validator` run against every generated ID, in the order added.
+    ///
+    /// IDs failing validation are not retrieved, generation fails with
+    /// `Error::Validation` holding the first failure instead. The Counter
+    /// values of rejected IDs are still consumed.
+    #[must_use]
+    pub fn validator(mut self, validator: impl Validator + Send + Sync + 'static) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// Sets a dedicated Machine ID used by `Factory::backfill` instead of the
+    /// Machine ID with the `BACKFILL_MARKER` bit flipped.
+    ///
+    /// The provided Machine ID must not be used by any live host, otherwise
+    /// backfilled IDs share the collision domain of the IDs it generates.
+    #[must_use]
+    pub fn backfill_machine_id(mut self, machine_id: MachineIdBytes) -> Self {
+        self.backfill_machine_id = Some(machine_id);
+        self
+    }
+
+    /// Retrieves the Machine ID held by IDs generated with
+    /// `Factory::backfill`
+    pub fn backfill_marker(&self) -> MachineIdBytes {
+        if let Some(machine_id) = self.backfill_machine_id {
+            machine_id
+        } else {
+            let [first, second, third] = self.machine_id;
+
+            [first ^ BACKFILL_MARKER, second, third]
+        }
+    }
+
+    /// Retrieves the separator used by this `Factory`
+    #[inline]
+    pub fn separator(&self) -> char {
+        self.separator
+    }
+
+    /// Encodes the provided ID using this `Factory` separator
+    #[inline]
+    pub fn encode(&self, id: &Pxid) -> String {
+        id.to_string_with_separator(self.separator)
+    }
+
+    /// Decodes an ID encoded using this `Factory` separator
+    #[inline]
+    pub fn decode(&self, s: &str) -> Result<Pxid> {
+        Pxid::from_str_with_separator(s, self.separator)
+    }
+
+    pub(crate) fn current_timestamp() -> Result<u32> {
+        Epoch::Unix.timestamp(SystemTime::now())
     }
 
     /// Creates a new ID using the current timestamp
     #[inline]
     pub fn new_id(&self, prefix: &str) -> Result<Pxid> {
-        let current_timestamp = Self::current_timestamp();
+        self.new_with_time(prefix, self.timestamp()?)
+    }
+
+    /// Retrieves the ID `new_id` would generate for `prefix` without
+    /// consuming a Counter value, useful for previews and estimates.
+    ///
+    /// This is not atomic with the following `new_id` call, if another
+    /// thread generates an ID in between, or the current second elapses,
+    /// the ID actually generated differs from the peeked one.
+    pub fn peek_next(&self, prefix: &str) -> Result<Pxid> {
+        self.check_padding(prefix)?;
+
+        let counter = match &self.deterministic {
+            Some(deterministic) => deterministic.peek_counter(prefix),
+            None => self.shared_counter().load(Ordering::SeqCst),
+        };
 
-        self.new_with_time(prefix, current_timestamp)
+        Pxid::from_parts(
+            prefix,
+            self.timestamp()?,
+            self.machine_id,
+            self.process_id,
+            counter,
+        )
     }
 
     /// Creates a new ID with the provided `time`
     pub fn new_with_time(&self, prefix: &str, time: u32) -> Result<Pxid> {
-        let counter: u32 = self.counter.fetch_add(1, Ordering::SeqCst);
+        self.check_padding(prefix)?;
+
+        let counter = self.next_counters(prefix, 1);
+
+        warn_on_threshold(counter);
+
+        let id = Pxid::from_parts(prefix, time, self.machine_id, self.process_id, counter)?;
+
+        self.check_validators(&id)?;
+        Ok(id)
+    }
+
+    /// Creates an ID with the provided historical `time` for backfilled
+    /// records, which never collides with the IDs generated by this `Factory`
+    /// through the other methods, even for the same Timestamp and Counter.
+    ///
+    /// # Marker Scheme
+    ///
+    /// Backfilled IDs hold the Machine ID retrieved by
+    /// `Factory::backfill_marker` instead of the `Factory` Machine ID:
+    ///
+    /// - The dedicated Machine ID set with `Factory::backfill_machine_id`, if
+    ///   any.
+    /// - Otherwise, the `Factory` Machine ID with the most significant bit of
+    ///   its first byte flipped, this is, XOR `BACKFILL_MARKER`.
+    ///
+    /// Prefix, Timestamp, Process ID and Counter are set as done by
+    /// `Factory::new_with_time`, so the Machine ID is the only marker. IDs
+    /// keep no record of being backfilled, use `Pxid::is_backfilled` with the
+    /// same configuration to detect them.
+    ///
+    /// The flipped bit scheme only guarantees disjoint IDs from this host,
+    /// another host whose Machine ID differs on that bit shares the collision
+    /// domain of the backfilled IDs.
+    pub fn backfill(&self, prefix: &str, time: u32) -> Result<Pxid> {
+        self.check_padding(prefix)?;
+
+        let counter = self.next_counters(prefix, 1);
+        let id = Pxid::from_parts(
+            prefix,
+            time,
+            self.backfill_marker(),
+            self.process_id,
+            counter,
+        )?;
+
+        self.check_validators(&id)?;
+        Ok(id)
+    }
+
+    /// Creates `count` IDs sharing the current timestamp and holding
+    /// consecutive Counters, sorted in ascending order so they can be bulk
+    /// inserted in primary key order without sorting them.
+    ///
+    /// If the Counters wrap past `COUNTER_MAX`, the IDs holding the wrapped
+    /// Counters are moved to the start so the order is kept.
+    pub fn new_sorted_ids(&self, prefix: &str, count: usize) -> Result<Vec<Pxid>> {
+        if count > COUNTER_MAX as usize + 1 {
+            return Err(Error::BatchTooLarge(count));
+        }
+
+        self.check_padding(prefix)?;
+
+        let time = self.timestamp()?;
+        let count_u32 = u32::try_from(count).map_err(|_| Error::BatchTooLarge(count))?;
+        let first = self.next_counters(prefix, count_u32);
+        let mut ids = Vec::with_capacity(count);
+
+        for offset in 0..count_u32 {
+            let counter = first.wrapping_add(offset);
+
+            warn_on_threshold(counter);
+
+            let id = Pxid::from_parts(prefix, time, self.machine_id, self.process_id, counter)?;
+
+            self.check_validators(&id)?;
+            ids.push(id);
+        }
+
+        let before_wrap = (COUNTER_MAX - (first & COUNTER_MAX)) as usize + 1;
+
+        if before_wrap < count {
+            ids.rotate_left(before_wrap);
+        }
+
+        Ok(ids)
+    }
+
+    /// Retrieves the timestamp for the next ID, which is fixed on
+    /// deterministic factories
+    fn timestamp(&self) -> Result<u32> {
+        match self.deterministic {
+            Some(_) => Ok(DETERMINISTIC_TIMESTAMP),
+            None => self.epoch.timestamp(SystemTime::now()),
+        }
+    }
+
+    /// Reserves `count` consecutive Counters and retrieves the first one
+    fn next_counters(&self, prefix: &str, count: u32) -> u32 {
+        match &self.deterministic {
+            Some(deterministic) => deterministic.next_counters(prefix, count),
+            None => counter::next_counters(count),
+        }
+    }
+
+    /// Retrieves the Counter read by `counter` and written by `set_counter`
+    /// and `reseed`, which is `GLOBAL_COUNTER` unless the `Factory` is
+    /// deterministic
+    fn shared_counter(&self) -> &AtomicU32 {
+        match &self.deterministic {
+            Some(deterministic) => &deterministic.counter,
+            None => &counter::GLOBAL_COUNTER,
+        }
+    }
+
+    /// Runs the validators of this `Factory` against `id`, stopping on the
+    /// first failure
+    fn check_validators(&self, id: &Pxid) -> Result<()> {
+        for validator in &self.validators {
+            validator.validate(id)?;
+        }
 
-        Pxid::from_parts(prefix, time, self.machine_id, self.process_id, counter)
+        Ok(())
     }
+
+    fn check_padding(&self, prefix: &str) -> Result<()> {
+        if self.padding == PrefixPadding::Strict && prefix.len() < PREFIX_LENGTH {
+            return Err(Error::PrefixTooShort(prefix.to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+impl Deterministic {
+    /// Reserves `count` Counters for the next IDs of the provided `prefix`
+    /// and retrieves the first one
+    fn next_counters(&self, prefix: &str, count: u32) -> u32 {
+        let mut sequences = self
+            .sequences
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let sequence = sequences.entry(prefix.to_string()).or_insert(0);
+        let counter = self.counter_seed.wrapping_add(*sequence);
+
+        *sequence = sequence.wrapping_add(count);
+        counter
+    }
+
+    /// Retrieves the Counter for the next ID of the provided `prefix`
+    /// without advancing its sequence
+    fn peek_counter(&self, prefix: &str) -> u32 {
+        let sequences = self
+            .sequences
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let sequence = sequences.get(prefix).copied().unwrap_or(0);
+
+        self.counter_seed.wrapping_add(sequence)
+    }
+}
+
+/// Warns once each time the Counter crosses the warning threshold
+#[inline]
+#[cfg_attr(not(feature = "log"), allow(unused_variables))]
+fn warn_on_threshold(counter: u32) {
+    #[cfg(feature = "log")]
+    if counter & COUNTER_MAX == COUNTER_WARNING_THRESHOLD + 1 {
+        log::warn!("Pxid counter approaching exhaustion: {}", counter);
+    }
+}
+
+/// Advances the [SplitMix64][1] `state` and retrieves its next output
+///
+/// [1]: https://prng.di.unimi.it/splitmix64.c
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+    let mut z = *state;
+
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
 }
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::validate::{NotNil, PrefixAllowList, ValidationError};
+
     use super::*;
 
+    #[test]
+    fn creates_factory_with_try_default() {
+        let factory = Factory::try_default().unwrap();
+        let id = factory.new_id("acct").unwrap();
+
+        // Off Linux each `machine_id` call draws a random Host ID, so the
+        // Machine ID is checked against the IDs the `Factory` generates
+        assert_eq!(id.machine_id(), factory.machine_id);
+        assert_eq!(
+            factory.new_id("acct").unwrap().machine_id(),
+            id.machine_id()
+        );
+        assert_eq!(factory.separator(), SEPARATOR);
+    }
+
+    #[test]
+    fn creates_factory_or_falls_back_to_nil_machine_id() {
+        let factory = Factory::new_or_nil_from(Ok([1, 2, 3]));
+
+        assert_eq!(factory.new_id("acct").unwrap().machine_id(), [1, 2, 3]);
+        assert!(Factory::new_or_nil().new_id("acct").is_ok());
+
+        let factory = Factory::new_or_nil_from(Err(Error::MachineID(String::from("No host ID"))));
+        let id = factory.new_id("acct").unwrap();
+
+        assert_eq!(id.machine_id(), [0; 3]);
+        assert_eq!(id.process_id(), Pxid::read_process_id());
+        assert_ne!(factory.new_id("acct").unwrap(), id);
+    }
+
     #[test]
     fn factory_never_repeats() {
         const TRYOUTS: usize = 1000;
@@ -81,9 +622,297 @@ mod tests {
             specimen.push(id);
         }
 
-        for id in specimen.iter() {
+        for id in &specimen {
             let count = specimen.iter().filter(|&x| x == id).count();
             assert_eq!(count, 1);
         }
     }
+
+    #[test]
+    fn factory_pads_short_prefixes_with_nul() {
+        let factory = Factory::new().unwrap().padding(PrefixPadding::Nul);
+        let id = factory.new_id("dog").unwrap();
+
+        assert_eq!(id.prefix_bytes(), [b'd', b'o', b'g', 0]);
+        assert_eq!(Pxid::from_str(&id.to_string()).unwrap(), id);
+    }
+
+    #[test]
+    fn factory_rejects_short_prefixes_when_strict() {
+        let factory = Factory::new().unwrap().padding(PrefixPadding::Strict);
+        let id = factory.new_id("acct").unwrap();
+
+        assert_eq!(Pxid::from_str(&id.to_string()).unwrap(), id);
+        assert_eq!(
+            factory.new_id("dog"),
+            Err(Error::PrefixTooShort(String::from("dog")))
+        );
+    }
+
+    #[test]
+    fn factory_runs_validators_on_generated_ids() {
+        let factory = Factory::deterministic(7)
+            .validator(NotNil)
+            .validator(PrefixAllowList::new(["acct", "dog"]))
+            .validator(|id: &Pxid| match id.counter() % 2 {
+                0 => Ok(()),
+                _ => Err(ValidationError::new("odd_counter", "Counter must be even.")),
+            });
+        let results = [factory.new_id("acct"), factory.new_id("acct")];
+
+        assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+        assert!(
+            results.contains(&Err(Error::Validation(vec![ValidationError::new(
+                "odd_counter",
+                "Counter must be even."
+            )])))
+        );
+        assert!(results.iter().flatten().all(|id| id.counter() % 2 == 0));
+        assert_eq!(
+            factory.new_id("user").unwrap_err(),
+            Error::Validation(vec![ValidationError::new(
+                "prefix_not_allowed",
+                "Prefix \"user\" is not one of: acct, dog."
+            )])
+        );
+        assert!(matches!(
+            factory.new_sorted_ids("dog", 2),
+            Err(Error::Validation(errors)) if errors[0].code == "odd_counter"
+        ));
+    }
+
+    #[test]
+    fn detects_backfilled_ids() {
+        let factory = Factory::deterministic(7);
+        let live = factory.new_with_time("acct", 1_600_000_000).unwrap();
+        let backfilled = factory.backfill("acct", 1_600_000_000).unwrap();
+        let [first, second, third] = factory.machine_id;
+
+        assert_eq!(live.counter() + 1, backfilled.counter());
+        assert_eq!(backfilled.machine_id(), [first ^ 0x80, second, third]);
+        assert_eq!(backfilled.timestamp(), live.timestamp());
+        assert_eq!(Pxid::from_str(&backfilled.to_string()), Ok(backfilled));
+        assert!(backfilled.is_backfilled(&factory));
+        assert!(!live.is_backfilled(&factory));
+
+        let dedicated = Factory::deterministic(7).backfill_machine_id([0, 0, 1]);
+        let backfilled = dedicated.backfill("acct", 1_600_000_000).unwrap();
+
+        assert_eq!(backfilled.machine_id(), [0, 0, 1]);
+        assert!(backfilled.is_backfilled(&dedicated));
+        assert!(!backfilled.is_backfilled(&factory));
+        assert!(!dedicated.new_id("acct").unwrap().is_backfilled(&dedicated));
+    }
+
+    #[test]
+    fn factory_encodes_with_separator() {
+        let factory = Factory::with_separator('.').unwrap();
+        let id = factory.new_id("acct").unwrap();
+        let encoded = factory.encode(&id);
+
+        assert_eq!(factory.separator(), '.');
+        assert!(encoded.starts_with("acct."));
+        assert_eq!(factory.decode(&encoded).unwrap(), id);
+    }
+
+    #[test]
+    fn generates_sorted_ids() {
+        let factory = Factory::new().unwrap();
+        let ids = factory.new_sorted_ids("acct", 1000).unwrap();
+        let mut sorted = ids.clone();
+
+        sorted.sort();
+
+        assert_eq!(ids.len(), 1000);
+        assert_eq!(ids, sorted);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn generates_sorted_ids_on_deterministic_factories() {
+        let factory = Factory::deterministic(42);
+        let ids = factory.new_sorted_ids("acct", 3).unwrap();
+        let next = factory.new_id("acct").unwrap();
+
+        factory.reset();
+
+        let expected = (0..4)
+            .map(|_| factory.new_id("acct").unwrap())
+            .collect::<Vec<Pxid>>();
+
+        assert_eq!(ids, expected[..3]);
+        assert_eq!(next, expected[3]);
+    }
+
+    #[test]
+    fn sorted_ids_reject_oversized_batches() {
+        let factory = Factory::new().unwrap().padding(PrefixPadding::Strict);
+
+        assert_eq!(factory.new_sorted_ids("acct", 0), Ok(Vec::new()));
+        assert_eq!(
+            factory.new_sorted_ids("acct", COUNTER_MAX as usize + 2),
+            Err(Error::BatchTooLarge(COUNTER_MAX as usize + 2))
+        );
+        assert_eq!(
+            factory.new_sorted_ids("usr", 2),
+            Err(Error::PrefixTooShort(String::from("usr")))
+        );
+    }
+
+    #[test]
+    fn factory_uses_unix_epoch_by_default() {
+        let factory = Factory::new().unwrap();
+        let before = Epoch::Unix.timestamp(SystemTime::now()).unwrap();
+        let id = factory.new_id("acct").unwrap();
+        let after = Epoch::Unix.timestamp(SystemTime::now()).unwrap();
+        let expected = (before..=after)
+            .map(|time| {
+                Pxid::from_parts(
+                    "acct",
+                    time,
+                    factory.machine_id,
+                    factory.process_id,
+                    id.counter(),
+                )
+                .unwrap()
+            })
+            .collect::<Vec<Pxid>>();
+
+        assert!(expected.contains(&id));
+        assert_eq!(
+            Factory::new().unwrap().epoch(Epoch::Unix).epoch,
+            factory.epoch
+        );
+    }
+
+    #[test]
+    fn factory_generates_ids_with_custom_epoch() {
+        let epoch =
+            Epoch::Custom(UNIX_EPOCH + Duration::from_secs(u64::from(DETERMINISTIC_TIMESTAMP)));
+        let factory = Factory::new().unwrap().epoch(epoch);
+        let before = SystemTime::now();
+        let id = factory.new_id("acct").unwrap();
+        let time = id.timestamp_with_epoch(epoch).unwrap();
+
+        assert!(time + Duration::from_secs(1) >= before);
+        assert!(time <= SystemTime::now());
+        assert!(
+            id.timestamp() < UNIX_EPOCH + Duration::from_secs(u64::from(DETERMINISTIC_TIMESTAMP))
+        );
+    }
+
+    #[test]
+    fn factory_rejects_times_out_of_epoch_range() {
+        let future = SystemTime::now() + Duration::from_hours(1);
+        let factory = Factory::new().unwrap().epoch(Epoch::Custom(future));
+
+        assert!(matches!(
+            factory.new_id("acct"),
+            Err(Error::TimestampOutOfRange(secs)) if secs < 0
+        ));
+        assert!(matches!(
+            factory.peek_next("acct"),
+            Err(Error::TimestampOutOfRange(_))
+        ));
+        assert!(matches!(
+            factory.new_sorted_ids("acct", 2),
+            Err(Error::TimestampOutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn peek_next_matches_next_deterministic_id() {
+        let factory = Factory::deterministic(42);
+
+        factory.new_id("acct").unwrap();
+
+        let peeked = factory.peek_next("acct").unwrap();
+
+        assert_eq!(
+            factory.peek_next("ordr"),
+            Factory::deterministic(42).new_id("ordr")
+        );
+        assert_eq!(factory.new_id("acct").unwrap(), peeked);
+    }
+
+    #[test]
+    fn peek_next_checks_padding() {
+        let factory = Factory::new().unwrap().padding(PrefixPadding::Strict);
+
+        assert_eq!(
+            factory.peek_next("dog"),
+            Err(Error::PrefixTooShort(String::from("dog")))
+        );
+    }
+
+    #[test]
+    fn self_test_succeeds() {
+        assert_eq!(Factory::new().unwrap().self_test(), Ok(()));
+        assert_eq!(Factory::with_separator('.').unwrap().self_test(), Ok(()));
+    }
+
+    #[test]
+    fn self_test_keeps_deterministic_sequences() {
+        let factory = Factory::deterministic(42);
+        let other = Factory::deterministic(42);
+
+        factory.self_test().unwrap();
+
+        assert_eq!(factory.new_id("acct"), other.new_id("acct"));
+    }
+
+    #[test]
+    fn deterministic_factory_generates_known_sequence() {
+        let factory = Factory::deterministic(42);
+        let ids = (0..5)
+            .map(|_| factory.new_id("acct").unwrap().to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            ids,
+            vec![
+                "acct_bo5u205tqsp2hrq7a9jg",
+                "acct_bo5u205tqsp2hrq7a9k0",
+                "acct_bo5u205tqsp2hrq7a9kg",
+                "acct_bo5u205tqsp2hrq7a9l0",
+                "acct_bo5u205tqsp2hrq7a9lg",
+            ]
+        );
+
+        let first = Pxid::from_str(&ids[0]).unwrap();
+
+        assert_eq!(first.machine_id(), [0xbd, 0xd7, 0x32]);
+        assert_eq!(first.process_id(), 0x28ef);
+        assert_eq!(first.counter(), 0x47_5267);
+    }
+
+    #[test]
+    fn deterministic_factory_sequences_are_independent_per_prefix() {
+        let factory = Factory::deterministic(42);
+        let other = Factory::deterministic(42);
+
+        let first = factory.new_id("acct").unwrap();
+        factory.new_id("ordr").unwrap();
+        factory.new_id("ordr").unwrap();
+        let second = factory.new_id("acct").unwrap();
+
+        assert_eq!(first, other.new_id("acct").unwrap());
+        assert_eq!(second, other.new_id("acct").unwrap());
+    }
+
+    #[test]
+    fn deterministic_factory_reset_restarts_sequence() {
+        let factory = Factory::deterministic(42);
+        let first = (0..3)
+            .map(|_| factory.new_id("acct").unwrap())
+            .collect::<Vec<Pxid>>();
+
+        factory.reset();
+
+        let second = (0..3)
+            .map(|_| factory.new_id("acct").unwrap())
+            .collect::<Vec<Pxid>>();
+
+        assert_eq!(first, second);
+    }
 }
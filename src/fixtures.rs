@@ -0,0 +1,150 @@
+//! Stable fixture IDs for tests, available with the `test-util` feature.
+//!
+//! # Stability
+//!
+//! Every fixture holds the same bytes and string form across releases, so
+//! they are safe to use on snapshot tests. Fixtures are built out of fixed
+//! parts:
+//!
+//! | Part       | Value                                         |
+//! | ---------- | --------------------------------------------- |
+//! | Timestamp  | `DETERMINISTIC_TIMESTAMP`, 2020-01-01T00:00:00Z |
+//! | Machine ID | `FIXTURE_MACHINE_ID`, `0x70 0x78 0x64`        |
+//! | Process ID | `FIXTURE_PROCESS_ID`, `1`                     |
+//! | Counter    | Fixture number `n`                            |
+//!
+//! Exact string forms are locked by tests within this crate, any change to
+//! them is a breaking change.
+//!
+//! ```no_run
+//! use pxid::fixtures;
+//!
+//! assert_eq!(fixtures::ACCT_1.to_string(), "acct_bo5u203gf1i00080000g");
+//! assert_eq!(fixtures::with_prefix("ordr", 1), fixtures::ORDR_1);
+//! ```
+use crate::factory::DETERMINISTIC_TIMESTAMP;
+use crate::host_id::MachineIdBytes;
+use crate::id::{Pxid, PREFIX_LENGTH};
+
+/// Machine ID used by fixtures, `pxd` in ASCII
+pub const FIXTURE_MACHINE_ID: MachineIdBytes = [0x70, 0x78, 0x64];
+
+/// Process ID used by fixtures
+pub const FIXTURE_PROCESS_ID: u16 = 1;
+
+/// First Account fixture
+pub const ACCT_1: Pxid = fixture(*b"acct", 1);
+
+/// First Order fixture
+pub const ORDR_1: Pxid = fixture(*b"ordr", 1);
+
+/// Second Order fixture
+pub const ORDR_2: Pxid = fixture(*b"ordr", 2);
+
+/// Third Order fixture
+pub const ORDR_3: Pxid = fixture(*b"ordr", 3);
+
+/// Fourth Order fixture
+pub const ORDR_4: Pxid = fixture(*b"ordr", 4);
+
+/// Fifth Order fixture
+pub const ORDR_5: Pxid = fixture(*b"ordr", 5);
+
+const fn fixture(prefix_bytes: [u8; PREFIX_LENGTH], n: u8) -> Pxid {
+    Pxid::from_parts_unchecked(
+        prefix_bytes,
+        DETERMINISTIC_TIMESTAMP,
+        FIXTURE_MACHINE_ID,
+        FIXTURE_PROCESS_ID,
+        n as u32,
+    )
+}
+
+/// Retrieves the `n`th fixture for the provided `prefix`, fixtures with the
+/// same prefix are sorted by `n`.
+///
+/// # Panics
+///
+/// If the prefix is empty or longer than 4 bytes.
+#[allow(clippy::panic)]
+pub fn with_prefix(prefix: &str, n: u8) -> Pxid {
+    Pxid::from_parts(
+        prefix,
+        DETERMINISTIC_TIMESTAMP,
+        FIXTURE_MACHINE_ID,
+        FIXTURE_PROCESS_ID,
+        u32::from(n),
+    )
+    .unwrap_or_else(|err| panic!("Invalid fixture prefix {prefix:?}. {err}"))
+}
+
+/// Retrieves every named fixture, for table-driven tests
+pub fn all() -> Vec<Pxid> {
+    vec![ACCT_1, ORDR_1, ORDR_2, ORDR_3, ORDR_4, ORDR_5]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locks_fixture_strings() {
+        assert_eq!(
+            all().iter().map(Pxid::to_string).collect::<Vec<String>>(),
+            vec![
+                "acct_bo5u203gf1i00080000g",
+                "ordr_bo5u203gf1i00080000g",
+                "ordr_bo5u203gf1i000800010",
+                "ordr_bo5u203gf1i00080001g",
+                "ordr_bo5u203gf1i000800020",
+                "ordr_bo5u203gf1i00080002g",
+            ]
+        );
+    }
+
+    #[test]
+    fn locks_fixture_bytes() {
+        assert_eq!(
+            *ACCT_1,
+            [
+                0x61, 0x63, 0x63, 0x74, 0x5e, 0x0b, 0xe1, 0x00, 0x70, 0x78, 0x64, 0x00, 0x01, 0x00,
+                0x00, 0x01
+            ]
+        );
+    }
+
+    #[test]
+    fn builds_fixtures_for_any_prefix() {
+        assert_eq!(with_prefix("acct", 1), ACCT_1);
+        assert_eq!(
+            (1..=5)
+                .map(|n| with_prefix("ordr", n))
+                .collect::<Vec<Pxid>>(),
+            all()[1..]
+        );
+        assert_eq!(
+            with_prefix("usr", 7),
+            Pxid::from_parts_unchecked(
+                *b"usr\0",
+                DETERMINISTIC_TIMESTAMP,
+                FIXTURE_MACHINE_ID,
+                FIXTURE_PROCESS_ID,
+                7
+            )
+        );
+        assert!(with_prefix("usr", 1) < with_prefix("usr", 2));
+    }
+
+    #[test]
+    fn fixtures_are_valid() {
+        for id in all() {
+            assert_eq!(id.validate(), Ok(()), "{id}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid fixture prefix \"account\"")]
+    fn panics_on_invalid_prefix() {
+        with_prefix("account", 1);
+    }
+}
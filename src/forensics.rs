@@ -0,0 +1,204 @@
+//! Helpers to trace IDs back to the host and process which generated them,
+//! useful during incident response.
+//!
+//! ```no_run
+//! # let ids: Vec<pxid::Pxid> = Vec::new();
+//! # let inventory: Vec<String> = Vec::new();
+//! use pxid::forensics::{group_by_origin, match_host};
+//!
+//! for group in group_by_origin(ids) {
+//!     let host = match_host(&group.machine_id, inventory.iter().map(String::as_str));
+//!
+//!     println!("{:?} {} {} IDs", host, group.process_id, group.count);
+//! }
+//! ```
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::SystemTime;
+
+use crate::host_id::{machine_id_from, MachineIdBytes};
+use crate::id::Pxid;
+
+/// Max amount of IDs kept as sample on each `OriginGroup`
+pub const SAMPLE_SIZE: usize = 5;
+
+/// IDs sharing the same Machine ID and Process ID
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OriginGroup {
+    /// Machine ID as lowercase hexadecimal
+    pub machine_id: String,
+    pub process_id: u16,
+    pub count: usize,
+    pub min_timestamp: SystemTime,
+    pub max_timestamp: SystemTime,
+    /// First `SAMPLE_SIZE` IDs found for this origin
+    pub sample: Vec<Pxid>,
+}
+
+/// Groups IDs by their Machine ID and Process ID, sorted by count in
+/// descending order. Groups with the same count are sorted by Machine ID and
+/// Process ID.
+pub fn group_by_origin(ids: impl IntoIterator<Item = Pxid>) -> Vec<OriginGroup> {
+    let mut groups: HashMap<(MachineIdBytes, u16), OriginGroup> = HashMap::new();
+
+    for id in ids {
+        let timestamp = id.timestamp();
+        let group = groups
+            .entry((id.machine_id(), id.process_id()))
+            .or_insert_with(|| OriginGroup {
+                machine_id: to_hex(id.machine_id()),
+                process_id: id.process_id(),
+                count: 0,
+                min_timestamp: timestamp,
+                max_timestamp: timestamp,
+                sample: Vec::with_capacity(SAMPLE_SIZE),
+            });
+
+        group.count += 1;
+        group.min_timestamp = group.min_timestamp.min(timestamp);
+        group.max_timestamp = group.max_timestamp.max(timestamp);
+
+        if group.sample.len() < SAMPLE_SIZE {
+            group.sample.push(id);
+        }
+    }
+
+    let mut groups = groups.into_values().collect::<Vec<OriginGroup>>();
+
+    groups.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.machine_id.cmp(&b.machine_id))
+            .then_with(|| a.process_id.cmp(&b.process_id))
+    });
+
+    groups
+}
+
+/// Finds the first candidate Host ID or hostname deriving the provided
+/// hexadecimal Machine ID, as done by `pxid::machine_id`. Surrounding
+/// whitespaces on candidates are trimmed, as done when reading Host IDs.
+pub fn match_host<'a>(
+    machine_id_hex: &str,
+    host_names: impl Iterator<Item = &'a str>,
+) -> Option<String> {
+    let machine_id = from_hex(machine_id_hex)?;
+
+    host_names
+        .map(str::trim)
+        .find(|candidate| machine_id_from(candidate) == machine_id)
+        .map(str::to_string)
+}
+
+fn to_hex(bytes: MachineIdBytes) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(6), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+fn from_hex(hex: &str) -> Option<MachineIdBytes> {
+    let mut bytes: MachineIdBytes = [0_u8; 3];
+
+    if hex.len() != bytes.len() * 2 || !hex.is_ascii() {
+        return None;
+    }
+
+    for (idx, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[idx * 2..idx * 2 + 2], 16).ok()?;
+    }
+
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+
+    const HOST_A: [u8; 3] = [0x0a, 0x0b, 0x0c];
+    const HOST_B: [u8; 3] = [0xf0, 0x0d, 0x01];
+
+    fn id(machine_id: MachineIdBytes, process_id: u16, time: u32, counter: u32) -> Pxid {
+        Pxid::from_parts_unchecked(*b"acct", time, machine_id, process_id, counter)
+    }
+
+    #[test]
+    fn groups_ids_by_origin() {
+        let ids = vec![
+            id(HOST_A, 1, 300, 1),
+            id(HOST_B, 7, 100, 1),
+            id(HOST_A, 1, 100, 2),
+            id(HOST_A, 2, 200, 1),
+            id(HOST_A, 1, 200, 3),
+        ];
+        let groups = group_by_origin(ids.clone());
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(
+            groups[0],
+            OriginGroup {
+                machine_id: String::from("0a0b0c"),
+                process_id: 1,
+                count: 3,
+                min_timestamp: UNIX_EPOCH + Duration::from_secs(100),
+                max_timestamp: UNIX_EPOCH + Duration::from_mins(5),
+                sample: vec![ids[0], ids[2], ids[4]],
+            }
+        );
+        assert_eq!(
+            groups[1..]
+                .iter()
+                .map(|group| (group.machine_id.as_str(), group.process_id, group.count))
+                .collect::<Vec<_>>(),
+            vec![("0a0b0c", 2, 1), ("f00d01", 7, 1)]
+        );
+    }
+
+    #[test]
+    fn bounds_group_samples() {
+        let groups = group_by_origin((0..20).map(|counter| id(HOST_A, 1, 100, counter)));
+
+        assert_eq!(groups[0].count, 20);
+        assert_eq!(groups[0].sample.len(), SAMPLE_SIZE);
+    }
+
+    #[test]
+    fn matches_host_deriving_machine_id() {
+        let host_id = "b08dfa6083e7567a1921a715000001fb";
+        let hex = to_hex(machine_id_from(host_id));
+        let candidates = ["web-01", host_id, "web-02"];
+
+        assert_eq!(
+            match_host(&hex, candidates.iter().copied()),
+            Some(String::from(host_id))
+        );
+        assert_eq!(
+            match_host(&hex, [" b08dfa6083e7567a1921a715000001fb\n"].into_iter()),
+            Some(String::from(host_id))
+        );
+    }
+
+    #[test]
+    fn matches_current_host() {
+        let host_id = crate::host_id().unwrap();
+        let hex = to_hex(machine_id_from(&host_id));
+
+        assert_eq!(
+            match_host(&hex, ["unknown", host_id.as_str()].into_iter()),
+            Some(host_id)
+        );
+    }
+
+    #[test]
+    fn returns_none_without_matching_host() {
+        let hex = to_hex(machine_id_from("b08dfa6083e7567a1921a715000001fb"));
+
+        assert_eq!(match_host(&hex, ["web-01", "web-02"].into_iter()), None);
+        assert_eq!(match_host("zz0b0c", ["web-01"].into_iter()), None);
+        assert_eq!(match_host("0a0b", ["web-01"].into_iter()), None);
+    }
+}
@@ -0,0 +1,250 @@
+//! Recovery parsing for IDs transcribed by humans.
+//!
+//! Characters in the encoding alphabet are never changed, even if they are
+//! commonly confused with others (`0` and `o`, `1`, `l` and `i`, `u` and
+//! `v`), given that there is no way to tell which one was intended. Only
+//! characters outside the alphabet which are a different form of exactly one
+//! alphabet character are corrected:
+//!
+//! | Input                                  | Corrected to     |
+//! | -------------------------------------- | ---------------- |
+//! | ASCII uppercase `A` to `V`             | `a` to `v`       |
+//! | Fullwidth digits `０` to `９`          | `0` to `9`       |
+//! | Fullwidth letters `ａ` to `ｖ`, `Ａ` to `Ｖ` | `a` to `v`   |
+//! | Cyrillic and Greek homoglyphs          | See `CONFUSABLES` |
+//!
+//! Characters such as `w`, `x`, `y` and `z` have no safe mapping and fail
+//! parsing.
+use crate::error::DecodeError;
+use crate::id::{Pxid, ENCODING_CHARS, SEPARATOR};
+
+/// Homoglyphs rendered the same as a single encoding alphabet character
+pub const CONFUSABLES: [(char, char); 9] = [
+    ('а', 'a'), // Cyrillic Small Letter A
+    ('с', 'c'), // Cyrillic Small Letter Es
+    ('е', 'e'), // Cyrillic Small Letter Ie
+    ('і', 'i'), // Cyrillic Small Letter Byelorussian-Ukrainian I
+    ('ј', 'j'), // Cyrillic Small Letter Je
+    ('о', 'o'), // Cyrillic Small Letter O
+    ('р', 'p'), // Cyrillic Small Letter Er
+    ('ο', 'o'), // Greek Small Letter Omicron
+    ('ѕ', 's'), // Cyrillic Small Letter Dze
+];
+
+/// Outcome of `Pxid::parse_fuzzy`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FuzzyResult {
+    /// The input is a valid Pxid as is
+    Exact(Pxid),
+
+    /// The input is a valid Pxid after replacing confusable characters.
+    /// Each substitution holds the character position in the input, the
+    /// original character and its replacement.
+    Corrected {
+        id: Pxid,
+        substitutions: Vec<(usize, char, char)>,
+    },
+
+    /// The input cannot be decoded even after replacing confusable
+    /// characters
+    Failed(DecodeError),
+}
+
+impl Pxid {
+    /// Parses a Pxid correcting characters outside the encoding alphabet
+    /// which have an unambiguous mapping into it, as described on the
+    /// `fuzzy` module. Only the encoded XID is corrected, prefixes are kept
+    /// as provided.
+    ///
+    /// Strict parsing through `FromStr` is not affected.
+    pub fn parse_fuzzy(s: &str) -> FuzzyResult {
+        let err = match Pxid::decode_str(s) {
+            Ok(id) => return FuzzyResult::Exact(id),
+            Err(err) => err,
+        };
+
+        let Some((prefix, xid)) = s.split_once(SEPARATOR) else {
+            return FuzzyResult::Failed(err);
+        };
+
+        let prefix_chars = prefix.chars().count() + 1;
+        let mut corrected = String::with_capacity(s.len());
+        let mut substitutions = Vec::new();
+
+        corrected.push_str(prefix);
+        corrected.push(SEPARATOR);
+
+        for (pos, (offset, c)) in xid.char_indices().enumerate() {
+            if c.is_ascii() && ENCODING_CHARS.contains(&(c as u8)) {
+                corrected.push(c);
+                continue;
+            }
+
+            match confusable(c) {
+                Some(replacement) => {
+                    substitutions.push((prefix_chars + pos, c, replacement));
+                    corrected.push(replacement);
+                }
+                None => {
+                    return FuzzyResult::Failed(DecodeError::InvalidChar(
+                        xid.to_string(),
+                        c,
+                        offset,
+                    ));
+                }
+            }
+        }
+
+        if substitutions.is_empty() {
+            return FuzzyResult::Failed(err);
+        }
+
+        match Pxid::decode_str(&corrected) {
+            Ok(id) => FuzzyResult::Corrected { id, substitutions },
+            Err(err) => FuzzyResult::Failed(err),
+        }
+    }
+}
+
+/// Retrieves the encoding alphabet character `c` is a different form of
+fn confusable(c: char) -> Option<char> {
+    match c {
+        'A'..='V' => Some(c.to_ascii_lowercase()),
+        '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32),
+        'ａ'..='ｖ' => char::from_u32(c as u32 - 'ａ' as u32 + 'a' as u32),
+        'Ａ'..='Ｖ' => char::from_u32(c as u32 - 'Ａ' as u32 + 'a' as u32),
+        _ => CONFUSABLES
+            .iter()
+            .find(|(confusable, _)| *confusable == c)
+            .map(|(_, replacement)| *replacement),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    const ID: &str = "acct_9m4e2mr0ui3e8a215n4g";
+
+    fn assert_corrected(input: &str, expected: Vec<(usize, char, char)>) {
+        assert_eq!(
+            Pxid::parse_fuzzy(input),
+            FuzzyResult::Corrected {
+                id: Pxid::from_str(ID).unwrap(),
+                substitutions: expected,
+            },
+            "{input}"
+        );
+    }
+
+    #[test]
+    fn returns_exact_for_valid_input() {
+        assert_eq!(
+            Pxid::parse_fuzzy(ID),
+            FuzzyResult::Exact(Pxid::from_str(ID).unwrap())
+        );
+    }
+
+    #[test]
+    fn corrects_uppercase_characters() {
+        assert_corrected(
+            "acct_9M4E2MR0UI3E8A215N4G",
+            vec![
+                (6, 'M', 'm'),
+                (8, 'E', 'e'),
+                (10, 'M', 'm'),
+                (11, 'R', 'r'),
+                (13, 'U', 'u'),
+                (14, 'I', 'i'),
+                (16, 'E', 'e'),
+                (18, 'A', 'a'),
+                (22, 'N', 'n'),
+                (24, 'G', 'g'),
+            ],
+        );
+    }
+
+    #[test]
+    fn corrects_each_mapped_character() {
+        let mut mapped = Vec::new();
+
+        mapped.extend(('A'..='V').zip('a'..='v'));
+        mapped.extend(('０'..='９').zip('0'..='9'));
+        mapped.extend(('ａ'..='ｖ').zip('a'..='v'));
+        mapped.extend(('Ａ'..='Ｖ').zip('a'..='v'));
+        mapped.extend(CONFUSABLES);
+
+        for (c, replacement) in mapped {
+            let expected = format!("acct_{replacement}0000000000000000000");
+            let input = format!("acct_{c}0000000000000000000");
+
+            assert_eq!(
+                Pxid::parse_fuzzy(&input),
+                FuzzyResult::Corrected {
+                    id: Pxid::from_str(&expected).unwrap(),
+                    substitutions: vec![(5, c, replacement)],
+                },
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn reports_positions_after_multibyte_prefixes() {
+        let input = "cañ_9m4e2mr0ui3e8a215n4ｇ";
+        let id = Pxid::from_str("cañ_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(
+            Pxid::parse_fuzzy(input),
+            FuzzyResult::Corrected {
+                id,
+                substitutions: vec![(23, 'ｇ', 'g')],
+            }
+        );
+    }
+
+    #[test]
+    fn corrects_multiple_substitutions() {
+        assert_corrected(
+            "acct_9m4е2mr0ui3e8a215n４g",
+            vec![(8, 'е', 'e'), (23, '４', '4')],
+        );
+    }
+
+    #[test]
+    fn fails_on_characters_without_safe_mapping() {
+        for c in ['w', 'x', 'y', 'z', 'W', 'X', 'Y', 'Z', '-', 'ω'] {
+            let xid = format!("9m4e2mr0ui3e8a215n4{c}");
+
+            assert_eq!(
+                Pxid::parse_fuzzy(&format!("acct_{xid}")),
+                FuzzyResult::Failed(DecodeError::InvalidChar(xid, c, 19))
+            );
+        }
+    }
+
+    #[test]
+    fn fails_without_substitutions() {
+        assert_eq!(
+            Pxid::parse_fuzzy("acct9m4e2mr0ui3e8a215n4g"),
+            FuzzyResult::Failed(DecodeError::MissingPrefix(String::from(
+                "acct9m4e2mr0ui3e8a215n4g"
+            )))
+        );
+    }
+
+    #[test]
+    fn fails_when_corrected_input_is_invalid() {
+        assert_eq!(
+            Pxid::parse_fuzzy("acct_9M4E"),
+            FuzzyResult::Failed(DecodeError::InvalidLength(String::from("acct_9m4e"), 9))
+        );
+    }
+
+    #[test]
+    fn strict_parsing_is_unaffected() {
+        assert!(Pxid::from_str("acct_9M4E2MR0UI3E8A215N4G").is_err());
+    }
+}
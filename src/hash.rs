@@ -0,0 +1,141 @@
+//! Fast hashing for `Pxid` keys based on [FxHash][1].
+//!
+//! `FxPxidHasher` folds the 16 bytes of a `Pxid` into two big endian `u64`
+//! words with one multiply each, which is considerably faster than the
+//! default `SipHash` used by `std::collections::HashMap`.
+//!
+//! # HashDoS
+//!
+//! Unlike `SipHash`, this hasher is not keyed, so an attacker able to choose
+//! the IDs used as keys can craft collisions and degrade map operations to
+//! linear time. Only use `PxidMap` and `PxidSet` when keys are not controlled
+//! by untrusted parties, the default `HashMap` hasher remains the safe choice
+//! otherwise.
+//!
+//! [1]: https://github.com/rust-lang/rustc-hash
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasherDefault, Hasher};
+
+use crate::id::Pxid;
+
+/// FxHash multiplier constant
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+/// `HashMap` using `Pxid` keys hashed with `FxPxidHasher`
+pub type PxidMap<V> = HashMap<Pxid, V, BuildPxidHasher>;
+
+/// `HashSet` of `Pxid` values hashed with `FxPxidHasher`
+pub type PxidSet = HashSet<Pxid, BuildPxidHasher>;
+
+/// `BuildHasher` for `FxPxidHasher`
+pub type BuildPxidHasher = BuildHasherDefault<FxPxidHasher>;
+
+/// Hasher specialized for `Pxid` keys.
+///
+/// Words are always folded as `u64` values, so hashes are the same on both
+/// 32-bit and 64-bit targets.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FxPxidHasher {
+    hash: u64,
+}
+
+impl FxPxidHasher {
+    #[inline]
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxPxidHasher {
+    /// Folds `bytes` in big endian `u64` words, so the trailing bytes of the
+    /// `Pxid` (the Counter) end up on the lower bits of the hash.
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0_u8; 8];
+
+            for (dst, src) in word.iter_mut().zip(chunk) {
+                *dst = *src;
+            }
+
+            self.add_to_hash(u64::from_be_bytes(word));
+        }
+    }
+
+    /// Slice lengths are constant for `Pxid` keys, so these are mixed in
+    /// without a multiply
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.hash ^= i as u64;
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::BuildHasher;
+    use std::str::FromStr;
+
+    use crate::Factory;
+
+    use super::*;
+
+    fn hash(id: &Pxid) -> u64 {
+        BuildPxidHasher::default().hash_one(id)
+    }
+
+    #[test]
+    fn equal_ids_hash_equal() {
+        let a = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let b = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(hash(&a), hash(&b));
+    }
+
+    #[test]
+    fn hash_is_target_independent() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let mut expected = FxPxidHasher::default();
+
+        expected.hash ^= 16;
+        expected.add_to_hash(0x6163_6374_4d88_e15b);
+        expected.add_to_hash(0x60f4_86e4_2841_2dc9);
+
+        assert_eq!(hash(&id), expected.finish());
+    }
+
+    #[test]
+    fn collision_rate_sanity() {
+        const TRYOUTS: usize = 1_000_000;
+
+        let factory = Factory::new().unwrap();
+        let hashes = (0..TRYOUTS)
+            .map(|_| hash(&factory.new_id("acct").unwrap()))
+            .collect::<HashSet<u64>>();
+
+        assert_eq!(hashes.len(), TRYOUTS);
+    }
+
+    #[test]
+    fn works_as_map_and_set_hasher() {
+        let factory = Factory::new().unwrap();
+        let ids = (0..1000)
+            .map(|_| factory.new_id("acct").unwrap())
+            .collect::<Vec<Pxid>>();
+        let mut map = PxidMap::default();
+        let set = ids.iter().copied().collect::<PxidSet>();
+
+        for (idx, id) in ids.iter().enumerate() {
+            map.insert(*id, idx);
+        }
+
+        for (idx, id) in ids.iter().enumerate() {
+            assert_eq!(map.get(id), Some(&idx));
+            assert!(set.contains(id));
+        }
+    }
+}
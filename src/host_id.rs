@@ -1,29 +1,75 @@
 use md5::compute;
 use rand::{distributions::Alphanumeric, Rng};
 
+#[cfg(target_os = "linux")]
+use std::fs::read_to_string;
+
+#[cfg(target_os = "linux")]
+use std::path::Path;
+
 use crate::Result;
 
 /// Machine ID first 3 bytes
 pub type MachineIdBytes = [u8; 3];
 
+/// Files holding a Host ID on Linux, relative to the filesystem root and
+/// sorted by priority
+#[cfg(target_os = "linux")]
+const LINUX_HOST_ID_PATHS: [&str; 3] = [
+    "etc/machine-id",
+    "var/lib/dbus/machine-id",
+    "proc/sys/kernel/random/boot_id",
+];
+
 /// Retrieves a Machine ID using system based approach
 pub fn machine_id() -> Result<MachineIdBytes> {
-    let mut bytes: MachineIdBytes = [0_u8; 3];
     let host_id = host_id()?;
 
-    bytes.copy_from_slice(&compute(host_id)[0..3]);
+    Ok(machine_id_from(&host_id))
+}
 
-    Ok(bytes)
+/// Derives the Machine ID bytes from the provided Host ID, using the first
+/// 3 bytes of its MD5 digest
+pub(crate) fn machine_id_from(host_id: &str) -> MachineIdBytes {
+    let [a, b, c, ..] = compute(host_id).0;
+
+    [a, b, c]
 }
 
 // https://github.com/rs/xid/blob/e6fb919be3fc74f2b846a6d174e57e076a38b1c1/id.go#L124
-// #[cfg(not(target_os = "macos"))]
+#[cfg(not(target_os = "linux"))]
+pub fn host_id() -> Result<String> {
+    Ok(random_host_id())
+}
+
+/// Retrieves the Host ID from `/etc/machine-id`, `/var/lib/dbus/machine-id`
+/// or `/proc/sys/kernel/random/boot_id` in that order, using the first
+/// non-empty value found.
+///
+/// Falls back to a random Host ID if none of these files is available.
+#[cfg(target_os = "linux")]
 pub fn host_id() -> Result<String> {
-    Ok(rand::thread_rng()
+    Ok(read_host_id(Path::new("/")).unwrap_or_else(random_host_id))
+}
+
+/// Reads the first non-empty Host ID from `LINUX_HOST_ID_PATHS` relative to
+/// the provided `base` path
+#[cfg(target_os = "linux")]
+fn read_host_id(base: &Path) -> Option<String> {
+    LINUX_HOST_ID_PATHS.iter().find_map(|path| {
+        read_to_string(base.join(path))
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    })
+}
+
+fn random_host_id() -> String {
+    rand::thread_rng()
         .sample_iter(&Alphanumeric)
         .take(3)
         .map(char::from)
-        .collect::<String>())
+        .collect::<String>()
 }
 
 // #[cfg(any(target_os = "macos"))]
@@ -39,3 +85,75 @@ pub fn host_id() -> Result<String> {
 
 //     Ok(machine_id)
 // }
+
+#[cfg(target_os = "linux")]
+#[cfg(test)]
+mod tests {
+    use std::fs::{create_dir_all, remove_dir_all, write};
+    use std::path::PathBuf;
+
+    use super::*;
+
+    /// Creates a temporary root with the provided files written relative
+    /// to it
+    fn make_root(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("pxid-host-id-{}-{}", name, std::process::id()));
+
+        let _ = remove_dir_all(&root);
+
+        for (path, contents) in files {
+            let path = root.join(path);
+
+            create_dir_all(path.parent().unwrap()).unwrap();
+            write(path, contents).unwrap();
+        }
+
+        root
+    }
+
+    #[test]
+    fn reads_etc_machine_id_first() {
+        let root = make_root(
+            "etc",
+            &[
+                ("etc/machine-id", "etc-machine-id\n"),
+                ("var/lib/dbus/machine-id", "dbus-machine-id\n"),
+                ("proc/sys/kernel/random/boot_id", "boot-id\n"),
+            ],
+        );
+
+        assert_eq!(read_host_id(&root), Some(String::from("etc-machine-id")));
+        remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn falls_back_to_dbus_machine_id() {
+        let root = make_root(
+            "dbus",
+            &[
+                ("etc/machine-id", "\n"),
+                ("var/lib/dbus/machine-id", "dbus-machine-id\n"),
+                ("proc/sys/kernel/random/boot_id", "boot-id\n"),
+            ],
+        );
+
+        assert_eq!(read_host_id(&root), Some(String::from("dbus-machine-id")));
+        remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn falls_back_to_boot_id() {
+        let root = make_root("boot", &[("proc/sys/kernel/random/boot_id", "boot-id\n")]);
+
+        assert_eq!(read_host_id(&root), Some(String::from("boot-id")));
+        remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn returns_none_without_host_id_files() {
+        let root = make_root("none", &[]);
+
+        assert_eq!(read_host_id(&root), None);
+    }
+}
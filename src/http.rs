@@ -0,0 +1,495 @@
+//! HTTP semantics for Pxid errors using [RFC 7807][1] Problem Details.
+//!
+//! [1]: https://www.rfc-editor.org/rfc/rfc7807
+use serde::Serialize;
+
+use crate::error::{DecodeError, Error};
+use crate::factory::COUNTER_MAX;
+use crate::validate::display_all;
+
+/// Problem type for `DecodeError::MissingPrefix`
+pub const MISSING_PREFIX_TYPE: &str = "https://github.com/whizzes/pxid/problems/missing-prefix";
+
+/// Problem type for `DecodeError::InvalidLength`
+pub const INVALID_LENGTH_TYPE: &str = "https://github.com/whizzes/pxid/problems/invalid-length";
+
+/// Problem type for `DecodeError::InvalidPrefixLength`
+pub const INVALID_PREFIX_LENGTH_TYPE: &str =
+    "https://github.com/whizzes/pxid/problems/invalid-prefix-length";
+
+/// Problem type for `DecodeError::InvalidChar`
+pub const INVALID_CHAR_TYPE: &str = "https://github.com/whizzes/pxid/problems/invalid-char";
+
+/// Problem type for `DecodeError::InvalidUtf8`
+pub const INVALID_UTF8_TYPE: &str = "https://github.com/whizzes/pxid/problems/invalid-utf8";
+
+/// Problem type for `DecodeError::InvalidXidLength`
+pub const INVALID_XID_LENGTH_TYPE: &str =
+    "https://github.com/whizzes/pxid/problems/invalid-xid-length";
+
+/// Problem type for `DecodeError::InvalidChecksum`
+pub const INVALID_CHECKSUM_TYPE: &str = "https://github.com/whizzes/pxid/problems/invalid-checksum";
+
+/// Problem type for `DecodeError::InvalidUlid`
+pub const INVALID_ULID_TYPE: &str = "https://github.com/whizzes/pxid/problems/invalid-ulid";
+
+/// Problem type for `Error::MachineID`
+pub const MACHINE_ID_TYPE: &str = "https://github.com/whizzes/pxid/problems/machine-id";
+
+/// Problem type for the deprecated `Error::PrefixExceedsMaxLength`
+pub const PREFIX_EXCEEDS_MAX_LENGTH_TYPE: &str =
+    "https://github.com/whizzes/pxid/problems/prefix-exceeds-max-length";
+
+/// Problem type for `Error::PrefixTooShort`
+pub const PREFIX_TOO_SHORT_TYPE: &str = "https://github.com/whizzes/pxid/problems/prefix-too-short";
+
+/// Problem type for `Error::UnregisteredPrefix`
+pub const UNREGISTERED_PREFIX_TYPE: &str =
+    "https://github.com/whizzes/pxid/problems/unregistered-prefix";
+
+/// Problem type for `Error::PrefixLimitReached`
+pub const PREFIX_LIMIT_REACHED_TYPE: &str =
+    "https://github.com/whizzes/pxid/problems/prefix-limit-reached";
+
+/// Problem type for `Error::PrefixMismatch`
+pub const PREFIX_MISMATCH_TYPE: &str = "https://github.com/whizzes/pxid/problems/prefix-mismatch";
+
+/// Problem type for `Error::BatchTooLarge`
+pub const BATCH_TOO_LARGE_TYPE: &str = "https://github.com/whizzes/pxid/problems/batch-too-large";
+
+/// Problem type for `Error::SelfTest`
+pub const SELF_TEST_TYPE: &str = "https://github.com/whizzes/pxid/problems/self-test";
+
+/// Problem type for `Error::InvalidPrefixChar`
+pub const INVALID_PREFIX_CHAR_TYPE: &str =
+    "https://github.com/whizzes/pxid/problems/invalid-prefix-char";
+
+/// Problem type for `Error::ZeroTimestamp`
+pub const ZERO_TIMESTAMP_TYPE: &str = "https://github.com/whizzes/pxid/problems/zero-timestamp";
+
+/// Problem type for `Error::ZeroMachineId`
+pub const ZERO_MACHINE_ID_TYPE: &str = "https://github.com/whizzes/pxid/problems/zero-machine-id";
+
+/// Problem type for `Error::NilPxid`
+pub const NIL_PXID_TYPE: &str = "https://github.com/whizzes/pxid/problems/nil-pxid";
+
+/// Problem type for `Error::DuplicatePrefix`
+pub const DUPLICATE_PREFIX_TYPE: &str = "https://github.com/whizzes/pxid/problems/duplicate-prefix";
+
+/// Problem type for `Error::TimestampOutOfRange`
+pub const TIMESTAMP_OUT_OF_RANGE_TYPE: &str =
+    "https://github.com/whizzes/pxid/problems/timestamp-out-of-range";
+
+/// Problem type for `Error::InvalidBinaryPrefix`
+pub const INVALID_BINARY_PREFIX_TYPE: &str =
+    "https://github.com/whizzes/pxid/problems/invalid-binary-prefix";
+
+/// Problem type for `Error::InvalidBinaryLength`
+pub const INVALID_BINARY_LENGTH_TYPE: &str =
+    "https://github.com/whizzes/pxid/problems/invalid-binary-length";
+
+/// Problem type for `Error::NullArrayValue`
+pub const NULL_ARRAY_VALUE_TYPE: &str = "https://github.com/whizzes/pxid/problems/null-array-value";
+
+/// Problem type for `Error::SkewedTimestamp`
+pub const SKEWED_TIMESTAMP_TYPE: &str = "https://github.com/whizzes/pxid/problems/skewed-timestamp";
+
+/// Problem type for `Error::Validation`
+pub const VALIDATION_TYPE: &str = "https://github.com/whizzes/pxid/problems/validation";
+
+/// Problem type for `Error::PaddingOnly`
+pub const PADDING_ONLY_TYPE: &str = "https://github.com/whizzes/pxid/problems/padding-only";
+
+/// Problem type for `Error::InteriorPadding`
+pub const INTERIOR_PADDING_TYPE: &str = "https://github.com/whizzes/pxid/problems/interior-padding";
+
+/// Problem type for `Error::InvalidAlphabetChar`
+pub const INVALID_ALPHABET_CHAR_TYPE: &str =
+    "https://github.com/whizzes/pxid/problems/invalid-alphabet-char";
+
+/// Problem type for `Error::DuplicateAlphabetChar`
+pub const DUPLICATE_ALPHABET_CHAR_TYPE: &str =
+    "https://github.com/whizzes/pxid/problems/duplicate-alphabet-char";
+
+/// Max amount of characters from the offending input included in the
+/// `detail` field
+pub const MAX_DETAIL_INPUT_LENGTH: usize = 32;
+
+/// Problem Details as described by [RFC 7807][1]
+///
+/// [1]: https://www.rfc-editor.org/rfc/rfc7807#section-3.1
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub title: &'static str,
+    pub status: u16,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+}
+
+impl DecodeError {
+    /// Retrieves the HTTP Status Code corresponding to this error
+    pub fn status_code(&self) -> u16 {
+        match self {
+            DecodeError::MissingPrefix(_)
+            | DecodeError::InvalidLength(_, _)
+            | DecodeError::InvalidPrefixLength(_, _)
+            | DecodeError::InvalidChar(_, _, _)
+            | DecodeError::InvalidUtf8(_)
+            | DecodeError::InvalidXidLength(_, _)
+            | DecodeError::InvalidChecksum(_, _)
+            | DecodeError::InvalidUlid(_) => 400,
+        }
+    }
+}
+
+/// Builds a `ProblemDetails` instance out of a Pxid `Error`, the offending
+/// input is truncated to `MAX_DETAIL_INPUT_LENGTH` characters.
+// A single arm per `Error` variant keeps every problem type in one place
+#[allow(clippy::too_many_lines)]
+pub fn problem(err: &Error, instance: Option<&str>) -> ProblemDetails {
+    let (kind, title, status, detail) = match err {
+        Error::Decode(decode) => {
+            let (kind, title, detail) = match decode {
+                DecodeError::MissingPrefix(input) => (
+                    MISSING_PREFIX_TYPE,
+                    "Missing Pxid Prefix",
+                    format!("Failed to retrieve the prefix from {}", truncate(input)),
+                ),
+                DecodeError::InvalidLength(input, len) => (
+                    INVALID_LENGTH_TYPE,
+                    "Invalid Pxid Length",
+                    format!("{} has an invalid length of {}", truncate(input), len),
+                ),
+                DecodeError::InvalidPrefixLength(input, len) => (
+                    INVALID_PREFIX_LENGTH_TYPE,
+                    "Invalid Pxid Prefix Length",
+                    format!(
+                        "Prefix {} has an invalid length of {}",
+                        truncate(input),
+                        len
+                    ),
+                ),
+                DecodeError::InvalidChar(input, c, pos) => (
+                    INVALID_CHAR_TYPE,
+                    "Invalid Pxid Character",
+                    format!(
+                        "{} contains the invalid character {:?} at position {}",
+                        truncate(input),
+                        c,
+                        pos
+                    ),
+                ),
+                DecodeError::InvalidUtf8(utf8) => {
+                    (INVALID_UTF8_TYPE, "Invalid Pxid UTF-8", utf8.to_string())
+                }
+                DecodeError::InvalidXidLength(input, len) => (
+                    INVALID_XID_LENGTH_TYPE,
+                    "Invalid Pxid XID Length",
+                    format!("XID {} has an invalid length of {}", truncate(input), len),
+                ),
+                DecodeError::InvalidChecksum(input, _) => (
+                    INVALID_CHECKSUM_TYPE,
+                    "Invalid Pxid Checksum",
+                    format!("{} has an invalid check character", truncate(input)),
+                ),
+                DecodeError::InvalidUlid(input) => (
+                    INVALID_ULID_TYPE,
+                    "Invalid Pxid ULID",
+                    format!("{} is not a valid XID encoded as ULID", truncate(input)),
+                ),
+            };
+
+            (kind, title, decode.status_code(), detail)
+        }
+        Error::MachineID(reason) => (
+            MACHINE_ID_TYPE,
+            "Machine ID Unavailable",
+            500,
+            format!("Failed to retrieve Machine ID. {}", truncate(reason)),
+        ),
+        #[allow(deprecated)]
+        Error::PrefixExceedsMaxLength(input) => (
+            PREFIX_EXCEEDS_MAX_LENGTH_TYPE,
+            "Pxid Prefix Too Long",
+            400,
+            format!("Prefix {} exceeds the max length of 4", truncate(input)),
+        ),
+        Error::PrefixTooShort(input) => (
+            PREFIX_TOO_SHORT_TYPE,
+            "Pxid Prefix Too Short",
+            400,
+            format!("Prefix {} is shorter than 4 characters", truncate(input)),
+        ),
+        Error::UnregisteredPrefix(input) => (
+            UNREGISTERED_PREFIX_TYPE,
+            "Pxid Prefix Not Registered",
+            422,
+            format!("Prefix {} is not registered", truncate(input)),
+        ),
+        Error::PrefixLimitReached(input, max) => (
+            PREFIX_LIMIT_REACHED_TYPE,
+            "Pxid Prefix Limit Reached",
+            422,
+            format!(
+                "Cannot register prefix {}, max allowed prefixes are {}",
+                truncate(input),
+                max
+            ),
+        ),
+        Error::PrefixMismatch { expected, found } => (
+            PREFIX_MISMATCH_TYPE,
+            "Pxid Prefix Mismatch",
+            400,
+            format!(
+                "Expected prefix {}, but received {}",
+                truncate(expected),
+                truncate(found)
+            ),
+        ),
+        Error::BatchTooLarge(count) => (
+            BATCH_TOO_LARGE_TYPE,
+            "Pxid Batch Too Large",
+            400,
+            format!(
+                "Batch of {} IDs exceeds the max batch size of {}",
+                count,
+                COUNTER_MAX as usize + 1
+            ),
+        ),
+        Error::SelfTest(reason) => (
+            SELF_TEST_TYPE,
+            "Pxid Factory Self Test Failed",
+            500,
+            format!("Factory self test failed. {}", truncate(reason)),
+        ),
+        Error::InvalidPrefixChar(input, c) => (
+            INVALID_PREFIX_CHAR_TYPE,
+            "Invalid Pxid Prefix Character",
+            400,
+            format!(
+                "{} prefix contains the invalid character {:?}",
+                truncate(input),
+                c
+            ),
+        ),
+        Error::ZeroTimestamp(input) => (
+            ZERO_TIMESTAMP_TYPE,
+            "Zero Pxid Timestamp",
+            400,
+            format!("{} has a zero timestamp", truncate(input)),
+        ),
+        Error::ZeroMachineId(input) => (
+            ZERO_MACHINE_ID_TYPE,
+            "Zero Pxid Machine ID",
+            400,
+            format!("{} has a zero Machine ID", truncate(input)),
+        ),
+        Error::NilPxid => (
+            NIL_PXID_TYPE,
+            "Nil Pxid",
+            400,
+            String::from("Expected a non nil Pxid"),
+        ),
+        Error::DuplicatePrefix(input) => (
+            DUPLICATE_PREFIX_TYPE,
+            "Pxid Prefix Already Registered",
+            409,
+            format!(
+                "Prefix {} is already registered with a different entry",
+                truncate(input)
+            ),
+        ),
+        Error::TimestampOutOfRange(secs) => (
+            TIMESTAMP_OUT_OF_RANGE_TYPE,
+            "Pxid Timestamp Out Of Range",
+            500,
+            format!("Timestamp {secs} doesn't fit in 4 bytes"),
+        ),
+        Error::InvalidBinaryPrefix(bytes) => (
+            INVALID_BINARY_PREFIX_TYPE,
+            "Invalid Pxid Prefix Bytes",
+            400,
+            format!("Prefix bytes {bytes:02x?} are not valid UTF-8"),
+        ),
+        Error::InvalidBinaryLength(len) => (
+            INVALID_BINARY_LENGTH_TYPE,
+            "Invalid Pxid Binary Length",
+            400,
+            format!("Binary form has {len} bytes instead of 16"),
+        ),
+        Error::NullArrayValue(idx) => (
+            NULL_ARRAY_VALUE_TYPE,
+            "Null Pxid Array Value",
+            400,
+            format!("Expected a Pxid, but found a null value at index {idx}"),
+        ),
+        Error::SkewedTimestamp(input, violation) => (
+            SKEWED_TIMESTAMP_TYPE,
+            "Skewed Pxid Timestamp",
+            422,
+            format!("{} timestamp is skewed. {}", truncate(input), violation),
+        ),
+        Error::Validation(errors) => (
+            VALIDATION_TYPE,
+            "Pxid Validation Failed",
+            422,
+            display_all(errors),
+        ),
+        Error::PaddingOnly => (
+            PADDING_ONLY_TYPE,
+            "Blank Fixed Width Pxid",
+            400,
+            String::from("Fixed width string only holds padding"),
+        ),
+        Error::InteriorPadding(input, pos) => (
+            INTERIOR_PADDING_TYPE,
+            "Invalid Fixed Width Pxid Padding",
+            400,
+            format!("{} holds padding at position {}", truncate(input), pos),
+        ),
+        Error::InvalidAlphabetChar(byte, pos) => (
+            INVALID_ALPHABET_CHAR_TYPE,
+            "Invalid Pxid Alphabet",
+            500,
+            format!("Alphabet holds the invalid character {byte:#04x} at position {pos}"),
+        ),
+        Error::DuplicateAlphabetChar(c, pos) => (
+            DUPLICATE_ALPHABET_CHAR_TYPE,
+            "Invalid Pxid Alphabet",
+            500,
+            format!(
+                "Alphabet holds the character {c:?} more than once, repeated at position {pos}"
+            ),
+        ),
+    };
+
+    ProblemDetails {
+        kind,
+        title,
+        status,
+        detail,
+        instance: instance.map(std::string::ToString::to_string),
+    }
+}
+
+/// Truncates the provided input to `MAX_DETAIL_INPUT_LENGTH` characters
+fn truncate(input: &str) -> String {
+    match input.char_indices().nth(MAX_DETAIL_INPUT_LENGTH) {
+        Some((idx, _)) => format!("{}...", input.get(..idx).unwrap_or(input)),
+        None => input.to_string(),
+    }
+}
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for ProblemDetails {
+    fn into_response(self) -> axum::response::Response {
+        let status = axum::http::StatusCode::from_u16(self.status)
+            .unwrap_or(axum::http::StatusCode::BAD_REQUEST);
+
+        (
+            status,
+            [(axum::http::header::CONTENT_TYPE, "application/problem+json")],
+            axum::Json(self),
+        )
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use serde_json::json;
+
+    use crate::Pxid;
+
+    use super::*;
+
+    fn problem_json(input: &str) -> serde_json::Value {
+        let err = Pxid::from_str(input).unwrap_err();
+
+        serde_json::to_value(problem(&err, Some("/accounts"))).unwrap()
+    }
+
+    #[test]
+    fn builds_missing_prefix_problem() {
+        assert_eq!(
+            problem_json("acct9m4e2mr0ui3e8a215n4g"),
+            json!({
+                "type": MISSING_PREFIX_TYPE,
+                "title": "Missing Pxid Prefix",
+                "status": 400,
+                "detail": "Failed to retrieve the prefix from acct9m4e2mr0ui3e8a215n4g",
+                "instance": "/accounts",
+            })
+        );
+    }
+
+    #[test]
+    fn builds_invalid_length_problem() {
+        assert_eq!(
+            problem_json("invalid"),
+            json!({
+                "type": INVALID_LENGTH_TYPE,
+                "title": "Invalid Pxid Length",
+                "status": 400,
+                "detail": "invalid has an invalid length of 7",
+                "instance": "/accounts",
+            })
+        );
+    }
+
+    #[test]
+    fn builds_invalid_prefix_length_problem() {
+        assert_eq!(
+            problem_json("account_9m4e2mr0ui3e8a21"),
+            json!({
+                "type": INVALID_PREFIX_LENGTH_TYPE,
+                "title": "Invalid Pxid Prefix Length",
+                "status": 400,
+                "detail": "Prefix account has an invalid length of 7",
+                "instance": "/accounts",
+            })
+        );
+    }
+
+    #[test]
+    fn builds_invalid_char_problem() {
+        assert_eq!(
+            problem_json("acct_9m4e2mr0ui3e8a215n4x"),
+            json!({
+                "type": INVALID_CHAR_TYPE,
+                "title": "Invalid Pxid Character",
+                "status": 400,
+                "detail": "9m4e2mr0ui3e8a215n4x contains the invalid character 'x' at position 19",
+                "instance": "/accounts",
+            })
+        );
+    }
+
+    #[test]
+    fn omits_instance_when_not_provided() {
+        let err = Pxid::from_str("invalid").unwrap_err();
+        let value = serde_json::to_value(problem(&err, None)).unwrap();
+
+        assert!(value.get("instance").is_none());
+    }
+
+    #[test]
+    fn bounds_offending_input_length_on_detail() {
+        let input = "a".repeat(1024);
+        let err = Error::Decode(DecodeError::InvalidLength(input.clone(), input.len()));
+        let details = problem(&err, None);
+
+        assert_eq!(
+            details.detail,
+            format!(
+                "{}... has an invalid length of 1024",
+                "a".repeat(MAX_DETAIL_INPUT_LENGTH)
+            )
+        );
+    }
+}
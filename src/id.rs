@@ -1,17 +1,17 @@
+use std::borrow::Cow;
+use std::cmp::Ordering as CmpOrdering;
 use std::fmt::{self, Debug, Display};
+use std::io;
 use std::ops::Deref;
 use std::process;
 use std::str::{from_utf8, FromStr};
-use std::sync::atomic::{AtomicU32, Ordering};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use rand::RngCore;
-
 #[cfg(feature = "async-graphql")]
 use async_graphql::connection::CursorType;
 
 #[cfg(feature = "async-graphql")]
-use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value, ID};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -19,56 +19,51 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 #[cfg(feature = "serde")]
 use serde::de::Visitor;
 
+use crate::alphabet::Alphabet;
+use crate::counter;
+use crate::epoch::Epoch;
 use crate::error::{DecodeError, Error};
+use crate::factory::{Factory, COUNTER_MAX};
 use crate::host_id::{machine_id, MachineIdBytes};
+use crate::wrapper::check_prefix;
 use crate::Result;
 
-/// Statically creates an array of bytes which is then used to decode a
-/// `String` into an Pxid instance.
-const fn make_decoding_dec() -> [u8; 256] {
-    let mut decoding_bytes = [0_u8; 256];
-
-    decoding_bytes[48] = 0;
-    decoding_bytes[49] = 1;
-    decoding_bytes[50] = 2;
-    decoding_bytes[51] = 3;
-    decoding_bytes[52] = 4;
-    decoding_bytes[53] = 5;
-    decoding_bytes[54] = 6;
-    decoding_bytes[55] = 7;
-    decoding_bytes[56] = 8;
-    decoding_bytes[57] = 9;
-    decoding_bytes[97] = 10;
-    decoding_bytes[98] = 11;
-    decoding_bytes[99] = 12;
-    decoding_bytes[100] = 13;
-    decoding_bytes[101] = 14;
-    decoding_bytes[102] = 15;
-    decoding_bytes[103] = 16;
-    decoding_bytes[104] = 17;
-    decoding_bytes[105] = 18;
-    decoding_bytes[106] = 19;
-    decoding_bytes[107] = 20;
-    decoding_bytes[108] = 21;
-    decoding_bytes[109] = 22;
-    decoding_bytes[110] = 23;
-    decoding_bytes[111] = 24;
-    decoding_bytes[112] = 25;
-    decoding_bytes[113] = 26;
-    decoding_bytes[114] = 27;
-    decoding_bytes[115] = 28;
-    decoding_bytes[116] = 29;
-    decoding_bytes[117] = 30;
-    decoding_bytes[118] = 31;
-    decoding_bytes
-}
+/// Characters allowed on the encoded XID, in the order of their value on
+/// the base32 encoding. Decoding is case-sensitive, so a matching regex is
+/// `[0-9a-v]{20}`.
+pub const XID_CHARSET: &str = "0123456789abcdefghijklmnopqrstuv";
+
+/// Characters allowed on prefixes by `Pxid::validate`, which are ASCII
+/// alphanumeric characters. Prefixes hold from 1 to 4 of them, so a matching
+/// regex is `[0-9A-Za-z]{1,4}`.
+pub const PREFIX_CHARSET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
 
 /// Pxid encoding character collection
-pub const ENCODING_CHARS: &[u8] = "0123456789abcdefghijklmnopqrstuv".as_bytes();
+pub const ENCODING_CHARS: &[u8] = XID_CHARSET.as_bytes();
+
+/// Crockford's Base32 encoding character collection used by ULIDs
+pub const ULID_ENCODING_CHARS: &[u8] = "0123456789ABCDEFGHJKMNPQRSTVWXYZ".as_bytes();
+
+/// ULID string encoded length
+pub const ULID_ENCODED_LENGTH: usize = 26;
+
+/// Separator between the prefix and the encoded XID, which is not part of
+/// `PREFIX_CHARSET` nor `XID_CHARSET`
+pub const SEPARATOR: char = '_';
+
+/// Prefix used on the string form of the nil Pxid
+pub const NIL_PREFIX: &str = "nil";
 
 /// Pxid string encoded length
 pub const ENCODED_LENGTH: usize = 25;
 
+/// Character right-padding the fixed width form written by
+/// `Pxid::to_fixed_string`, which never appears on encoded IDs
+pub const FIXED_PAD: char = ' ';
+
+/// Pxid string encoded length including the trailing check character
+pub const CHECKSUM_ENCODED_LENGTH: usize = ENCODED_LENGTH + 1;
+
 /// XID Encoded Length
 pub const XID_ENCODED_LENGTH: usize = 20;
 
@@ -81,22 +76,24 @@ pub const BINARY_LENGTH: usize = 16;
 /// Xid binary raw length
 pub const XID_BINARY_LENGTH: usize = 12;
 
-pub const DECODING_BYTES: [u8; 256] = make_decoding_dec();
-
-/// Total parts found when splitting XID from Prefix on an encoded value
-pub const ENCODED_PARTS_LENGTH: usize = 2;
-
 /// Pxid instance Bytes
 pub type Bytes = [u8; BINARY_LENGTH];
 
 /// Pxid Instance
 ///
+/// Pxid is `Copy` — all method calls that take `&self` and return `Pxid`
+/// produce copies without heap allocation, as the ID is a plain 16 bytes
+/// array. Pass it by value rather than by reference.
+///
+/// IDs are ordered by their bytes, so IDs with the same prefix are sorted by
+/// Timestamp, Machine ID, Process ID and Counter.
+///
 /// ## Packed Data Layout
 ///
 /// Each Pxid instance bytes uses a "packed bytes" approach.
 /// This means that bytes in a XID instance have a layout.
 ///
-/// ```ignore
+/// ```text
 /// V V V V W W W W X X X Y Y Z Z Z
 /// └─────┘ └─────┘ └───┘ └─┘ └───┘
 ///    |       |      |    |    |
@@ -106,16 +103,97 @@ pub type Bytes = [u8; BINARY_LENGTH];
 ///               Machine ID
 /// ```
 ///
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Pxid(pub(crate) Bytes);
 
 impl Pxid {
-    /// Retrieves the Prefix as UTF-8 Encoded characters
+    /// Retrieves the Prefix as UTF-8 Encoded characters, including the NUL
+    /// padding of prefixes shorter than 4 bytes. Use `Pxid::prefix_str` to
+    /// borrow the Prefix without padding instead.
     #[inline]
     pub fn prefix(&self) -> Result<String> {
-        Ok(from_utf8(&self.prefix_bytes())
-            .map_err(|err| Error::Decode(DecodeError::InvalidUtf8(err)))?
-            .to_string())
+        self.padded_prefix_str().map(str::to_string)
+    }
+
+    /// Borrows the Prefix without its trailing NUL padding, without
+    /// allocating. The nil Pxid has an empty Prefix.
+    ///
+    /// IDs are built with canonical padding, NUL bytes only trail the Prefix,
+    /// so the Prefix always spans the first `Pxid::prefix_len` bytes.
+    #[inline]
+    pub fn prefix_str(&self) -> Result<&str> {
+        self.padded_prefix_str()
+            .map(|prefix| prefix.trim_end_matches('\0'))
+    }
+
+    /// Retrieves the length in bytes of the Prefix without its trailing NUL
+    /// padding
+    #[inline]
+    pub fn prefix_len(&self) -> usize {
+        self.0
+            .iter()
+            .take(PREFIX_LENGTH)
+            .rposition(|byte| *byte != 0)
+            .map_or(0, |idx| idx + 1)
+    }
+
+    /// Borrows the Prefix bytes including their NUL padding as UTF-8
+    #[inline]
+    fn padded_prefix_str(&self) -> Result<&str> {
+        from_utf8(self.0.get(..PREFIX_LENGTH).unwrap_or_default())
+            .map_err(|err| Error::Decode(DecodeError::InvalidUtf8(err)))
+    }
+
+    /// Creates the nil Pxid, with every byte set to zero.
+    ///
+    /// The nil Pxid is encoded as `nil_00000000000000000000` instead of
+    /// using its 4 NUL prefix characters, and such string decodes back into
+    /// the nil Pxid.
+    #[inline]
+    pub const fn nil() -> Self {
+        Self([0_u8; BINARY_LENGTH])
+    }
+
+    /// Checks whether this is the nil Pxid
+    #[inline]
+    pub fn is_nil(&self) -> bool {
+        self.0 == [0_u8; BINARY_LENGTH]
+    }
+
+    /// Runs every consistency check on this Pxid, meant for IDs coming from
+    /// untrusted sources, by calling it after `Pxid::from_str`:
+    ///
+    /// 1. Prefix bytes are valid UTF-8
+    /// 2. Prefix holds ASCII alphanumeric characters, followed by NUL padding
+    /// 3. Timestamp is not zero
+    /// 4. Machine ID has at least one non-zero byte
+    pub fn validate(&self) -> Result<()> {
+        self.validate_prefix()?;
+
+        if self.0[4..8] == [0; 4] {
+            return Err(Error::ZeroTimestamp(self.to_string()));
+        }
+
+        if self.machine_id() == [0; 3] {
+            return Err(Error::ZeroMachineId(self.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Runs the Prefix checks done by `validate`
+    pub(crate) fn validate_prefix(&self) -> Result<()> {
+        let trimmed = self.prefix_str()?;
+
+        if let Some(c) = trimmed.chars().find(|c| !c.is_ascii_alphanumeric()) {
+            return Err(Error::InvalidPrefixChar(self.to_string(), c));
+        }
+
+        if trimmed.is_empty() {
+            return Err(Error::Decode(DecodeError::MissingPrefix(self.to_string())));
+        }
+
+        Ok(())
     }
 
     /// Retrieves the Prefix Bytes
@@ -127,10 +205,22 @@ impl Pxid {
     /// Retrieves the Unix Timestamp used to build this Pxid
     #[inline]
     pub fn timestamp(&self) -> SystemTime {
-        let secs = u64::from(u32::from_be_bytes([
-            self.0[4], self.0[5], self.0[6], self.0[7],
-        ]));
-        UNIX_EPOCH + Duration::from_secs(secs)
+        // Every 4 bytes Timestamp fits `SystemTime` when counted from the
+        // Unix epoch
+        UNIX_EPOCH + Duration::from_secs(u64::from(self.timestamp_secs()))
+    }
+
+    /// Retrieves the time used to build this Pxid, for IDs generated by a
+    /// `Factory` using the provided `Epoch`, failing as `Epoch::time` does
+    #[inline]
+    pub fn timestamp_with_epoch(&self, epoch: Epoch) -> Result<SystemTime> {
+        epoch.time(self.timestamp_secs())
+    }
+
+    /// Retrieves the seconds since the epoch held by the Timestamp Bytes
+    #[inline]
+    fn timestamp_secs(&self) -> u32 {
+        u32::from_be_bytes([self.0[4], self.0[5], self.0[6], self.0[7]])
     }
 
     /// Retrieves the Machine Pxid used to build this Pxid
@@ -160,7 +250,56 @@ impl Pxid {
     /// Retrieves Counter value used to build the Pxid
     #[inline]
     pub fn counter(&self) -> u32 {
-        u32::from_be_bytes([0, self.0[9], self.0[10], self.0[11]])
+        u32::from_be_bytes([0, self.0[13], self.0[14], self.0[15]])
+    }
+
+    /// Retrieves a sort key packing the 4 Timestamp bytes followed by the 3
+    /// Counter bytes and a spare zero byte into a big endian `u64`, useful
+    /// as a cheap chronological key for in-memory indexes.
+    ///
+    /// Prefix, Machine ID and Process ID are ignored, so keys only sort IDs
+    /// chronologically and uniquely when they come from the same `Factory`,
+    /// and IDs from different machines or processes can share a key.
+    #[inline]
+    pub fn time_counter_key(&self) -> u64 {
+        u64::from_be_bytes([
+            self.0[4], self.0[5], self.0[6], self.0[7], self.0[13], self.0[14], self.0[15], 0,
+        ])
+    }
+
+    /// Compares this Pxid with `other` by Timestamp and then by Counter,
+    /// ignoring Prefix, Machine ID and Process ID, so IDs from different
+    /// hosts order purely by the time they were generated.
+    ///
+    /// Unlike `Ord`, which compares every byte starting with the Prefix,
+    /// IDs with different Prefix, Machine ID or Process ID can compare as
+    /// `Ordering::Equal`, so this is not consistent with `Eq`.
+    #[inline]
+    pub fn chrono_cmp(&self, other: &Pxid) -> CmpOrdering {
+        self.time_counter_key().cmp(&other.time_counter_key())
+    }
+
+    /// Checks whether this Pxid is the one generated right after `prev` by
+    /// the same `Factory`, this is, both share prefix, Timestamp, Machine ID
+    /// and Process ID, and this Counter is `prev` Counter plus one.
+    ///
+    /// The Counter wraps from `COUNTER_MAX` to zero, so an ID with a zero
+    /// Counter is the successor of one holding `COUNTER_MAX`.
+    pub fn is_successor_of(&self, prev: &Pxid) -> bool {
+        let same_origin = self.0.get(..13) == prev.0.get(..13);
+        let next_counter = match prev.counter() {
+            COUNTER_MAX => 0,
+            counter => counter + 1,
+        };
+
+        same_origin && self.counter() == next_counter
+    }
+
+    /// Checks whether this Pxid was generated by `Factory::backfill` on a
+    /// `Factory` configured as `factory`, this is, whether it holds the
+    /// Machine ID retrieved by `Factory::backfill_marker`
+    pub fn is_backfilled(&self, factory: &Factory) -> bool {
+        self.machine_id() == factory.backfill_marker()
     }
 
     /// Generates a Pxid instance using the current timestamp.
@@ -173,10 +312,7 @@ impl Pxid {
     ///
     /// [1]: https://github.com/rs/xid/blob/e6fb919be3fc74f2b846a6d174e57e076a38b1c1/id.go#L142
     pub fn new(prefix: &str) -> Result<Self> {
-        let time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Failed to retrive time")
-            .as_secs() as u32;
+        let time = Epoch::Unix.timestamp(SystemTime::now())?;
 
         Self::new_with_time(prefix, time)
     }
@@ -187,15 +323,101 @@ impl Pxid {
     ///
     /// If an error ocurrs creating a Pxid instance either by retrieving a
     /// Timestamp (Clock might be in an invalid state), generating the
-    /// Machine Pxid, gathering the Process Pxid (PID) or generating a random value.
+    /// Machine Pxid, or validating the prefix. The panic message includes the
+    /// underlying `Error`.
     ///
+    #[allow(clippy::panic)]
+    pub fn new_or_panic(prefix: &str) -> Self {
+        Self::new(prefix)
+            .unwrap_or_else(|err| panic!("Failed to create Pxid with prefix {prefix:?}. {err}"))
+    }
+
+    /// Creates a new `Pxid` instance using the current timestamp, panicking
+    /// on failure.
+    #[deprecated(since = "1.1.0", note = "use `Pxid::new_or_panic` instead")]
     pub fn new_unchecked(prefix: &str) -> Self {
-        let time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Failed to retrive time")
-            .as_secs() as u32;
+        Self::new_or_panic(prefix)
+    }
+
+    /// Builds a Pxid out of its parts without any validation, for trusted
+    /// hot paths.
+    ///
+    /// # Invariants
+    ///
+    /// Callers must provide a valid UTF-8 prefix, padded with trailing NUL
+    /// bytes when shorter than 4 bytes. Only the 3 least significant bytes
+    /// of `counter` are kept.
+    #[inline]
+    pub const fn from_parts_unchecked(
+        prefix_bytes: [u8; PREFIX_LENGTH],
+        time: u32,
+        machine_id: MachineIdBytes,
+        process_id: u16,
+        counter: u32,
+    ) -> Pxid {
+        let time = time.to_be_bytes();
+        let process_id = process_id.to_be_bytes();
+        let counter = counter.to_be_bytes();
+
+        Pxid([
+            prefix_bytes[0],
+            prefix_bytes[1],
+            prefix_bytes[2],
+            prefix_bytes[3],
+            time[0],
+            time[1],
+            time[2],
+            time[3],
+            machine_id[0],
+            machine_id[1],
+            machine_id[2],
+            process_id[0],
+            process_id[1],
+            counter[1],
+            counter[2],
+            counter[3],
+        ])
+    }
+
+    /// Generates `count` Pxid instances sharing the current timestamp, with
+    /// monotonically increasing counters.
+    ///
+    /// The timestamp is read once and the whole range of counters is reserved
+    /// at once on the Counter shared with `Pxid::new`, so IDs are strictly
+    /// increasing within the batch and never repeat the Counters of other
+    /// IDs generated by `Pxid::new` or a `Factory`. Batches can't exceed the 3 bytes
+    /// Counter space, `COUNTER_MAX + 1` IDs.
+    ///
+    /// If the Counters wrap past `COUNTER_MAX`, the IDs holding the wrapped
+    /// Counters are moved to the start so the order is kept.
+    pub fn new_batch(prefix: &str, count: usize) -> Result<Vec<Self>> {
+        if count > COUNTER_MAX as usize + 1 {
+            return Err(Error::BatchTooLarge(count));
+        }
 
-        Self::new_with_time(prefix, time).unwrap()
+        let time = Epoch::Unix.timestamp(SystemTime::now())?;
+        let machine_id = Self::read_machine_id()?;
+        let process_id = Self::read_process_id();
+        let count = u32::try_from(count).map_err(|_| Error::BatchTooLarge(count))?;
+        let first = counter::next_counters(count);
+        let mut ids = (0..count)
+            .map(|offset| {
+                Self::from_parts(
+                    prefix,
+                    time,
+                    machine_id,
+                    process_id,
+                    first.wrapping_add(offset),
+                )
+            })
+            .collect::<Result<Vec<Self>>>()?;
+        let before_wrap = (COUNTER_MAX - (first & COUNTER_MAX)) as usize + 1;
+
+        if before_wrap < ids.len() {
+            ids.rotate_left(before_wrap);
+        }
+
+        Ok(ids)
     }
 
     /// Generates a Pxid instance using the passed in time seconds as an instance
@@ -215,9 +437,26 @@ impl Pxid {
         Ok(id)
     }
 
+    /// Retrieves the lowest Pxid with the provided `prefix`, with every byte
+    /// of the XID region set to `0x00`. Useful as the lower bound of range
+    /// scans over IDs sharing a prefix.
+    pub fn min_for(prefix: &str) -> Result<Self> {
+        Self::from_parts(prefix, 0, [0; 3], 0, 0)
+    }
+
+    /// Retrieves the highest Pxid with the provided `prefix`, with every
+    /// byte of the XID region set to `0xFF`. Useful as the upper bound of
+    /// range scans over IDs sharing a prefix.
+    ///
+    /// Only the XID region is filled, short prefixes keep their NUL padding
+    /// so the prefix decodes as provided.
+    pub fn max_for(prefix: &str) -> Result<Self> {
+        Self::from_parts(prefix, u32::MAX, [0xff; 3], u16::MAX, COUNTER_MAX)
+    }
+
     /// Retrieve the bytes corresponding to a traditional XID instance
     ///
-    /// ```ignore
+    /// ```text
     /// V V V V W W W W X X X Y Y Z Z Z
     /// └─────┘ └─────────────────────┘
     ///    |              |
@@ -227,6 +466,7 @@ impl Pxid {
     ///                  XID
     /// ```
     ///
+    #[inline]
     pub fn xid_bytes(&self) -> [u8; 12] {
         let b = self.0;
 
@@ -235,6 +475,238 @@ impl Pxid {
         ]
     }
 
+    /// Retrieves a shortened form of this Pxid for display purposes, keeping
+    /// the first and last 4 characters of the encoded XID.
+    ///
+    /// ```text
+    /// acct_9m4e…5n4g
+    /// ```
+    ///
+    /// The shortened form cannot be decoded back into a Pxid.
+    #[inline]
+    pub fn short(&self) -> String {
+        self.short_with(4, 4)
+    }
+
+    /// Retrieves a shortened form of this Pxid keeping the first `head` and
+    /// last `tail` characters of the encoded XID. The encoded XID is kept
+    /// whole if `head + tail` covers it.
+    pub fn short_with(&self, head: usize, tail: usize) -> String {
+        let prefix = self.display_prefix_lossy();
+        let prefix = prefix.trim_end_matches('\0');
+        let xid = self.encoded_xid_lossy();
+
+        if head.saturating_add(tail) >= XID_ENCODED_LENGTH {
+            return format!("{prefix}{SEPARATOR}{xid}");
+        }
+
+        format!(
+            "{}{}{}…{}",
+            prefix,
+            SEPARATOR,
+            xid.chars().take(head).collect::<String>(),
+            xid.chars()
+                .skip(XID_ENCODED_LENGTH - tail)
+                .collect::<String>()
+        )
+    }
+
+    /// Retrieves a key to sort IDs by their XID bytes (Timestamp, Machine ID,
+    /// Process ID and Counter) ignoring the Prefix
+    #[inline]
+    pub fn sort_key(&self) -> [u8; XID_BINARY_LENGTH] {
+        self.xid_bytes()
+    }
+
+    /// Retrieves the `sort_key` as the lower 96 bits of an `u128`
+    #[inline]
+    pub fn sort_key_u96(&self) -> u128 {
+        let mut bytes = [0_u8; 16];
+
+        bytes[4..].copy_from_slice(&self.sort_key());
+        u128::from_be_bytes(bytes)
+    }
+
+    /// Retrieves a stable shard number in `0..num_shards` for this Pxid, or
+    /// `0` if `num_shards` is `0`.
+    ///
+    /// The shard is the 64-bit [FNV-1a][1] hash of the XID bytes modulo
+    /// `num_shards`. The Prefix is not hashed, so IDs sharing the same XID
+    /// bytes land on the same shard regardless of their Prefix. The hash is
+    /// part of the public contract and won't change across versions, so
+    /// shards remain stable for stored IDs.
+    ///
+    /// [1]: http://www.isthe.com/chongo/tech/comp/fnv/index.html
+    // The remainder is lower than `num_shards` so it fits in a `u32`
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn shard(&self, num_shards: u32) -> u32 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+        let hash = self
+            .xid_bytes()
+            .iter()
+            .fold(FNV_OFFSET_BASIS, |hash, byte| {
+                (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+            });
+
+        hash.checked_rem(u64::from(num_shards)).unwrap_or(0) as u32
+    }
+
+    /// Retrieves this Pxid as a big endian 128-bit integer, for databases
+    /// using integer keys.
+    ///
+    /// The Prefix occupies the 4 most significant bytes, so numeric order
+    /// sorts by Prefix first and then by Timestamp. Prefixes starting with
+    /// non-ASCII characters set the sign bit and sort before ASCII ones.
+    #[inline]
+    pub fn to_i128(&self) -> i128 {
+        i128::from_be_bytes(self.0)
+    }
+
+    /// Builds a Pxid out of the big endian 128-bit integer produced by
+    /// `to_i128`
+    #[inline]
+    pub fn from_i128(value: i128) -> Pxid {
+        Pxid(value.to_be_bytes())
+    }
+
+    /// Retrieves the Prefix with trailing NUL bytes trimmed and the encoded
+    /// XID as separate parts
+    ///
+    /// ```text
+    /// acct_9m4e2mr0ui3e8a215n4g -> ("acct", "9m4e2mr0ui3e8a215n4g")
+    /// ```
+    pub fn split(&self) -> Result<(String, String)> {
+        let prefix = self.prefix_str()?.to_string();
+        let xid = Self::encode_xid(&self.xid_bytes())?;
+
+        Ok((prefix, xid))
+    }
+
+    /// Retrieves the byte index of the first occurrence of this Pxid encoded
+    /// form in `text`, stable alternative to `std::str::pattern::Pattern`.
+    ///
+    /// ```no_run
+    /// # use std::str::FromStr;
+    /// # use pxid::Pxid;
+    /// # let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+    /// let line = "2024-01-01 INFO created acct_9m4e2mr0ui3e8a215n4g";
+    ///
+    /// assert_eq!(id.find_in(line), Some(24));
+    /// ```
+    #[inline]
+    pub fn find_in(&self, text: &str) -> Option<usize> {
+        text.find(&self.to_string())
+    }
+
+    /// Retrieves the byte index of every non-overlapping occurrence of this
+    /// Pxid encoded form in `text`
+    pub fn find_all_in<'a>(&self, text: &'a str) -> impl Iterator<Item = usize> + 'a {
+        let needle = self.to_string();
+        let mut offset = 0;
+
+        std::iter::from_fn(move || {
+            let idx = offset + text[offset..].find(&needle)?;
+
+            offset = idx + needle.len();
+            Some(idx)
+        })
+    }
+
+    /// Builds a Pxid out of bytes written by versions previous to the fix
+    /// on Machine ID, Process ID and Counter offsets, re-packing them into
+    /// the current layout.
+    ///
+    /// ```text
+    /// Legacy:  V V V V W W W W 0 X X X Y Y Z Z
+    /// Current: V V V V W W W W X X X Y Y Z Z Z
+    /// ```
+    ///
+    /// | Part       | Legacy Offset | Current Offset |
+    /// | ---------- | ------------- | -------------- |
+    /// | Prefix     | `0..=3`       | `0..=3`        |
+    /// | Timestamp  | `4..=7`       | `4..=7`        |
+    /// | Machine ID | `9..=11`      | `8..=10`       |
+    /// | Process ID | `12..=13`     | `11..=12`      |
+    /// | Counter    | `14..=15`     | `13..=15`      |
+    ///
+    /// Legacy versions only wrote the 2 lower bytes of the counter, so the
+    /// counter's upper byte is set to `0`.
+    pub fn from_legacy_bytes(bytes: Bytes) -> Pxid {
+        let mut id: Bytes = [0; BINARY_LENGTH];
+
+        id[0..=7].copy_from_slice(&bytes[0..=7]);
+        id[8..=10].copy_from_slice(&bytes[9..=11]);
+        id[11..=12].copy_from_slice(&bytes[12..=13]);
+        id[14..=15].copy_from_slice(&bytes[14..=15]);
+
+        Self(id)
+    }
+
+    /// Decodes a Pxid from a string during the migration from the legacy
+    /// byte layout, interpreting the decoded bytes with the legacy offsets
+    /// described on `from_legacy_bytes` when `legacy` is set.
+    ///
+    /// Legacy strings use the same encoding as current ones, so strings
+    /// in either layout are valid and the layout cannot be detected from
+    /// the string, callers must tell which layout the sender uses.
+    pub fn from_str_migrating(s: &str, legacy: bool) -> Result<Pxid> {
+        let id = Self::from_str(s)?;
+
+        if legacy && !id.is_nil() {
+            return Ok(Self::from_legacy_bytes(id.0));
+        }
+
+        Ok(id)
+    }
+
+    /// Decodes a Pxid from its packed binary form, the 4 bytes Prefix
+    /// left-aligned and padded with trailing NUL bytes followed by the 12 XID
+    /// bytes, as sent by binary protocols.
+    ///
+    /// Unlike `From<Bytes>`, the Prefix region is validated as done by
+    /// `validate`, except for the nil Pxid which is accepted as is.
+    pub fn from_packed(bytes: &Bytes) -> Result<Pxid> {
+        let id = Self(*bytes);
+
+        if !id.is_nil() {
+            id.validate_prefix()?;
+        }
+
+        id.debug_assert_canonical();
+        Ok(id)
+    }
+
+    /// Retrieves the bytes of this Pxid for a Protocol Buffers `bytes`
+    /// field, the same as the packed binary form
+    #[inline]
+    pub fn to_protobuf_bytes(&self) -> [u8; BINARY_LENGTH] {
+        self.0
+    }
+
+    /// Decodes a Pxid from a Protocol Buffers `bytes` field, which must hold
+    /// the 16 bytes packed binary form validated as done by `from_packed`
+    pub fn from_protobuf_bytes(b: &[u8]) -> Result<Pxid> {
+        let bytes: Bytes = b
+            .try_into()
+            .map_err(|_| Error::InvalidBinaryLength(b.len()))?;
+
+        Self::from_packed(&bytes)
+    }
+
+    /// Checks on debug builds that this Pxid holds its Prefix in the
+    /// canonical form defined by `is_canonical_prefix`, constructors must
+    /// call it before returning so equal IDs are equal byte for byte.
+    #[inline]
+    fn debug_assert_canonical(&self) {
+        debug_assert!(
+            self.is_nil() || is_canonical_prefix(self.prefix_bytes()),
+            "Pxid prefix {:02x?} is not canonical",
+            self.prefix_bytes()
+        );
+    }
+
     #[inline]
     pub(crate) fn from_parts(
         prefix: &str,
@@ -244,261 +716,464 @@ impl Pxid {
         counter: u32,
     ) -> Result<Pxid> {
         if prefix.len() > 4 {
-            return Err(Error::PrefixExceedsMaxLength(prefix.to_string()));
+            return Err(Error::Decode(DecodeError::InvalidPrefixLength(
+                prefix.to_string(),
+                prefix.len(),
+            )));
         }
 
-        if prefix.is_empty() {
+        if prefix.trim_end_matches('\0').is_empty() {
             return Err(Error::Decode(DecodeError::MissingPrefix(
                 prefix.to_string(),
             )));
         }
 
-        let prefix_len = prefix.len();
-        let mut bytes: Bytes = [0; BINARY_LENGTH];
-        let mut prefix_bytes: Vec<u8> = vec![0; prefix_len];
+        // Trailing NUL bytes are the canonical padding, any other NUL byte
+        // would make the prefix region ambiguous
+        if let Some(pos) = prefix.trim_end_matches('\0').find('\0') {
+            return Err(Error::Decode(DecodeError::InvalidChar(
+                prefix.to_string(),
+                '\0',
+                pos,
+            )));
+        }
 
-        prefix_bytes.copy_from_slice(prefix.as_bytes());
+        let mut bytes: Bytes = [0; BINARY_LENGTH];
 
         // Copies binary representation of UTF-8 characters as part of the
-        // inner slice containing the prefix
-        bytes[0..=prefix_len - 1].copy_from_slice(&prefix_bytes[0..=prefix_len - 1]);
+        // inner slice containing the prefix, padded with trailing NUL bytes
+        bytes[..PREFIX_LENGTH].copy_from_slice(&pad_prefix(prefix.as_bytes()));
 
         // Copies UNIX Timestamp first 4 bytes to Pxid's first 4 bytes using
         // Big Endian order
         bytes[4..=7].copy_from_slice(&time.to_be_bytes());
 
         // Copies first 3 bytes from Machine Pxid
-        bytes[9..=11].copy_from_slice(&machine_id);
+        bytes[8..=10].copy_from_slice(&machine_id);
 
         // Copies first 2 bytes from Process Pxid
-        bytes[12..=13].copy_from_slice(&process_id.to_be_bytes());
+        bytes[11..=12].copy_from_slice(&process_id.to_be_bytes());
 
         // 3 bytes of increment counter (big endian)
-        bytes[14..].copy_from_slice(&counter.to_be_bytes()[2..4]);
+        bytes[13..].copy_from_slice(&counter.to_be_bytes()[1..4]);
+
+        let id = Self(bytes);
 
-        Ok(Self(bytes))
+        id.debug_assert_canonical();
+        Ok(id)
+    }
+
+    /// Builds a Pxid out of a Prefix and the binary XID
+    #[inline]
+    pub(crate) fn from_xid_bytes(
+        prefix: &str,
+        xid_bytes: &[u8; XID_BINARY_LENGTH],
+    ) -> Result<Pxid> {
+        Self::from_parts(
+            prefix,
+            u32::from_be_bytes([xid_bytes[0], xid_bytes[1], xid_bytes[2], xid_bytes[3]]),
+            [xid_bytes[4], xid_bytes[5], xid_bytes[6]],
+            u16::from_be_bytes([xid_bytes[7], xid_bytes[8]]),
+            u32::from_be_bytes([0, xid_bytes[9], xid_bytes[10], xid_bytes[11]]),
+        )
     }
 
+    // Hot path of `Display` and `FromStr`, measured by `benches/display.rs`
+    #[allow(clippy::inline_always)]
+    #[inline(always)]
     pub fn encode_xid(xid_bytes: &[u8; 12]) -> Result<String> {
-        let mut bytes: [u8; XID_BINARY_LENGTH] = [0; XID_BINARY_LENGTH];
-        bytes.copy_from_slice(xid_bytes);
+        let enc_bytes = Self::encode_xid_bytes(xid_bytes);
 
+        from_utf8(&enc_bytes)
+            .map(String::from)
+            .map_err(|err| Error::Decode(DecodeError::InvalidUtf8(err)))
+    }
+
+    /// Encodes the XID bytes into the ASCII characters of its string form
+    // Hot path of `Display` and `FromStr`, measured by `benches/display.rs`
+    #[allow(clippy::inline_always)]
+    #[inline(always)]
+    fn encode_xid_bytes(bytes: &[u8; XID_BINARY_LENGTH]) -> [u8; XID_ENCODED_LENGTH] {
+        Self::encode_xid_bytes_with(bytes, encode_char)
+    }
+
+    /// Encodes the XID bytes mapping each 5 bits value to a character with
+    /// `encode_char`, which receives values from 0 to 31
+    // Hot path of `Display` and `FromStr`, measured by `benches/display.rs`
+    #[allow(clippy::inline_always)]
+    #[inline(always)]
+    pub(crate) fn encode_xid_bytes_with(
+        bytes: &[u8; XID_BINARY_LENGTH],
+        encode_char: impl Fn(u8) -> u8,
+    ) -> [u8; XID_ENCODED_LENGTH] {
         let mut enc_bytes = [0_u8; XID_ENCODED_LENGTH];
 
-        enc_bytes[19] = ENCODING_CHARS[((bytes[11] << 4) & 31) as usize];
-        enc_bytes[18] = ENCODING_CHARS[((bytes[11] >> 1) & 31) as usize];
-        enc_bytes[17] = ENCODING_CHARS[(((bytes[11] >> 6) | (bytes[10] << 2)) & 31) as usize];
-        enc_bytes[16] = ENCODING_CHARS[(bytes[10] >> 3) as usize];
-        enc_bytes[15] = ENCODING_CHARS[(bytes[9] & 31) as usize];
-        enc_bytes[14] = ENCODING_CHARS[(((bytes[9] >> 5) | (bytes[8] << 3)) & 31) as usize];
-        enc_bytes[13] = ENCODING_CHARS[((bytes[8] >> 2) & 31) as usize];
-        enc_bytes[12] = ENCODING_CHARS[(((bytes[8] >> 7) | (bytes[7] << 1)) & 31) as usize];
-        enc_bytes[11] = ENCODING_CHARS[(((bytes[7] >> 4) | (bytes[6] << 4)) & 31) as usize];
-        enc_bytes[10] = ENCODING_CHARS[((bytes[6] >> 1) & 31) as usize];
-        enc_bytes[9] = ENCODING_CHARS[(((bytes[6] >> 6) | (bytes[5] << 2)) & 31) as usize];
-        enc_bytes[8] = ENCODING_CHARS[(bytes[5] >> 3) as usize];
-        enc_bytes[7] = ENCODING_CHARS[(bytes[4] & 31) as usize];
-        enc_bytes[6] = ENCODING_CHARS[(((bytes[4] >> 5) | (bytes[3] << 3)) & 31) as usize];
-        enc_bytes[5] = ENCODING_CHARS[((bytes[3] >> 2) & 31) as usize];
-        enc_bytes[4] = ENCODING_CHARS[(((bytes[3] >> 7) | (bytes[2] << 1)) & 31) as usize];
-        enc_bytes[3] = ENCODING_CHARS[(((bytes[2] >> 4) | (bytes[1] << 4)) & 31) as usize];
-        enc_bytes[2] = ENCODING_CHARS[((bytes[1] >> 1) & 31) as usize];
-        enc_bytes[1] = ENCODING_CHARS[(((bytes[1] >> 6) | (bytes[0] << 2)) & 31) as usize];
-        enc_bytes[0] = ENCODING_CHARS[(bytes[0] >> 3) as usize];
-
-        Ok(String::from(
-            from_utf8(&enc_bytes).expect("Invalid UTF-8 value found encoding ID"),
-        ))
+        enc_bytes[19] = encode_char((bytes[11] << 4) & 31);
+        enc_bytes[18] = encode_char((bytes[11] >> 1) & 31);
+        enc_bytes[17] = encode_char(((bytes[11] >> 6) | (bytes[10] << 2)) & 31);
+        enc_bytes[16] = encode_char(bytes[10] >> 3);
+        enc_bytes[15] = encode_char(bytes[9] & 31);
+        enc_bytes[14] = encode_char(((bytes[9] >> 5) | (bytes[8] << 3)) & 31);
+        enc_bytes[13] = encode_char((bytes[8] >> 2) & 31);
+        enc_bytes[12] = encode_char(((bytes[8] >> 7) | (bytes[7] << 1)) & 31);
+        enc_bytes[11] = encode_char(((bytes[7] >> 4) | (bytes[6] << 4)) & 31);
+        enc_bytes[10] = encode_char((bytes[6] >> 1) & 31);
+        enc_bytes[9] = encode_char(((bytes[6] >> 6) | (bytes[5] << 2)) & 31);
+        enc_bytes[8] = encode_char(bytes[5] >> 3);
+        enc_bytes[7] = encode_char(bytes[4] & 31);
+        enc_bytes[6] = encode_char(((bytes[4] >> 5) | (bytes[3] << 3)) & 31);
+        enc_bytes[5] = encode_char((bytes[3] >> 2) & 31);
+        enc_bytes[4] = encode_char(((bytes[3] >> 7) | (bytes[2] << 1)) & 31);
+        enc_bytes[3] = encode_char(((bytes[2] >> 4) | (bytes[1] << 4)) & 31);
+        enc_bytes[2] = encode_char((bytes[1] >> 1) & 31);
+        enc_bytes[1] = encode_char(((bytes[1] >> 6) | (bytes[0] << 2)) & 31);
+        enc_bytes[0] = encode_char(bytes[0] >> 3);
+
+        enc_bytes
     }
 
+    // Hot path of `Display` and `FromStr`, measured by `benches/display.rs`
+    #[allow(clippy::inline_always)]
+    #[inline(always)]
     pub fn decode_xid(s: &str) -> Result<[u8; XID_BINARY_LENGTH]> {
-        if s.len() != XID_ENCODED_LENGTH {
-            return Err(Error::Decode(DecodeError::InvalidXidLength(
-                s.to_string(),
-                s.len(),
-            )));
-        }
-
-        if let Some(c) = s.chars().find(|&c| !matches!(c, '0'..='9' | 'a'..='v')) {
-            return Err(Error::Decode(DecodeError::InvalidChar(s.to_string(), c)));
-        }
+        Alphabet::STANDARD.decode_xid(s).map_err(Error::Decode)
+    }
 
-        let str_bytes = s.as_bytes();
+    /// Packs the 5 bits values of each encoded XID character back into the
+    /// XID bytes
+    // Hot path of `FromStr`, measured by `benches/display.rs`
+    #[allow(clippy::inline_always)]
+    #[inline(always)]
+    pub(crate) fn decode_xid_quintets(
+        dec_bytes: &[u8; XID_ENCODED_LENGTH],
+    ) -> [u8; XID_BINARY_LENGTH] {
         let mut bytes: [u8; XID_BINARY_LENGTH] = [0; XID_BINARY_LENGTH];
 
-        bytes[11] = DECODING_BYTES[str_bytes[17] as usize] << 6
-            | DECODING_BYTES[str_bytes[18] as usize] << 1
-            | DECODING_BYTES[str_bytes[19] as usize] >> 4;
-        bytes[10] = DECODING_BYTES[str_bytes[16] as usize] << 3
-            | DECODING_BYTES[str_bytes[17] as usize] >> 2;
-        bytes[9] =
-            DECODING_BYTES[str_bytes[14] as usize] << 5 | DECODING_BYTES[str_bytes[15] as usize];
-        bytes[8] = DECODING_BYTES[str_bytes[12] as usize] << 7
-            | DECODING_BYTES[str_bytes[13] as usize] << 2
-            | DECODING_BYTES[str_bytes[14] as usize] >> 3;
-        bytes[7] = DECODING_BYTES[str_bytes[11] as usize] << 4
-            | DECODING_BYTES[str_bytes[12] as usize] >> 1;
-        bytes[6] = DECODING_BYTES[str_bytes[9] as usize] << 6
-            | DECODING_BYTES[str_bytes[10] as usize] << 1
-            | DECODING_BYTES[str_bytes[11] as usize] >> 4;
-        bytes[5] =
-            DECODING_BYTES[str_bytes[8] as usize] << 3 | DECODING_BYTES[str_bytes[9] as usize] >> 2;
-        bytes[4] =
-            DECODING_BYTES[str_bytes[6] as usize] << 5 | DECODING_BYTES[str_bytes[7] as usize];
-        bytes[3] = DECODING_BYTES[str_bytes[4] as usize] << 7
-            | DECODING_BYTES[str_bytes[5] as usize] << 2
-            | DECODING_BYTES[str_bytes[6] as usize] >> 3;
-        bytes[2] =
-            DECODING_BYTES[str_bytes[3] as usize] << 4 | DECODING_BYTES[str_bytes[4] as usize] >> 1;
-        bytes[1] = DECODING_BYTES[str_bytes[1] as usize] << 6
-            | DECODING_BYTES[str_bytes[2] as usize] << 1
-            | DECODING_BYTES[str_bytes[3] as usize] >> 4;
-        bytes[0] =
-            DECODING_BYTES[str_bytes[0] as usize] << 3 | DECODING_BYTES[str_bytes[1] as usize] >> 2;
-
-        Ok(bytes)
+        bytes[11] = dec_bytes[17] << 6 | dec_bytes[18] << 1 | dec_bytes[19] >> 4;
+        bytes[10] = dec_bytes[16] << 3 | dec_bytes[17] >> 2;
+        bytes[9] = dec_bytes[14] << 5 | dec_bytes[15];
+        bytes[8] = dec_bytes[12] << 7 | dec_bytes[13] << 2 | dec_bytes[14] >> 3;
+        bytes[7] = dec_bytes[11] << 4 | dec_bytes[12] >> 1;
+        bytes[6] = dec_bytes[9] << 6 | dec_bytes[10] << 1 | dec_bytes[11] >> 4;
+        bytes[5] = dec_bytes[8] << 3 | dec_bytes[9] >> 2;
+        bytes[4] = dec_bytes[6] << 5 | dec_bytes[7];
+        bytes[3] = dec_bytes[4] << 7 | dec_bytes[5] << 2 | dec_bytes[6] >> 3;
+        bytes[2] = dec_bytes[3] << 4 | dec_bytes[4] >> 1;
+        bytes[1] = dec_bytes[1] << 6 | dec_bytes[2] << 1 | dec_bytes[3] >> 4;
+        bytes[0] = dec_bytes[0] << 3 | dec_bytes[1] >> 2;
+
+        bytes
     }
 
-    /// Retrieves the Platform's Machine Pxid
+    /// Encodes this Pxid using `sep` instead of `SEPARATOR` between the
+    /// prefix and the encoded XID
     ///
-    /// # Reference
-    ///
-    /// Follows the authors algorithm writen on Golang in the [following source][1].
+    /// ```text
+    /// acct.9m4e2mr0ui3e8a215n4g
+    /// ```
     ///
-    /// [1]: https://github.com/rs/xid/blob/e6fb919be3fc74f2b846a6d174e57e076a38b1c1/id.go#L113
-    #[inline]
-    fn read_machine_id() -> Result<MachineIdBytes> {
-        machine_id()
+    /// Invalid UTF-8 sequences on the prefix are replaced as done by
+    /// `Display`.
+    pub fn to_string_with_separator(&self, sep: char) -> String {
+        format!(
+            "{}{}{}",
+            self.display_prefix_lossy(),
+            sep,
+            self.encoded_xid_lossy()
+        )
     }
 
-    /// Retrieves `process::id` as `u16` value
-    #[inline]
-    fn read_process_id() -> u16 {
-        process::id() as u16
+    /// Encodes this Pxid, failing with `Error::InvalidBinaryPrefix` if the
+    /// prefix is not valid UTF-8 instead of replacing invalid sequences as
+    /// done by `Display`.
+    pub fn try_to_string(&self) -> Result<String> {
+        let mut encoded = String::with_capacity(ENCODED_LENGTH);
+
+        encoded.push_str(self.display_prefix()?);
+        encoded.push(SEPARATOR);
+        encoded.push_str(&self.encoded_xid_lossy());
+
+        Ok(encoded)
     }
 
-    /// Retrieves the next value from the Atomic Counter
-    ///
-    /// # Reference
-    ///
-    /// Follows the authors algorithm writen on Golang in the [following source][1].
-    ///
-    /// [1]: https://github.com/rs/xid/blob/e6fb919be3fc74f2b846a6d174e57e076a38b1c1/id.go#L159
-    fn read_counter() -> u32 {
-        let mut rand_bytes: [u8; 3] = [0; 3];
-        rand::thread_rng().fill_bytes(&mut rand_bytes);
-        let seed = u32::from_be_bytes([0, rand_bytes[0], rand_bytes[1], rand_bytes[2]]);
+    /// Writes the encoded form of this Pxid into `w` without allocating,
+    /// fails if the prefix is not valid UTF-8
+    pub fn write_encoded<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        let prefix = self.display_prefix().map_err(|_| fmt::Error)?;
 
-        AtomicU32::new(seed).fetch_add(1, Ordering::SeqCst)
+        w.write_str(prefix)?;
+        w.write_char(SEPARATOR)?;
+        self.write_encoded_xid(w)
     }
-}
 
-impl Default for Pxid {
-    fn default() -> Self {
-        Self([0_u8; BINARY_LENGTH])
+    /// Writes the encoded form of this Pxid into `w` as done by `Display`,
+    /// replacing invalid UTF-8 sequences on the prefix with
+    /// `U+FFFD REPLACEMENT CHARACTER`
+    pub(crate) fn write_encoded_lossy<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        w.write_str(&self.display_prefix_lossy())?;
+        w.write_char(SEPARATOR)?;
+        self.write_encoded_xid(w)
     }
-}
 
-impl Deref for Pxid {
-    type Target = Bytes;
+    /// Writes the encoded XID into `w` with a single `write_str` call, the
+    /// encoded bytes are ASCII so they are always valid UTF-8
+    #[inline]
+    fn write_encoded_xid<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        let xid = Self::encode_xid_bytes(&self.xid_bytes());
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+        w.write_str(from_utf8(&xid).map_err(|_| fmt::Error)?)
     }
-}
 
-impl Debug for Pxid {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let value = self.to_string();
+    /// Retrieves the encoded XID, which only holds ASCII characters
+    fn encoded_xid_lossy(&self) -> String {
+        Self::encode_xid_bytes(&self.xid_bytes())
+            .iter()
+            .map(|c| char::from(*c))
+            .collect()
+    }
 
-        f.debug_tuple("Pxid").field(&value).finish()
+    /// Retrieves a `Display` adaptor which always writes the whole encoded
+    /// form, ignoring width, fill, alignment and precision. Useful on
+    /// templating engines applying format flags.
+    #[inline]
+    pub fn display_full(&self) -> impl Display + '_ {
+        DisplayFull(self)
     }
-}
 
-impl Display for Pxid {
-    /// Encodes the XID instance using a subset of Base32 characters where only
-    /// lowercase characters are included
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let bytes = self.xid_bytes();
-        let mut enc_bytes = [0_u8; XID_ENCODED_LENGTH];
+    /// Appends the encoded form of this Pxid to `s`, reserving exactly the
+    /// `ENCODED_LENGTH` bytes needed
+    pub fn append_to(&self, s: &mut String) {
+        s.reserve_exact(ENCODED_LENGTH);
 
-        enc_bytes[19] = ENCODING_CHARS[((bytes[11] << 4) & 31) as usize];
-        enc_bytes[18] = ENCODING_CHARS[((bytes[11] >> 1) & 31) as usize];
-        enc_bytes[17] = ENCODING_CHARS[(((bytes[11] >> 6) | (bytes[10] << 2)) & 31) as usize];
-        enc_bytes[16] = ENCODING_CHARS[(bytes[10] >> 3) as usize];
-        enc_bytes[15] = ENCODING_CHARS[(bytes[9] & 31) as usize];
-        enc_bytes[14] = ENCODING_CHARS[(((bytes[9] >> 5) | (bytes[8] << 3)) & 31) as usize];
-        enc_bytes[13] = ENCODING_CHARS[((bytes[8] >> 2) & 31) as usize];
-        enc_bytes[12] = ENCODING_CHARS[(((bytes[8] >> 7) | (bytes[7] << 1)) & 31) as usize];
-        enc_bytes[11] = ENCODING_CHARS[(((bytes[7] >> 4) | (bytes[6] << 4)) & 31) as usize];
-        enc_bytes[10] = ENCODING_CHARS[((bytes[6] >> 1) & 31) as usize];
-        enc_bytes[9] = ENCODING_CHARS[(((bytes[6] >> 6) | (bytes[5] << 2)) & 31) as usize];
-        enc_bytes[8] = ENCODING_CHARS[(bytes[5] >> 3) as usize];
-        enc_bytes[7] = ENCODING_CHARS[(bytes[4] & 31) as usize];
-        enc_bytes[6] = ENCODING_CHARS[(((bytes[4] >> 5) | (bytes[3] << 3)) & 31) as usize];
-        enc_bytes[5] = ENCODING_CHARS[((bytes[3] >> 2) & 31) as usize];
-        enc_bytes[4] = ENCODING_CHARS[(((bytes[3] >> 7) | (bytes[2] << 1)) & 31) as usize];
-        enc_bytes[3] = ENCODING_CHARS[(((bytes[2] >> 4) | (bytes[1] << 4)) & 31) as usize];
-        enc_bytes[2] = ENCODING_CHARS[((bytes[1] >> 1) & 31) as usize];
-        enc_bytes[1] = ENCODING_CHARS[(((bytes[1] >> 6) | (bytes[0] << 2)) & 31) as usize];
-        enc_bytes[0] = ENCODING_CHARS[(bytes[0] >> 3) as usize];
-
-        write!(
-            f,
-            "{}_{}",
-            self.prefix().unwrap(),
-            from_utf8(&enc_bytes).expect("Invalid UTF-8 value found encoding Pxid")
-        )
+        // Writing into a `String` never fails
+        let _ = self.write_encoded_lossy(s);
     }
-}
 
-impl FromStr for Pxid {
-    type Err = crate::Error;
+    /// Retrieves the encoded form of this Pxid as bytes, as written by
+    /// `Display`, without wrapping them into a `String`
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(ENCODED_LENGTH);
+        let mut separator = [0_u8; 4];
 
-    fn from_str(s: &str) -> Result<Self> {
-        let encoded_length = s.to_string().len();
+        bytes.extend_from_slice(self.display_prefix_lossy().as_bytes());
+        bytes.extend_from_slice(SEPARATOR.encode_utf8(&mut separator).as_bytes());
+        bytes.extend_from_slice(&Self::encode_xid_bytes(&self.xid_bytes()));
+        bytes
+    }
 
-        if encoded_length > ENCODED_LENGTH {
-            return Err(Error::Decode(DecodeError::InvalidLength(
-                s.to_string(),
-                s.len(),
-            )));
+    /// Writes the encoded form of this Pxid into `out` with plain byte
+    /// stores, without going through `core::fmt`, and retrieves the amount
+    /// of bytes written.
+    ///
+    /// Output is byte-identical to `Display`, including the NUL padding of
+    /// short prefixes and the `NIL_PREFIX` of the nil Pxid, so up to
+    /// `ENCODED_LENGTH` bytes are written. Prefixes which are not valid UTF-8
+    /// don't fit the lossy `Display` form, nothing is written for them and
+    /// `0` is returned.
+    #[inline]
+    pub fn encode_into(&self, out: &mut [u8; ENCODED_LENGTH]) -> usize {
+        let Ok(prefix) = self.display_prefix() else {
+            return 0;
+        };
+
+        let separator = [SEPARATOR as u8];
+        let xid = Self::encode_xid_bytes(&self.xid_bytes());
+        let mut len = 0;
+
+        for (dst, src) in out
+            .iter_mut()
+            .zip(prefix.as_bytes().iter().chain(&separator).chain(&xid))
+        {
+            *dst = *src;
+            len += 1;
         }
 
-        if encoded_length < (ENCODED_LENGTH - (PREFIX_LENGTH + 1)) {
-            return Err(Error::Decode(DecodeError::InvalidLength(
-                s.to_string(),
-                s.len(),
-            )));
-        }
+        len
+    }
 
-        let parts = s.split('_').collect::<Vec<&str>>();
+    /// Decodes a Pxid from the encoded bytes written by `encode_into`,
+    /// as done by `FromStr`. Slices of any length are accepted and fail to
+    /// decode unless they hold a whole encoded Pxid.
+    #[inline]
+    pub fn decode_from(input: &[u8]) -> Result<Self> {
+        let s = from_utf8(input).map_err(|err| Error::Decode(DecodeError::InvalidUtf8(err)))?;
 
-        if parts.len() != ENCODED_PARTS_LENGTH {
-            return Err(Error::Decode(DecodeError::MissingPrefix(s.to_string())));
-        }
-        let prefix = parts.first().unwrap().to_string();
-        let xid = parts.get(1).unwrap().to_string();
+        Self::from_str(s)
+    }
+
+    /// Writes the encoded form of this Pxid into the `w` byte sink with a
+    /// single `write_all` call
+    pub fn write_encoded_bytes<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        let prefix = self
+            .display_prefix()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+            .as_bytes();
+        let mut separator = [0_u8; 4];
+        let separator = SEPARATOR.encode_utf8(&mut separator).as_bytes();
+        let xid = Self::encode_xid_bytes(&self.xid_bytes());
+        let mut buf = [0_u8; ENCODED_LENGTH];
+        let mut len = 0;
+
+        for (dst, src) in buf
+            .iter_mut()
+            .zip(prefix.iter().chain(separator).chain(&xid))
+        {
+            *dst = *src;
+            len += 1;
+        }
+
+        w.write_all(buf.get(..len).unwrap_or_default())
+    }
+
+    /// Retrieves the prefix used on the string form of this Pxid, which is
+    /// `NIL_PREFIX` for the nil Pxid instead of 4 NUL characters
+    fn display_prefix(&self) -> Result<&str> {
+        if self.is_nil() {
+            return Ok(NIL_PREFIX);
+        }
+
+        from_utf8(&self.0[..PREFIX_LENGTH])
+            .map_err(|_| Error::InvalidBinaryPrefix(self.prefix_bytes()))
+    }
+
+    /// Retrieves the prefix used on the string form of this Pxid, replacing
+    /// invalid UTF-8 sequences with `U+FFFD REPLACEMENT CHARACTER`
+    pub(crate) fn display_prefix_lossy(&self) -> Cow<'_, str> {
+        match self.display_prefix() {
+            Ok(prefix) => Cow::Borrowed(prefix),
+            Err(_) => String::from_utf8_lossy(&self.0[..PREFIX_LENGTH]),
+        }
+    }
+
+    /// Decodes a Pxid from a string wrapped in a single pair of double
+    /// quotes, as found when copying IDs from JSON logs. Strings without
+    /// surrounding quotes are decoded as is.
+    ///
+    /// ```text
+    /// "acct_9m4e2mr0ui3e8a215n4g" -> acct_9m4e2mr0ui3e8a215n4g
+    /// ```
+    pub fn parse_unquoted(s: &str) -> Result<Self> {
+        let unquoted = s
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .unwrap_or(s);
+
+        Self::from_str(unquoted)
+    }
+
+    /// Decodes a Pxid from a string and checks its prefix matches the
+    /// provided `prefix`, failing with `Error::PrefixMismatch` otherwise.
+    ///
+    /// ```no_run
+    /// # use pxid::Pxid;
+    /// # fn main() -> pxid::Result<()> {
+    /// let id = Pxid::expect_prefix("acct_9m4e2mr0ui3e8a215n4g", "acct")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn expect_prefix(s: &str, prefix: &str) -> Result<Self> {
+        let id = Self::from_str(s)?;
+
+        check_prefix(&id, prefix)?;
+
+        Ok(id)
+    }
+
+    /// Decodes a Pxid from a string using `sep` instead of `SEPARATOR`
+    /// between the prefix and the encoded XID.
+    ///
+    /// ASCII characters on the prefix are normalized to lowercase, as done
+    /// by `FromStr`.
+    pub fn from_str_with_separator(s: &str, sep: char) -> Result<Self> {
+        Self::decode_normalized(s, sep, &Alphabet::STANDARD).map_err(Error::Decode)
+    }
+
+    /// Decodes a Pxid as done by `FromStr`, failing with the `DecodeError`
+    /// itself for callers reporting decoding failures only
+    pub(crate) fn decode_str(s: &str) -> std::result::Result<Self, DecodeError> {
+        Self::decode_normalized(s, SEPARATOR, &Alphabet::STANDARD)
+    }
+
+    /// Decodes a Pxid normalizing ASCII characters on the prefix to
+    /// lowercase
+    pub(crate) fn decode_normalized(
+        s: &str,
+        sep: char,
+        alphabet: &Alphabet,
+    ) -> std::result::Result<Self, DecodeError> {
+        let mut id = Self::decode_preserving_case(s, sep, alphabet)?;
+
+        id.0[..PREFIX_LENGTH].make_ascii_lowercase();
+        id.debug_assert_canonical();
+
+        Ok(id)
+    }
+
+    /// Decodes a Pxid from a string keeping the prefix as provided, unlike
+    /// `FromStr` which normalizes ASCII characters on the prefix to
+    /// lowercase.
+    ///
+    /// ```text
+    /// ACCT_9m4e2mr0ui3e8a215n4g -> ACCT_9m4e2mr0ui3e8a215n4g
+    /// ```
+    pub fn from_str_preserve_case(s: &str) -> Result<Self> {
+        Self::decode_preserving_case(s, SEPARATOR, &Alphabet::STANDARD).map_err(Error::Decode)
+    }
+
+    fn decode_preserving_case(
+        s: &str,
+        sep: char,
+        alphabet: &Alphabet,
+    ) -> std::result::Result<Self, DecodeError> {
+        if let Some(xid) = s
+            .strip_prefix(NIL_PREFIX)
+            .and_then(|rest| rest.strip_prefix(sep))
+        {
+            if alphabet.is_nil_xid(xid) {
+                return Ok(Self::nil());
+            }
+        }
+
+        let encoded_length = s.len();
+        let max_length = ENCODED_LENGTH - SEPARATOR.len_utf8() + sep.len_utf8();
+
+        if encoded_length > max_length {
+            return Err(DecodeError::InvalidLength(s.to_string(), s.len()));
+        }
+
+        if encoded_length < (max_length - (PREFIX_LENGTH + sep.len_utf8())) {
+            return Err(DecodeError::InvalidLength(s.to_string(), s.len()));
+        }
+
+        let Some((prefix, xid)) = s.split_once(sep).filter(|(_, xid)| !xid.contains(sep)) else {
+            return Err(DecodeError::MissingPrefix(s.to_string()));
+        };
+
+        if prefix.trim_end_matches('\0').is_empty() {
+            return Err(DecodeError::MissingPrefix(s.to_string()));
+        }
+
+        // Trailing NUL bytes are the canonical padding written by `Display`
+        if let Some(pos) = prefix.trim_end_matches('\0').find('\0') {
+            return Err(DecodeError::InvalidChar(s.to_string(), '\0', pos));
+        }
 
         if prefix.len() > 4 {
-            return Err(Error::Decode(DecodeError::InvalidPrefixLength(
+            return Err(DecodeError::InvalidPrefixLength(
                 prefix.to_string(),
                 prefix.len(),
-            )));
+            ));
         }
 
         if xid.len() > XID_ENCODED_LENGTH {
-            return Err(Error::Decode(DecodeError::InvalidXidLength(
-                xid.to_string(),
-                xid.len(),
-            )));
+            return Err(DecodeError::InvalidXidLength(xid.to_string(), xid.len()));
         }
 
         let mut id: [u8; 16] = [0; 16];
-        let prefix_bytes = prefix.as_bytes();
-        let xid_bytes = Self::decode_xid(&xid)?;
+        let prefix_bytes = pad_prefix(prefix.as_bytes());
+        let xid_bytes = alphabet.decode_xid(xid)?;
 
-        // Assign Prefix UTF-8 Bytes
+        // Assign Prefix UTF-8 Bytes, padded with trailing NUL bytes
         id[0] = prefix_bytes[0];
         id[1] = prefix_bytes[1];
         id[2] = prefix_bytes[2];
@@ -524,7 +1199,387 @@ impl FromStr for Pxid {
         id[14] = xid_bytes[10];
         id[15] = xid_bytes[11];
 
-        Ok(Self(id))
+        let id = Self(id);
+
+        id.debug_assert_canonical();
+        Ok(id)
+    }
+
+    /// Encodes this Pxid appending a Luhn mod 32 check character right after
+    /// the encoded XID, this allows detecting single character typos when
+    /// decoding with `from_str_with_checksum`.
+    ///
+    /// The check character covers the prefix and the separator along with the
+    /// encoded XID, as described on `checksum::generate`.
+    ///
+    /// ```text
+    /// acct_9m4e2mr0ui3e8a215n4gX
+    ///                          |
+    ///                   Check Character
+    /// ```
+    pub fn to_string_with_checksum(&self) -> String {
+        let encoded = self.to_string();
+        let check = Self::check_char(&encoded);
+
+        format!("{encoded}{}", check as char)
+    }
+
+    /// Retrieves the check character of the provided `Display` output
+    fn check_char(encoded: &str) -> u8 {
+        let (prefix, xid) = encoded
+            .as_bytes()
+            .split_at(encoded.len().saturating_sub(XID_ENCODED_LENGTH));
+
+        // Encoded XIDs only hold `ENCODING_CHARS`, so a check character is
+        // always generated
+        crate::checksum::generate(prefix, xid).unwrap_or(b'0')
+    }
+
+    /// Decodes a Pxid encoded with `to_string_with_checksum`, validating the
+    /// trailing check character against the prefix, separator and encoded
+    /// XID.
+    pub fn from_str_with_checksum(s: &str) -> Result<Self> {
+        if s.len() > CHECKSUM_ENCODED_LENGTH {
+            return Err(Error::Decode(DecodeError::InvalidLength(
+                s.to_string(),
+                s.len(),
+            )));
+        }
+
+        let Some((idx, check)) = s.char_indices().last() else {
+            return Err(Error::Decode(DecodeError::InvalidLength(
+                s.to_string(),
+                s.len(),
+            )));
+        };
+
+        let id = Self::from_str(s.get(..idx).unwrap_or_default())?;
+
+        // The check character is computed on the `Display` output of the
+        // decoded ID, so prefixes are compared lowercased as `FromStr` does
+        let expected = Self::check_char(&id.to_string());
+
+        if !check.is_ascii() || check as u8 != expected {
+            return Err(Error::Decode(DecodeError::InvalidChecksum(
+                s.to_string(),
+                expected as char,
+            )));
+        }
+
+        Ok(id)
+    }
+
+    /// Encodes this Pxid into exactly `ENCODED_LENGTH` characters for fixed
+    /// width columns, such as `CHAR(25)`, right-padding the encoded form with
+    /// `FIXED_PAD` instead of NUL padding short prefixes.
+    ///
+    /// ```text
+    /// acct_9m4e2mr0ui3e8a215n4g
+    /// dog_9m4e2mr0ui3e8a215n4g␠
+    /// a_9m4e2mr0ui3e8a215n4g␠␠␠
+    /// ```
+    pub fn to_fixed_string(&self) -> String {
+        let mut fixed = String::with_capacity(ENCODED_LENGTH);
+
+        fixed.push_str(
+            self.display_prefix()
+                .map_or("", |prefix| prefix.trim_end_matches('\0')),
+        );
+        fixed.push(SEPARATOR);
+        fixed.push_str(&self.encoded_xid_lossy());
+
+        while fixed.len() < ENCODED_LENGTH {
+            fixed.push(FIXED_PAD);
+        }
+
+        fixed
+    }
+
+    /// Decodes a Pxid encoded with `to_fixed_string`, as read back from a
+    /// fixed width column.
+    ///
+    /// The input must hold exactly `ENCODED_LENGTH` characters, `FIXED_PAD`
+    /// characters are only allowed at the tail and are stripped before
+    /// decoding as done by `FromStr`:
+    ///
+    /// - Inputs of any other length fail with `DecodeError::InvalidLength`
+    /// - Inputs only holding padding fail with `Error::PaddingOnly`
+    /// - Padding before the tail fails with `Error::InteriorPadding`
+    pub fn from_fixed_str(s: &str) -> Result<Self> {
+        let len = s.chars().count();
+
+        if len != ENCODED_LENGTH {
+            return Err(Error::Decode(DecodeError::InvalidLength(
+                s.to_string(),
+                len,
+            )));
+        }
+
+        let trimmed = s.trim_end_matches(FIXED_PAD);
+
+        if trimmed.is_empty() {
+            return Err(Error::PaddingOnly);
+        }
+
+        if let Some(pos) = trimmed.find(FIXED_PAD) {
+            return Err(Error::InteriorPadding(s.to_string(), pos));
+        }
+
+        Self::from_str(trimmed)
+    }
+
+    /// Encodes the XID of this Pxid as a ULID string, for systems which only
+    /// accept ULIDs. The Prefix is not encoded.
+    ///
+    /// The 96 bits XID is padded with 32 leading zero bits to fill the 128
+    /// bits of a ULID, so encoded values preserve the XID sort order.
+    pub fn to_ulid_string(&self) -> String {
+        let value = self.sort_key_u96();
+
+        (0..ULID_ENCODED_LENGTH)
+            .map(|idx| {
+                let shift = 5 * (ULID_ENCODED_LENGTH - 1 - idx);
+
+                ULID_ENCODING_CHARS
+                    .get(((value >> shift) & 0x1f) as usize)
+                    .map_or('0', |c| char::from(*c))
+            })
+            .collect()
+    }
+
+    /// Decodes a ULID string encoded with `to_ulid_string` into a Pxid with
+    /// the provided Prefix. Characters are matched case-insensitively.
+    pub fn from_ulid_string(prefix: &str, s: &str) -> Result<Self> {
+        if s.len() != ULID_ENCODED_LENGTH {
+            return Err(Error::Decode(DecodeError::InvalidLength(
+                s.to_string(),
+                s.len(),
+            )));
+        }
+
+        let mut value: u128 = 0;
+
+        for (pos, c) in s.char_indices() {
+            let Some(digit) = ULID_ENCODING_CHARS
+                .iter()
+                .position(|b| c.is_ascii() && *b == c.to_ascii_uppercase() as u8)
+            else {
+                return Err(Error::Decode(DecodeError::InvalidChar(
+                    s.to_string(),
+                    c,
+                    pos,
+                )));
+            };
+
+            value = (value << 5) | digit as u128;
+
+            // XIDs fit in the 96 least significant bits
+            if value >> 96 != 0 {
+                return Err(Error::Decode(DecodeError::InvalidUlid(s.to_string())));
+            }
+        }
+
+        let bytes = value.to_be_bytes();
+        let mut xid_bytes = [0_u8; XID_BINARY_LENGTH];
+
+        xid_bytes.copy_from_slice(&bytes[4..]);
+        Self::from_xid_bytes(prefix, &xid_bytes)
+    }
+
+    /// Retrieves the Platform's Machine Pxid
+    ///
+    /// # Reference
+    ///
+    /// Follows the authors algorithm writen on Golang in the [following source][1].
+    ///
+    /// [1]: https://github.com/rs/xid/blob/e6fb919be3fc74f2b846a6d174e57e076a38b1c1/id.go#L113
+    #[inline]
+    pub(crate) fn read_machine_id() -> Result<MachineIdBytes> {
+        machine_id()
+    }
+
+    /// Retrieves `process::id` as `u16` value
+    #[inline]
+    // Process IDs are truncated to their 2 least significant bytes as done by
+    // rs/xid
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn read_process_id() -> u16 {
+        process::id() as u16
+    }
+
+    /// Retrieves the next value from the Counter shared by `Pxid::new` and
+    /// every non-deterministic `Factory`, see `counter::next_counter`
+    #[inline]
+    pub(crate) fn read_counter() -> u32 {
+        counter::next_counter()
+    }
+}
+
+/// Retrieves the encoding character for the 5 least significant bits of
+/// `value`
+#[inline]
+fn encode_char(value: u8) -> u8 {
+    ENCODING_CHARS
+        .get(usize::from(value & 31))
+        .copied()
+        .unwrap_or(b'0')
+}
+
+/// Retrieves the prefix region for the provided prefix bytes, padded with
+/// trailing NUL bytes. Bytes past `PREFIX_LENGTH` are ignored.
+///
+/// Every constructor builds the prefix region with this function, so prefix
+/// regions are canonical as checked by `is_canonical_prefix`.
+pub(crate) fn pad_prefix(prefix: &[u8]) -> [u8; PREFIX_LENGTH] {
+    let mut bytes = [0_u8; PREFIX_LENGTH];
+
+    for (dst, src) in bytes.iter_mut().zip(prefix) {
+        *dst = *src;
+    }
+
+    bytes
+}
+
+/// Checks the prefix region is canonical: a non-empty UTF-8 prefix without
+/// NUL characters, left-aligned and padded with trailing NUL bytes.
+pub(crate) fn is_canonical_prefix(prefix: [u8; PREFIX_LENGTH]) -> bool {
+    let len = prefix.iter().position(|&b| b == 0).unwrap_or(PREFIX_LENGTH);
+
+    len > 0
+        && prefix.iter().skip(len).all(|&b| b == 0)
+        && from_utf8(prefix.get(..len).unwrap_or_default()).is_ok()
+}
+
+impl Default for Pxid {
+    fn default() -> Self {
+        Self::nil()
+    }
+}
+
+impl Deref for Pxid {
+    type Target = Bytes;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for Pxid {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[..]
+    }
+}
+
+impl PartialEq<Bytes> for Pxid {
+    fn eq(&self, other: &Bytes) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<Pxid> for Bytes {
+    fn eq(&self, other: &Pxid) -> bool {
+        *self == other.0
+    }
+}
+
+impl PartialEq<&[u8]> for Pxid {
+    fn eq(&self, other: &&[u8]) -> bool {
+        other.len() == BINARY_LENGTH && self.0[..] == **other
+    }
+}
+
+impl Debug for Pxid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.to_string();
+
+        f.debug_tuple("Pxid").field(&value).finish()
+    }
+}
+
+impl Display for Pxid {
+    /// Encodes the XID instance using a subset of Base32 characters where only
+    /// lowercase characters are included.
+    ///
+    /// Width, fill and alignment are honored padding the whole ID, which is
+    /// left aligned by default. Precision is ignored, the ID is never
+    /// truncated given that truncated IDs can't be decoded back.
+    ///
+    /// Invalid UTF-8 sequences on the prefix are replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`, use `Pxid::try_to_string` to fail on
+    /// them instead.
+    ///
+    /// ```no_run
+    /// # use std::str::FromStr;
+    /// # use pxid::Pxid;
+    /// # let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+    /// format!("{:>30}", id); // "     acct_9m4e2mr0ui3e8a215n4g"
+    /// format!("{:.10}", id); // "acct_9m4e2mr0ui3e8a215n4g"
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(width) = f.width() else {
+            return self.write_encoded_lossy(f);
+        };
+
+        let prefix = self.display_prefix_lossy();
+        let len = prefix.chars().count() + 1 + XID_ENCODED_LENGTH;
+        let padding = width.saturating_sub(len);
+        let (before, after) = match f.align() {
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            _ => (0, padding),
+        };
+        let fill = f.fill();
+
+        for _ in 0..before {
+            fmt::Write::write_char(f, fill)?;
+        }
+
+        self.write_encoded_lossy(f)?;
+
+        for _ in 0..after {
+            fmt::Write::write_char(f, fill)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `Display` adaptor returned by `Pxid::display_full`
+struct DisplayFull<'a>(&'a Pxid);
+
+impl Display for DisplayFull<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write_encoded_lossy(f)
+    }
+}
+
+impl FromStr for Pxid {
+    type Err = crate::Error;
+
+    /// Decodes a Pxid from its string form, normalizing ASCII characters on
+    /// the prefix to lowercase so `ACCT_9m4e2mr0ui3e8a215n4g` decodes into
+    /// the same Pxid as `acct_9m4e2mr0ui3e8a215n4g`. Use
+    /// `Pxid::from_str_preserve_case` to keep the prefix as provided.
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_str_with_separator(s, SEPARATOR)
+    }
+}
+
+impl TryFrom<&str> for Pxid {
+    type Error = crate::Error;
+
+    /// Decodes a Pxid from its string form, as done by `FromStr`
+    fn try_from(s: &str) -> Result<Self> {
+        Self::from_str(s)
+    }
+}
+
+impl TryFrom<String> for Pxid {
+    type Error = crate::Error;
+
+    /// Decodes a Pxid from its string form, as done by `FromStr`
+    fn try_from(s: String) -> Result<Self> {
+        Self::from_str(&s)
     }
 }
 
@@ -534,20 +1589,38 @@ impl From<Bytes> for Pxid {
     }
 }
 
+impl From<Pxid> for Bytes {
+    fn from(value: Pxid) -> Self {
+        value.0
+    }
+}
+
 #[cfg(feature = "async-graphql")]
 #[Scalar]
 impl ScalarType for Pxid {
+    /// Parses a `Pxid` from its string form, either as a scalar
+    /// (`"acct_..."`) or as an object holding a single `id` field
+    /// (`{ "id": "acct_..." }`).
     fn parse(data: Value) -> InputValueResult<Self> {
-        match data.clone() {
-            Value::String(inner) => {
-                if let Ok(pxid) = crate::Pxid::from_str(&inner) {
-                    return Ok(pxid);
-                }
-
-                Err(InputValueError::expected_type(data))
+        let inner = match &data {
+            Value::String(inner) => inner,
+            Value::Object(fields) if fields.len() == 1 => match fields.get("id") {
+                Some(Value::String(inner)) => inner,
+                _ => return Err(InputValueError::expected_type(data)),
+            },
+            Value::Number(number) => {
+                return Err(InputValueError::custom(format!(
+                    "Pxid must be provided as a string, found number {number}."
+                )))
             }
-            _ => Err(InputValueError::expected_type(data)),
+            _ => return Err(InputValueError::expected_type(data)),
+        };
+
+        if let Ok(pxid) = crate::Pxid::from_str(inner) {
+            return Ok(pxid);
         }
+
+        Err(InputValueError::expected_type(data))
     }
 
     fn to_value(&self) -> Value {
@@ -559,7 +1632,17 @@ impl ScalarType for Pxid {
 impl CursorType for Pxid {
     type Error = crate::error::Error;
 
+    /// Decodes the cursor into a `Pxid`, when the `percent-encoding` feature
+    /// is enabled URL-encoded cursors are decoded first, falling back to the
+    /// raw cursor if percent decoding fails.
     fn decode_cursor(s: &str) -> std::result::Result<Self, Self::Error> {
+        #[cfg(feature = "percent-encoding")]
+        if let Ok(decoded) = percent_encoding::percent_decode_str(s).decode_utf8() {
+            if let Ok(pxid) = Pxid::from_str(&decoded) {
+                return Ok(pxid);
+            }
+        }
+
         Pxid::from_str(s)
     }
 
@@ -568,11 +1651,25 @@ impl CursorType for Pxid {
     }
 }
 
+/// Decodes the string held by the built-in `ID` scalar as done by `FromStr`,
+/// for schemas using `ID` on every identifier, such as Relay node IDs.
+///
+/// The conversion into `ID` is provided by `async_graphql` for every
+/// `Display` type, so `ID::from(pxid)` holds the string form.
+#[cfg(feature = "async-graphql")]
+impl TryFrom<ID> for Pxid {
+    type Error = Error;
+
+    fn try_from(value: ID) -> Result<Self> {
+        Pxid::from_str(&value)
+    }
+}
+
 #[cfg(feature = "serde")]
 struct PxidVisitor;
 
 #[cfg(feature = "serde")]
-impl<'de> Visitor<'de> for PxidVisitor {
+impl Visitor<'_> for PxidVisitor {
     type Value = Pxid;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
@@ -610,9 +1707,12 @@ impl<'de> Deserialize<'de> for Pxid {
 #[cfg(feature = "async-graphql")]
 #[cfg(test)]
 mod asyng_graphql_tests {
-    use async_graphql::ScalarType;
+    use async_graphql::indexmap::IndexMap;
+    use std::str::FromStr;
 
-    use super::{Pxid, Value};
+    use async_graphql::{Name, Pos, ScalarType, ID};
+
+    use super::{DecodeError, Error, Pxid, Value};
 
     #[test]
     fn validates_string_is_actual_pxid_instance() {
@@ -632,24 +1732,472 @@ mod asyng_graphql_tests {
 
         assert!(pxid_str_scalar.is_err());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    #[cfg(feature = "serde")]
-    use serde_test::{assert_ser_tokens, Configure, Token};
+    #[test]
+    fn parses_object_holding_id_field() {
+        let mut fields = IndexMap::new();
 
-    use crate::{DecodeError, Error};
+        fields.insert(
+            Name::new("id"),
+            Value::String(String::from("acct_9m4e2mr0ui3e8a215n4g")),
+        );
 
-    use super::*;
+        let pxid = Pxid::parse(Value::Object(fields)).unwrap();
+
+        assert_eq!(pxid.to_string(), "acct_9m4e2mr0ui3e8a215n4g");
+    }
 
-    // https://github.com/rs/xid/blob/e6fb919be3fc74f2b846a6d174e57e076a38b1c1/id_test.go#L120
     #[test]
-    fn encodes_an_id_as_a_string() {
-        assert_eq!(
-            Pxid([
-                0x61, 0x63, 0x63, 0x74, 0x4d, 0x88, 0xe1, 0x5b, 0x60, 0xf4, 0x86, 0xe4, 0x28, 0x41,
-                0x2d, 0xc9
+    fn invalidates_object_without_single_id_field() {
+        let mut fields = IndexMap::new();
+
+        fields.insert(
+            Name::new("pxid"),
+            Value::String(String::from("acct_9m4e2mr0ui3e8a215n4g")),
+        );
+
+        assert!(Pxid::parse(Value::Object(fields.clone())).is_err());
+
+        fields.insert(
+            Name::new("id"),
+            Value::String(String::from("acct_9m4e2mr0ui3e8a215n4g")),
+        );
+
+        assert!(Pxid::parse(Value::Object(fields)).is_err());
+
+        let mut fields = IndexMap::new();
+
+        fields.insert(Name::new("id"), Value::Number(42.into()));
+
+        assert!(Pxid::parse(Value::Object(fields)).is_err());
+    }
+
+    #[test]
+    fn rejects_numbers_with_message() {
+        let err = Pxid::parse(Value::Number(42.into())).unwrap_err();
+
+        assert_eq!(
+            err.into_server_error(Pos::default()).message,
+            "Failed to parse \"Pxid\": Pxid must be provided as a string, found number 42."
+        );
+    }
+
+    #[test]
+    fn converts_into_built_in_id() {
+        let pxid = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let id = ID::from(pxid);
+
+        assert_eq!(id, ID::from("acct_9m4e2mr0ui3e8a215n4g"));
+        assert_eq!(Pxid::try_from(id), Ok(pxid));
+
+        let nil = ID::from(Pxid::nil());
+
+        assert_eq!(Pxid::try_from(nil), Ok(Pxid::nil()));
+    }
+
+    #[test]
+    fn rejects_built_in_id_not_holding_pxid() {
+        assert_eq!(
+            Pxid::try_from(ID::from("42")),
+            Err(Error::Decode(DecodeError::InvalidLength(
+                String::from("42"),
+                2
+            )))
+        );
+        assert!(Pxid::try_from(ID::from("acct_9m4e2mr0ui3e8a215n4!")).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "percent-encoding")]
+    fn decodes_url_encoded_cursor() {
+        use async_graphql::connection::CursorType;
+
+        let pxid = Pxid::decode_cursor("acct%5F9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(pxid.to_string(), "acct_9m4e2mr0ui3e8a215n4g");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[cfg(feature = "serde")]
+    use serde_test::{assert_ser_tokens, Configure, Token};
+    use static_assertions::assert_impl_all;
+
+    use crate::{DecodeError, Error};
+
+    use super::*;
+
+    #[test]
+    fn display_writes_prefix_separator_and_encoded_xid() {
+        let mut ids = Factory::deterministic(7)
+            .new_sorted_ids("acct", 100)
+            .unwrap();
+
+        ids.push(Pxid::new("dog").unwrap());
+        ids.push(Pxid::from_str("a_9m4e2mr0ui3e8a215n4g").unwrap());
+
+        for id in ids {
+            let expected = format!(
+                "{}_{}",
+                id.prefix().unwrap(),
+                Pxid::encode_xid(&id.xid_bytes()).unwrap()
+            );
+
+            assert_eq!(id.to_string(), expected);
+            assert_eq!(format!("{id:>30}"), format!("{:>30}", expected));
+        }
+
+        let mut invalid = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        invalid.0[0] = 0xff;
+
+        assert_eq!(invalid.to_string(), "\u{fffd}cct_9m4e2mr0ui3e8a215n4g");
+        assert_eq!(Pxid::nil().to_string(), "nil_00000000000000000000");
+    }
+
+    #[test]
+    fn borrows_prefix_without_padding() {
+        for prefix in ["a", "ab", "abc", "abcd", "A1", "z9Z9"] {
+            let id = Pxid::new(prefix).unwrap();
+            let borrowed: Result<&str> = id.prefix_str();
+
+            assert_eq!(borrowed, Ok(prefix));
+            assert_eq!(id.prefix_len(), prefix.len());
+            assert_eq!(
+                id.prefix().unwrap().trim_end_matches('\0'),
+                id.prefix_str().unwrap()
+            );
+            assert_eq!(id.prefix().unwrap().len(), PREFIX_LENGTH);
+        }
+
+        assert_eq!(Pxid::nil().prefix_str(), Ok(""));
+        assert_eq!(Pxid::nil().prefix_len(), 0);
+    }
+
+    #[test]
+    fn fails_to_borrow_non_utf8_prefixes() {
+        for prefix_bytes in [
+            [0xff, 0, 0, 0],
+            [b'a', 0xc3, 0, 0],
+            [0xf0, 0x9f, 0x98, b'a'],
+        ] {
+            let mut bytes = [0x11; BINARY_LENGTH];
+
+            bytes[..PREFIX_LENGTH].copy_from_slice(&prefix_bytes);
+
+            let id = Pxid::from(bytes);
+
+            assert!(matches!(
+                id.prefix_str(),
+                Err(Error::Decode(DecodeError::InvalidUtf8(_)))
+            ));
+            assert_eq!(id.prefix_str().unwrap_err(), id.prefix().unwrap_err());
+            assert_eq!(
+                id.prefix_len(),
+                prefix_bytes.iter().rposition(|b| *b != 0).unwrap() + 1
+            );
+        }
+    }
+
+    #[test]
+    fn from_parts_matches_unchecked_layout() {
+        assert_eq!(
+            Pxid::from_parts("acct", 0x0102_0304, [0xAA, 0xBB, 0xCC], 0xDDEE, 0x00_FF00),
+            Ok(Pxid::from_parts_unchecked(
+                *b"acct",
+                0x0102_0304,
+                [0xAA, 0xBB, 0xCC],
+                0xDDEE,
+                0x00_FF00
+            ))
+        );
+    }
+
+    #[test]
+    fn from_parts_pads_one_byte_prefixes() {
+        let id = Pxid::from_parts("a", 0x0102_0304, [0xAA, 0xBB, 0xCC], 0xDDEE, 7).unwrap();
+
+        assert_eq!(id.prefix_bytes(), *b"a\0\0\0");
+        assert_eq!(id.prefix_len(), 1);
+        assert_eq!(
+            id,
+            Pxid::from_parts_unchecked(*b"a\0\0\0", 0x0102_0304, [0xAA, 0xBB, 0xCC], 0xDDEE, 7)
+        );
+        assert_eq!(
+            Pxid::from_parts("", 0x0102_0304, [0xAA, 0xBB, 0xCC], 0xDDEE, 7),
+            Err(Error::Decode(DecodeError::MissingPrefix(String::new())))
+        );
+    }
+
+    #[test]
+    fn round_trips_fixed_strings() {
+        for prefix in ["a", "ab", "abc", "abcd"] {
+            let id = Pxid::new(prefix).unwrap();
+            let fixed = id.to_fixed_string();
+
+            assert_eq!(fixed.len(), ENCODED_LENGTH);
+            assert!(fixed.starts_with(&format!("{prefix}_")));
+            assert_eq!(
+                fixed.trim_end().len(),
+                prefix.len() + 1 + XID_ENCODED_LENGTH
+            );
+            assert_eq!(Pxid::from_fixed_str(&fixed), Ok(id));
+        }
+
+        assert_eq!(Pxid::nil().to_fixed_string(), "nil_00000000000000000000 ");
+        assert_eq!(
+            Pxid::from_fixed_str(&Pxid::nil().to_fixed_string()),
+            Ok(Pxid::nil())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_fixed_strings() {
+        assert_eq!(
+            Pxid::from_fixed_str("dog_9m4e2mr0ui3e8a215n4g"),
+            Err(Error::Decode(DecodeError::InvalidLength(
+                String::from("dog_9m4e2mr0ui3e8a215n4g"),
+                24
+            )))
+        );
+        assert_eq!(
+            Pxid::from_fixed_str("dog_9m4e2mr0ui3e8a215n4g  "),
+            Err(Error::Decode(DecodeError::InvalidLength(
+                String::from("dog_9m4e2mr0ui3e8a215n4g  "),
+                26
+            )))
+        );
+        assert_eq!(
+            Pxid::from_fixed_str(&" ".repeat(ENCODED_LENGTH)),
+            Err(Error::PaddingOnly)
+        );
+        assert_eq!(
+            Pxid::from_fixed_str("dog_9m4e2mr0 i3e8a215n4g "),
+            Err(Error::InteriorPadding(
+                String::from("dog_9m4e2mr0 i3e8a215n4g "),
+                12
+            ))
+        );
+        assert_eq!(
+            Pxid::from_fixed_str(" dog_9m4e2mr0ui3e8a215n4g"),
+            Err(Error::InteriorPadding(
+                String::from(" dog_9m4e2mr0ui3e8a215n4g"),
+                0
+            ))
+        );
+    }
+
+    #[test]
+    fn from_str_rejects_fixed_strings_padding() {
+        let fixed = Pxid::new("dog").unwrap().to_fixed_string();
+
+        assert!(fixed.ends_with(FIXED_PAD));
+        assert!(Pxid::from_str(&fixed).is_err());
+        assert!(Pxid::from_str(fixed.trim_end()).is_ok());
+    }
+
+    #[test]
+    fn shares_counter_between_generated_ids() {
+        let counters = (0..1000)
+            .map(|_| Pxid::new("acct").unwrap().counter())
+            .collect::<HashSet<u32>>();
+
+        assert_eq!(counters.len(), 1000);
+    }
+
+    #[test]
+    fn compares_chronologically_across_machines() {
+        let earlier = Pxid::from_parts("user", 100, [0xff; 3], 0xffff, 7).unwrap();
+        let later = Pxid::from_parts("acct", 101, [0; 3], 0, 0).unwrap();
+        let next = Pxid::from_parts("acct", 101, [0xff; 3], 0xffff, 1).unwrap();
+        let twin = Pxid::from_parts("dog", 101, [1, 2, 3], 42, 0).unwrap();
+
+        assert!(earlier > later);
+        assert_eq!(earlier.chrono_cmp(&later), CmpOrdering::Less);
+        assert_eq!(later.chrono_cmp(&earlier), CmpOrdering::Greater);
+        assert_eq!(later.chrono_cmp(&next), CmpOrdering::Less);
+        assert_eq!(later.chrono_cmp(&twin), CmpOrdering::Equal);
+        assert_ne!(later, twin);
+
+        let mut ids = vec![next, earlier, later];
+
+        ids.sort_by(Pxid::chrono_cmp);
+
+        assert_eq!(ids, [earlier, later, next]);
+    }
+
+    #[test]
+    fn checks_successors() {
+        let at = |time: u32, pid: u16, counter: u32| {
+            Pxid::from_parts("acct", time, [1, 2, 3], pid, counter).unwrap()
+        };
+        let prev = at(1_700_000_000, 7, 41);
+
+        assert!(at(1_700_000_000, 7, 42).is_successor_of(&prev));
+        assert!(!prev.is_successor_of(&prev));
+        assert!(!prev.is_successor_of(&at(1_700_000_000, 7, 42)));
+        assert!(!at(1_700_000_000, 7, 43).is_successor_of(&prev));
+        assert!(!at(1_700_000_001, 7, 42).is_successor_of(&prev));
+        assert!(!at(1_700_000_000, 8, 42).is_successor_of(&prev));
+        assert!(!Pxid::from_parts("dog", 1_700_000_000, [1, 2, 3], 7, 42)
+            .unwrap()
+            .is_successor_of(&prev));
+    }
+
+    #[test]
+    fn checks_successors_across_counter_wrap() {
+        let at =
+            |counter: u32| Pxid::from_parts("acct", 1_700_000_000, [1, 2, 3], 7, counter).unwrap();
+
+        assert!(at(0).is_successor_of(&at(COUNTER_MAX)));
+        assert!(at(COUNTER_MAX).is_successor_of(&at(COUNTER_MAX - 1)));
+        assert!(!at(1).is_successor_of(&at(COUNTER_MAX)));
+        assert!(!at(COUNTER_MAX).is_successor_of(&at(0)));
+    }
+
+    #[test]
+    fn is_copy_send_and_sync() {
+        assert_impl_all!(Pxid: Copy, Clone, Send, Sync);
+    }
+
+    #[test]
+    fn iterates_btree_set_in_time_order() {
+        use std::collections::BTreeSet;
+
+        let at = |time: u32, counter: u32| {
+            Pxid::from_parts_unchecked(*b"evnt", time, [9, 9, 9], 9, counter)
+        };
+        let mut ids = BTreeSet::new();
+
+        assert!(ids.insert(at(30, 0)));
+        assert!(ids.insert(at(10, 5)));
+        assert!(ids.insert(at(20, 0)));
+        assert!(ids.insert(at(10, 1)));
+        assert!(!ids.insert(at(20, 0)));
+
+        assert_eq!(
+            ids.iter().copied().collect::<Vec<Pxid>>(),
+            vec![at(10, 1), at(10, 5), at(20, 0), at(30, 0)]
+        );
+        assert!(ids
+            .iter()
+            .zip(ids.iter().skip(1))
+            .all(|(a, b)| a.timestamp() <= b.timestamp()));
+        assert_eq!(
+            ids.range(at(10, 2)..at(30, 0))
+                .copied()
+                .collect::<Vec<Pxid>>(),
+            vec![at(10, 5), at(20, 0)]
+        );
+    }
+
+    #[test]
+    fn scans_btree_map_by_timestamp() {
+        use std::collections::BTreeMap;
+
+        let lower = |time: u32| Pxid::from_parts_unchecked(*b"evnt", time, [0; 3], 0, 0);
+        let events = (0..10)
+            .map(|time| {
+                let id = Pxid::from_parts_unchecked(*b"evnt", time * 10, [1, 2, 3], 4, COUNTER_MAX);
+
+                (id, time * 10)
+            })
+            .collect::<BTreeMap<Pxid, u32>>();
+        let other = Pxid::from_parts_unchecked(*b"user", 25, [1, 2, 3], 4, 0);
+        let mut mixed = events.clone();
+
+        mixed.insert(other, 25);
+
+        assert_eq!(
+            events
+                .range(lower(20)..lower(50))
+                .map(|(_, time)| *time)
+                .collect::<Vec<u32>>(),
+            vec![20, 30, 40]
+        );
+        assert_eq!(
+            mixed
+                .range(lower(20)..lower(50))
+                .map(|(_, time)| *time)
+                .collect::<Vec<u32>>(),
+            vec![20, 30, 40]
+        );
+        assert_eq!(
+            events.range(lower(50)..).next().map(|(_, time)| *time),
+            Some(50)
+        );
+        assert_eq!(events.range(..lower(0)).next(), None);
+    }
+
+    #[test]
+    fn builds_range_bounds_keeping_prefix() {
+        let min = Pxid::min_for("usr").unwrap();
+        let max = Pxid::max_for("usr").unwrap();
+
+        assert_eq!(max.prefix_bytes(), *b"usr\0");
+        assert_eq!(max.prefix().unwrap().trim_end_matches('\0'), "usr");
+        assert_eq!(max.xid_bytes(), [0xff; 12]);
+        assert_eq!(min.prefix_bytes(), *b"usr\0");
+        assert_eq!(min.xid_bytes(), [0; 12]);
+        assert_eq!(max.to_string(), "usr\0_vvvvvvvvvvvvvvvvvvvg");
+        assert_eq!(Pxid::from_str(&max.to_string()).unwrap(), max);
+
+        let id = Pxid::new("usr").unwrap();
+        let other = Pxid::new("user").unwrap();
+
+        assert!(min <= id && id <= max);
+        assert!(!(min..=max).contains(&other));
+        assert!(Pxid::max_for("users").is_err());
+        assert!(Pxid::min_for("").is_err());
+    }
+
+    #[test]
+    fn builds_increasing_time_counter_keys() {
+        use crate::factory::Factory;
+
+        let factory = Factory::deterministic(7);
+        let first = factory.new_with_time("evnt", 1_700_000_000).unwrap();
+        let second = factory.new_with_time("evnt", 1_700_000_000).unwrap();
+        let later = factory.new_with_time("evnt", 1_700_000_001).unwrap();
+
+        assert!(first.time_counter_key() < second.time_counter_key());
+        assert!(second.time_counter_key() < later.time_counter_key());
+        assert_eq!(
+            first.time_counter_key(),
+            (1_700_000_000_u64 << 32) | (u64::from(first.counter()) << 8)
+        );
+    }
+
+    #[test]
+    fn computes_stable_shards() {
+        let account = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let user = Pxid::from_str("user_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(account.shard(1024), 167);
+        assert_eq!(account.shard(1024), user.shard(1024));
+        assert_eq!(account.shard(1), 0);
+        assert_eq!(account.shard(0), 0);
+
+        for _ in 0..100 {
+            let id = Pxid::new("acct").unwrap();
+
+            assert_eq!(id.shard(16), id.shard(16));
+            assert!(id.shard(16) < 16);
+            assert!(id.shard(u32::MAX) < u32::MAX);
+        }
+    }
+
+    // https://github.com/rs/xid/blob/e6fb919be3fc74f2b846a6d174e57e076a38b1c1/id_test.go#L120
+    #[test]
+    fn encodes_an_id_as_a_string() {
+        assert_eq!(
+            Pxid([
+                0x61, 0x63, 0x63, 0x74, 0x4d, 0x88, 0xe1, 0x5b, 0x60, 0xf4, 0x86, 0xe4, 0x28, 0x41,
+                0x2d, 0xc9
             ])
             .to_string(),
             "acct_9m4e2mr0ui3e8a215n4g"
@@ -688,11 +2236,41 @@ mod tests {
             Pxid::from_str("acct_9m4e2mr0ui3e8a215n4x"),
             Err(Error::Decode(DecodeError::InvalidChar(
                 "9m4e2mr0ui3e8a215n4x".into(),
-                'x'
+                'x',
+                19
             )))
         );
     }
 
+    #[test]
+    fn reports_invalid_char_position() {
+        for (input, c, pos) in [
+            ("acct_wm4e2mr0ui3e8a215n4g", 'w', 0),
+            ("acct_9m4e2mr0zi3e8a215n4g", 'z', 8),
+            ("acct_9m4e2mr0ui3e8a215nGg", 'G', 18),
+        ] {
+            let xid = input.split_once(SEPARATOR).unwrap().1;
+
+            match Pxid::from_str(input) {
+                Err(Error::Decode(DecodeError::InvalidChar(found, found_c, found_pos))) => {
+                    assert_eq!(
+                        (found.as_str(), found_c, found_pos),
+                        (xid, c, pos),
+                        "{input}"
+                    );
+                }
+                other => panic!("Unexpected result {other:?} for {input}"),
+            }
+        }
+
+        assert_eq!(
+            Pxid::from_str("acct_9m4e2mr0zi3e8a215n4g")
+                .unwrap_err()
+                .to_string(),
+            "Failed to decode into a XID. String cannot be decoded into a PXID instance. 9m4e2mr0zi3e8a215n4g found invalid char 'z' at position 8."
+        );
+    }
+
     #[test]
     fn retrieves_machine_id_from_xid_instance() {
         let id: Bytes = [
@@ -727,81 +2305,300 @@ mod tests {
     }
 
     #[test]
-    fn retrives_xid_bytes() {
-        let id: Bytes = [
-            0x61, 0x63, 0x63, 0x74, 0x4d, 0x88, 0xe1, 0x5b, 0x60, 0xf4, 0x86, 0xe4, 0x28, 0x41,
-            0x2d, 0xc9,
-        ];
-        let xid = Pxid::from(id);
+    fn retrieves_parts_from_xid_instance() {
+        let id =
+            Pxid::from_parts("acct", 0x4d88_e15b, [0x60, 0xf4, 0x86], 0xe428, 0x41_2dc9).unwrap();
 
+        assert_eq!(id.prefix().unwrap(), "acct");
         assert_eq!(
-            xid.xid_bytes(),
-            [0x4d, 0x88, 0xe1, 0x5b, 0x60, 0xf4, 0x86, 0xe4, 0x28, 0x41, 0x2d, 0xc9]
+            id.timestamp(),
+            UNIX_EPOCH + Duration::from_secs(0x4d88_e15b)
         );
+        assert_eq!(id.machine_id(), [0x60, 0xf4, 0x86]);
+        assert_eq!(id.process_id(), 0xe428);
+        assert_eq!(id.counter(), 0x41_2dc9);
     }
 
     #[test]
-    fn encodes_a_xid_as_a_string() {
+    fn converts_legacy_bytes_into_current_layout() {
+        let legacy: Bytes = [
+            0x61, 0x63, 0x63, 0x74, 0x4d, 0x88, 0xe1, 0x5b, 0x00, 0x60, 0xf4, 0x86, 0xe4, 0x28,
+            0x2d, 0xc9,
+        ];
+        let id = Pxid::from_legacy_bytes(legacy);
+
         assert_eq!(
-            Pxid::encode_xid(&[
-                0x4d, 0x88, 0xe1, 0x5b, 0x60, 0xf4, 0x86, 0xe4, 0x28, 0x41, 0x2d, 0xc9
+            id,
+            Pxid([
+                0x61, 0x63, 0x63, 0x74, 0x4d, 0x88, 0xe1, 0x5b, 0x60, 0xf4, 0x86, 0xe4, 0x28, 0x00,
+                0x2d, 0xc9
             ])
-            .unwrap(),
-            "9m4e2mr0ui3e8a215n4g"
         );
+        assert_eq!(id.machine_id(), [0x60, 0xf4, 0x86]);
+        assert_eq!(id.process_id(), 0xe428);
+        assert_eq!(id.counter(), 0x2dc9);
     }
 
     #[test]
-    fn decodes_a_xid_from_a_string() {
+    fn converts_protobuf_bytes() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(id.to_protobuf_bytes(), id.0);
+        assert_eq!(Pxid::from_protobuf_bytes(&id.to_protobuf_bytes()), Ok(id));
         assert_eq!(
-            Pxid::decode_xid("9m4e2mr0ui3e8a215n4g").unwrap(),
-            [0x4d, 0x88, 0xe1, 0x5b, 0x60, 0xf4, 0x86, 0xe4, 0x28, 0x41, 0x2d, 0xc9],
+            Pxid::from_protobuf_bytes(&[0; BINARY_LENGTH]),
+            Ok(Pxid::nil())
+        );
+        assert_eq!(
+            Pxid::from_protobuf_bytes(&id.0[..15]),
+            Err(Error::InvalidBinaryLength(15))
+        );
+        assert_eq!(
+            Pxid::from_protobuf_bytes(&[]),
+            Err(Error::InvalidBinaryLength(0))
+        );
+        assert_eq!(
+            Pxid::from_protobuf_bytes(&[0; 17]),
+            Err(Error::InvalidBinaryLength(17))
         );
+        assert!(Pxid::from_protobuf_bytes(&[0xff; BINARY_LENGTH]).is_err());
     }
 
     #[test]
-    fn validates_invalid_prefix() {
+    fn decodes_legacy_and_current_strings_while_migrating() {
+        let legacy = Pxid([
+            0x61, 0x63, 0x63, 0x74, 0x4d, 0x88, 0xe1, 0x5b, 0x00, 0x60, 0xf4, 0x86, 0xe4, 0x28,
+            0x2d, 0xc9,
+        ])
+        .to_string();
+        let current = Pxid::from_legacy_bytes(Pxid::from_str(&legacy).unwrap().0).to_string();
+
+        assert_ne!(legacy, current);
         assert_eq!(
-            Pxid::from_str("account_9m4e2mr0ui3e8a21"),
-            Err(Error::Decode(DecodeError::InvalidPrefixLength(
-                String::from("account"),
-                7
-            ))),
+            Pxid::from_str_migrating(&legacy, true).unwrap(),
+            Pxid::from_str_migrating(&current, false).unwrap()
+        );
+        assert_eq!(
+            Pxid::from_str_migrating(&current, false).unwrap(),
+            Pxid::from_str(&current).unwrap()
+        );
+        assert_eq!(
+            Pxid::from_str_migrating(&Pxid::nil().to_string(), true),
+            Ok(Pxid::nil())
         );
+        assert!(Pxid::from_str_migrating("acct_", true).is_err());
     }
 
     #[test]
-    fn validates_invalid_xid() {
+    fn retrives_xid_bytes() {
+        let id: Bytes = [
+            0x61, 0x63, 0x63, 0x74, 0x4d, 0x88, 0xe1, 0x5b, 0x60, 0xf4, 0x86, 0xe4, 0x28, 0x41,
+            0x2d, 0xc9,
+        ];
+        let xid = Pxid::from(id);
+
         assert_eq!(
-            Pxid::from_str("user_9m4e2mr0ui3e8a21s5n4g"),
-            Err(Error::Decode(DecodeError::InvalidLength(
-                String::from("user_9m4e2mr0ui3e8a21s5n4g"),
-                26
-            ))),
+            xid.xid_bytes(),
+            [0x4d, 0x88, 0xe1, 0x5b, 0x60, 0xf4, 0x86, 0xe4, 0x28, 0x41, 0x2d, 0xc9]
         );
     }
 
     #[test]
-    fn creates_pxid_with_prefix() {
-        let value = Pxid::new("acct");
+    fn converts_into_bytes_and_back() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let bytes: Bytes = id.into();
 
-        assert!(value.is_ok());
+        assert_eq!(Pxid::from(<Bytes>::from(id)), id);
+        assert_eq!(Pxid::from(bytes), id);
     }
 
     #[test]
-    fn creates_pxid_with_prefix_and_encodes_decodes() {
-        let value = Pxid::new("acct");
-
-        assert!(value.is_ok());
-        let id = value.unwrap();
-        let encoded = id.to_string();
-
-        assert!(encoded.starts_with("acct_"));
-
-        let decoded = Pxid::from_str(&encoded);
-        assert!(decoded.is_ok());
+    fn retrieves_bytes_as_slice() {
+        let id: Bytes = [
+            0x61, 0x63, 0x63, 0x74, 0x4d, 0x88, 0xe1, 0x5b, 0x60, 0xf4, 0x86, 0xe4, 0x28, 0x41,
+            0x2d, 0xc9,
+        ];
+        let xid = Pxid::from(id);
+        let slice: &[u8] = xid.as_ref();
 
-        assert_eq!(id, decoded.unwrap());
+        assert_eq!(slice, &id[..]);
+        assert_eq!(xid.len(), BINARY_LENGTH);
+    }
+
+    #[test]
+    fn compares_with_raw_bytes() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let bytes: Bytes = id.into();
+        let mut other = bytes;
+
+        other[15] ^= 1;
+
+        assert!(id == bytes);
+        assert!(bytes == id);
+        assert!(id != other);
+        assert!(other != id);
+        assert!(id == &bytes[..]);
+        assert!(id != &other[..]);
+        assert!(id != &bytes[..15]);
+        assert!(id != &[0_u8; 0][..]);
+    }
+
+    #[test]
+    fn sorts_by_xid_ignoring_prefix() {
+        let older = Pxid::from_parts("zzzz", 1, [0, 0, 0], 0, 0).unwrap();
+        let newer = Pxid::from_parts("aaaa", 2, [0, 0, 0], 0, 0).unwrap();
+
+        assert!(older.prefix_bytes() > newer.prefix_bytes());
+        assert!(older.sort_key() < newer.sort_key());
+        assert!(older.sort_key_u96() < newer.sort_key_u96());
+        assert_eq!(newer.sort_key_u96(), 2_u128 << 64);
+    }
+
+    #[test]
+    fn shortens_for_display() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(id.short(), "acct_9m4e…5n4g");
+        assert_eq!(id.short_with(2, 0), "acct_9m…");
+        assert_eq!(id.short_with(10, 10), "acct_9m4e2mr0ui3e8a215n4g");
+        assert!(id.short().starts_with("acct"));
+        assert!(id.short().contains('…'));
+    }
+
+    #[test]
+    fn generates_batches_sharing_timestamp() {
+        let ids = Pxid::new_batch("evnt", 100).unwrap();
+
+        assert_eq!(ids.len(), 100);
+
+        for pair in ids.windows(2) {
+            assert_eq!(pair[0].timestamp(), pair[1].timestamp());
+            assert_eq!(pair[0].machine_id(), pair[1].machine_id());
+            assert!(pair[0].counter() < pair[1].counter());
+        }
+    }
+
+    #[test]
+    fn batches_share_counter_with_generated_ids() {
+        let mut counters = HashSet::new();
+
+        for _ in 0..50 {
+            for id in Pxid::new_batch("evnt", 100).unwrap() {
+                assert!(counters.insert(id.counter()));
+            }
+
+            assert!(counters.insert(Pxid::new("evnt").unwrap().counter()));
+        }
+
+        assert_eq!(counters.len(), 5_050);
+    }
+
+    #[test]
+    fn generates_empty_batches() {
+        assert_eq!(Pxid::new_batch("evnt", 0), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn rejects_batches_exceeding_counter_space() {
+        let count = COUNTER_MAX as usize + 2;
+
+        assert_eq!(
+            Pxid::new_batch("evnt", count),
+            Err(Error::BatchTooLarge(count))
+        );
+    }
+
+    #[test]
+    fn finds_ids_in_text() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let line = "2024-01-01 INFO created acct_9m4e2mr0ui3e8a215n4g";
+
+        assert_eq!(id.find_in(line), Some(24));
+        assert_eq!(id.find_in("acct_9m4e2mr0ui3e8a215n40"), None);
+    }
+
+    #[test]
+    fn finds_all_ids_in_text() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let line =
+            "acct_9m4e2mr0ui3e8a215n4g -> ordr_9m4e2mr0ui3e8a215n4g -> acct_9m4e2mr0ui3e8a215n4g";
+
+        assert_eq!(id.find_all_in(line).collect::<Vec<usize>>(), vec![0, 58]);
+        assert_eq!(id.find_all_in("").count(), 0);
+    }
+
+    #[test]
+    fn splits_into_prefix_and_xid() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let (prefix, xid) = id.split().unwrap();
+
+        assert_eq!(prefix, "acct");
+        assert_eq!(xid, "9m4e2mr0ui3e8a215n4g");
+        assert_eq!(format!("{prefix}_{xid}"), id.to_string());
+    }
+
+    #[test]
+    fn encodes_a_xid_as_a_string() {
+        assert_eq!(
+            Pxid::encode_xid(&[
+                0x4d, 0x88, 0xe1, 0x5b, 0x60, 0xf4, 0x86, 0xe4, 0x28, 0x41, 0x2d, 0xc9
+            ])
+            .unwrap(),
+            "9m4e2mr0ui3e8a215n4g"
+        );
+    }
+
+    #[test]
+    fn decodes_a_xid_from_a_string() {
+        assert_eq!(
+            Pxid::decode_xid("9m4e2mr0ui3e8a215n4g").unwrap(),
+            [0x4d, 0x88, 0xe1, 0x5b, 0x60, 0xf4, 0x86, 0xe4, 0x28, 0x41, 0x2d, 0xc9],
+        );
+    }
+
+    #[test]
+    fn validates_invalid_prefix() {
+        assert_eq!(
+            Pxid::from_str("account_9m4e2mr0ui3e8a21"),
+            Err(Error::Decode(DecodeError::InvalidPrefixLength(
+                String::from("account"),
+                7
+            ))),
+        );
+    }
+
+    #[test]
+    fn validates_invalid_xid() {
+        assert_eq!(
+            Pxid::from_str("user_9m4e2mr0ui3e8a21s5n4g"),
+            Err(Error::Decode(DecodeError::InvalidLength(
+                String::from("user_9m4e2mr0ui3e8a21s5n4g"),
+                26
+            ))),
+        );
+    }
+
+    #[test]
+    fn creates_pxid_with_prefix() {
+        let value = Pxid::new("acct");
+
+        assert!(value.is_ok());
+    }
+
+    #[test]
+    fn creates_pxid_with_prefix_and_encodes_decodes() {
+        let value = Pxid::new("acct");
+
+        assert!(value.is_ok());
+        let id = value.unwrap();
+        let encoded = id.to_string();
+
+        assert!(encoded.starts_with("acct_"));
+
+        let decoded = Pxid::from_str(&encoded);
+        assert!(decoded.is_ok());
+
+        assert_eq!(id, decoded.unwrap());
     }
 
     #[test]
@@ -820,6 +2617,81 @@ mod tests {
         assert_eq!(id, decoded.unwrap());
     }
 
+    #[test]
+    fn builds_canonical_short_prefixes_on_every_path() {
+        use std::collections::hash_map::RandomState;
+        use std::hash::BuildHasher;
+
+        use crate::factory::Factory;
+
+        let generated = Pxid::from_parts("dog", 1_700_000_000, [1, 2, 3], 4, 5).unwrap();
+        let bytes: Bytes = generated.into();
+        let encoded = generated.to_string();
+        let ids = [
+            Pxid::from_parts_unchecked(*b"dog\0", 1_700_000_000, [1, 2, 3], 4, 5),
+            Pxid::from_parts("dog\0", 1_700_000_000, [1, 2, 3], 4, 5).unwrap(),
+            Pxid::from_str(&encoded).unwrap(),
+            Pxid::from_str(&encoded.replace('\0', "")).unwrap(),
+            Pxid::from_str(&encoded.replace("dog\0", "DOG")).unwrap(),
+            Pxid::from_str_preserve_case(&encoded).unwrap(),
+            Pxid::from(bytes),
+            Pxid::from_packed(&bytes).unwrap(),
+            Pxid::from_xid_bytes("dog", &generated.xid_bytes()).unwrap(),
+            Pxid::from_ulid_string("dog", &generated.to_ulid_string()).unwrap(),
+        ];
+        let hasher = RandomState::new();
+
+        assert_eq!(generated.prefix_bytes(), *b"dog\0");
+
+        for id in ids {
+            assert_eq!(Bytes::from(id), bytes);
+            assert_eq!(hasher.hash_one(id), hasher.hash_one(generated));
+            assert_eq!(id.to_string(), encoded);
+        }
+
+        let built = Factory::deterministic(7)
+            .new_with_time("dog", 1_700_000_000)
+            .unwrap();
+        let expected = Pxid::from_parts(
+            "dog",
+            1_700_000_000,
+            built.machine_id(),
+            built.process_id(),
+            built.counter(),
+        )
+        .unwrap();
+
+        assert_eq!(Bytes::from(built), Bytes::from(expected));
+        assert_eq!(hasher.hash_one(built), hasher.hash_one(expected));
+        assert_eq!(
+            Pxid::from_str(&built.to_string().replace('\0', "")).unwrap(),
+            built
+        );
+
+        #[cfg(feature = "serde")]
+        assert_eq!(
+            serde_json::from_str::<Pxid>(&serde_json::to_string(&generated).unwrap()).unwrap(),
+            generated
+        );
+    }
+
+    #[test]
+    fn rejects_non_canonical_prefixes() {
+        assert!(Pxid::from_parts("d\0g", 0, [0; 3], 0, 0).is_err());
+        assert!(Pxid::from_parts("\0", 0, [0; 3], 0, 0).is_err());
+        assert!(Pxid::from_str("d\0g_9m4e2mr0ui3e8a215n4g").is_err());
+        assert!(Pxid::from_str("\0\0_9m4e2mr0ui3e8a215n4g").is_err());
+        assert!(
+            Pxid::from_packed(&[b'd', 0, b'g', 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]).is_err()
+        );
+        assert!(is_canonical_prefix(*b"dog\0"));
+        assert!(is_canonical_prefix(*b"acct"));
+        assert!(!is_canonical_prefix(*b"\0dog"));
+        assert!(!is_canonical_prefix(*b"d\0g\0"));
+        assert!(!is_canonical_prefix([0; 4]));
+        assert!(!is_canonical_prefix([0xff, 0, 0, 0]));
+    }
+
     #[test]
     fn complains_in_too_large_prefixes() {
         let value = Pxid::new("account");
@@ -830,10 +2702,22 @@ mod tests {
         );
         assert_eq!(
             value.err().unwrap(),
-            Error::PrefixExceedsMaxLength("account".to_string())
+            Error::Decode(DecodeError::InvalidPrefixLength("account".to_string(), 7))
         );
     }
 
+    #[test]
+    fn reports_too_large_prefixes_consistently() {
+        let expected = Err(Error::Decode(DecodeError::InvalidPrefixLength(
+            String::from("account"),
+            7,
+        )));
+
+        assert_eq!(Pxid::new("account"), expected);
+        assert_eq!(Pxid::from_parts("account", 1, [1, 2, 3], 4, 5), expected);
+        assert_eq!(Pxid::from_str("account_9m4e2mr0ui3e8a21"), expected);
+    }
+
     #[test]
     fn display_value_as_string() {
         let value = Pxid::new("user");
@@ -850,7 +2734,668 @@ mod tests {
         let string = value.clone().unwrap().to_string();
         let debug = format!("{:?}", value.unwrap());
 
-        assert_eq!(debug, format!("Pxid(\"{}\")", string));
+        assert_eq!(debug, format!("Pxid(\"{string}\")"));
+    }
+
+    #[test]
+    fn encodes_and_decodes_with_separator() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        for sep in ['.', '-', '·'] {
+            let encoded = id.to_string_with_separator(sep);
+
+            assert_eq!(encoded, format!("acct{sep}9m4e2mr0ui3e8a215n4g"));
+            assert_eq!(Pxid::from_str_with_separator(&encoded, sep).unwrap(), id);
+        }
+
+        assert_eq!(id.to_string_with_separator(SEPARATOR), id.to_string());
+    }
+
+    #[test]
+    fn uses_separator_consistently() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let encoded = id.to_string();
+        let (prefix, xid) = encoded.split_once(SEPARATOR).unwrap();
+
+        assert_eq!(prefix, "acct");
+        assert_eq!(xid, "9m4e2mr0ui3e8a215n4g");
+        assert_eq!(Pxid::from_str(&format!("{prefix}{SEPARATOR}{xid}")), Ok(id));
+        assert!(Pxid::from_str(&encoded.replace(SEPARATOR, "-")).is_err());
+    }
+
+    #[test]
+    fn charsets_describe_encoded_ids() {
+        let encoded = Pxid::new("Ac9z").unwrap().to_string();
+        let (prefix, xid) = encoded.split_once(SEPARATOR).unwrap();
+
+        assert!(prefix.chars().all(|c| PREFIX_CHARSET.contains(c)));
+        assert!(xid.chars().all(|c| XID_CHARSET.contains(c)));
+        assert!(!PREFIX_CHARSET.contains(SEPARATOR));
+        assert!(!XID_CHARSET.contains(SEPARATOR));
+        assert!(PREFIX_CHARSET.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_eq!(PREFIX_CHARSET.len(), 62);
+        assert_eq!(XID_CHARSET.len(), 32);
+    }
+
+    /// `io::Write` sink accepting at most `chunk` bytes per call and
+    /// failing once `limit` bytes are written
+    struct ShortWriter {
+        written: Vec<u8>,
+        chunk: usize,
+        limit: usize,
+    }
+
+    impl io::Write for ShortWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.written.len() >= self.limit {
+                return Err(io::Error::new(io::ErrorKind::BrokenPipe, "sink closed"));
+            }
+
+            let len = buf.len().min(self.chunk);
+
+            self.written.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writes_encoded_as_to_string() {
+        for prefix in ["a", "ab", "abc", "abcd", "ñ", "ñu"] {
+            let id = Pxid::new(prefix).unwrap();
+            let mut encoded = String::new();
+            let mut bytes = Vec::new();
+
+            id.write_encoded(&mut encoded).unwrap();
+            id.write_encoded_bytes(&mut bytes).unwrap();
+
+            assert_eq!(encoded, id.to_string());
+            assert_eq!(bytes, id.to_string().into_bytes());
+        }
+    }
+
+    #[test]
+    fn appends_reserving_exact_capacity() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let mut buf = String::with_capacity(8);
+
+        buf.push_str("id: 0123");
+        assert_eq!(buf.capacity(), buf.len());
+
+        id.append_to(&mut buf);
+
+        assert_eq!(buf, "id: 0123acct_9m4e2mr0ui3e8a215n4g");
+        assert_eq!(buf.capacity(), 8 + ENCODED_LENGTH);
+    }
+
+    #[test]
+    fn decodes_with_try_from() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(Pxid::try_from("ACCT_9m4e2mr0ui3e8a215n4g"), Ok(id));
+        assert_eq!(
+            Pxid::try_from(String::from("acct_9m4e2mr0ui3e8a215n4g")),
+            Ok(id)
+        );
+        assert_eq!(
+            Pxid::try_from("acct"),
+            Err(Error::Decode(DecodeError::InvalidLength(
+                String::from("acct"),
+                4
+            )))
+        );
+    }
+
+    #[test]
+    fn encodes_into_fixed_buffer() {
+        let ids = ["a", "ab", "abc", "abcd"].map(|prefix| Pxid::new(prefix).unwrap());
+
+        for id in ids.into_iter().chain([Pxid::nil()]) {
+            let mut out = [0_u8; ENCODED_LENGTH];
+            let len = id.encode_into(&mut out);
+
+            assert_eq!(&out[..len], id.to_string().as_bytes());
+            assert_eq!(Pxid::decode_from(&out[..len]).unwrap(), id);
+        }
+
+        let mut out = [0_u8; ENCODED_LENGTH];
+
+        assert_eq!(Pxid::from([0xff; 16]).encode_into(&mut out), 0);
+        assert_eq!(out, [0; ENCODED_LENGTH]);
+    }
+
+    #[test]
+    fn decodes_from_undersized_slices() {
+        let encoded = b"acct_9m4e2mr0ui3e8a215n4g";
+
+        for len in 0..encoded.len() {
+            assert!(Pxid::decode_from(&encoded[..len]).is_err());
+        }
+
+        assert!(matches!(
+            Pxid::decode_from(&[b'a', 0xff, b'_']),
+            Err(Error::Decode(DecodeError::InvalidUtf8(_)))
+        ));
+        assert_eq!(
+            Pxid::decode_from(encoded).unwrap(),
+            Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap()
+        );
+    }
+
+    #[test]
+    fn encodes_to_vec() {
+        for s in ["acct_9m4e2mr0ui3e8a215n4g", "dog_9m4e2mr0ui3e8a215n4g"] {
+            let id = Pxid::from_str(s).unwrap();
+
+            assert_eq!(id.encode_to_vec(), id.to_string().into_bytes());
+        }
+
+        let id = Pxid::new("a").unwrap();
+
+        assert_eq!(id.encode_to_vec(), id.to_string().into_bytes());
+        assert_eq!(
+            Pxid::from([0xff; 16]).encode_to_vec(),
+            Pxid::from([0xff; 16]).to_string().into_bytes()
+        );
+    }
+
+    #[test]
+    fn writes_encoded_bytes_on_short_writes() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let mut sink = ShortWriter {
+            written: Vec::new(),
+            chunk: 3,
+            limit: usize::MAX,
+        };
+
+        id.write_encoded_bytes(&mut sink).unwrap();
+
+        assert_eq!(sink.written, b"acct_9m4e2mr0ui3e8a215n4g");
+    }
+
+    #[test]
+    fn forwards_sink_errors_on_encoded_bytes() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let mut sink = ShortWriter {
+            written: Vec::new(),
+            chunk: 4,
+            limit: 8,
+        };
+        let mut zero = ShortWriter {
+            written: Vec::new(),
+            chunk: 0,
+            limit: usize::MAX,
+        };
+
+        let err = id.write_encoded_bytes(&mut sink).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(sink.written, b"acct_9m4");
+        assert_eq!(
+            id.write_encoded_bytes(&mut zero).unwrap_err().kind(),
+            io::ErrorKind::WriteZero
+        );
+    }
+
+    #[test]
+    fn complains_writing_invalid_utf8_prefix() {
+        let mut bytes = [0_u8; BINARY_LENGTH];
+
+        bytes[0] = 0xff;
+
+        let id = Pxid::from(bytes);
+
+        assert!(id.write_encoded(&mut String::new()).is_err());
+        assert_eq!(
+            id.write_encoded_bytes(&mut Vec::new()).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(
+            id.try_to_string(),
+            Err(Error::InvalidBinaryPrefix([0xff, 0, 0, 0]))
+        );
+        assert_eq!(id.to_string(), "\u{fffd}\0\0\0_00000000000000000000");
+        assert_eq!(
+            Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g")
+                .unwrap()
+                .try_to_string(),
+            Ok(String::from("acct_9m4e2mr0ui3e8a215n4g"))
+        );
+    }
+
+    #[test]
+    fn displays_nil_with_nil_prefix() {
+        let nil = Pxid::nil();
+        let mut bytes = Vec::new();
+
+        nil.write_encoded_bytes(&mut bytes).unwrap();
+
+        assert!(nil.is_nil());
+        assert_eq!(nil, Pxid::default());
+        assert_eq!(nil.to_string(), "nil_00000000000000000000");
+        assert_eq!(
+            nil.to_string_with_separator('.'),
+            "nil.00000000000000000000"
+        );
+        assert_eq!(bytes, b"nil_00000000000000000000");
+        assert!(!Pxid::new("acct").unwrap().is_nil());
+    }
+
+    #[test]
+    fn decodes_nil_string_into_nil() {
+        assert_eq!(Pxid::from_str("nil_00000000000000000000"), Ok(Pxid::nil()));
+        assert_eq!(
+            Pxid::from_str_with_separator("nil.00000000000000000000", '.'),
+            Ok(Pxid::nil())
+        );
+    }
+
+    #[test]
+    fn validates_generated_ids() {
+        assert_eq!(Pxid::new("acct").unwrap().validate(), Ok(()));
+        assert_eq!(Pxid::new("usr").unwrap().validate(), Ok(()));
+        assert_eq!(
+            Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g")
+                .unwrap()
+                .validate(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn complains_validating_invalid_prefixes() {
+        let mut bytes = *Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        bytes[0] = 0xff;
+        assert!(matches!(
+            Pxid::from(bytes).validate(),
+            Err(Error::Decode(DecodeError::InvalidUtf8(_)))
+        ));
+
+        bytes[0] = b'-';
+        assert_eq!(
+            Pxid::from(bytes).validate(),
+            Err(Error::InvalidPrefixChar(
+                String::from("-cct_9m4e2mr0ui3e8a215n4g"),
+                '-'
+            ))
+        );
+
+        bytes[0..4].copy_from_slice(b"a\0ct");
+        assert_eq!(
+            Pxid::from(bytes).validate(),
+            Err(Error::InvalidPrefixChar(
+                String::from("a\0ct_9m4e2mr0ui3e8a215n4g"),
+                '\0'
+            ))
+        );
+
+        bytes[0..4].copy_from_slice(&[0; 4]);
+        assert!(matches!(
+            Pxid::from(bytes).validate(),
+            Err(Error::Decode(DecodeError::MissingPrefix(_)))
+        ));
+    }
+
+    #[test]
+    fn decodes_packed_bytes() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let short = Pxid::new("usr").unwrap();
+
+        assert_eq!(Pxid::from_packed(&id), Ok(id));
+        assert_eq!(Pxid::from_packed(&short), Ok(short));
+        assert_eq!(Pxid::from_packed(&[0; BINARY_LENGTH]), Ok(Pxid::nil()));
+    }
+
+    #[test]
+    fn complains_decoding_packed_bytes_with_invalid_prefix() {
+        let mut bytes = *Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        bytes[0..4].copy_from_slice(b"\0cct");
+        assert_eq!(
+            Pxid::from_packed(&bytes),
+            Err(Error::InvalidPrefixChar(
+                String::from("\0cct_9m4e2mr0ui3e8a215n4g"),
+                '\0'
+            ))
+        );
+
+        bytes[0..4].copy_from_slice(&[0xc3, 0x28, 0, 0]);
+        assert!(matches!(
+            Pxid::from_packed(&bytes),
+            Err(Error::Decode(DecodeError::InvalidUtf8(_)))
+        ));
+
+        bytes[0..4].copy_from_slice(&[0; 4]);
+        assert!(matches!(
+            Pxid::from_packed(&bytes),
+            Err(Error::Decode(DecodeError::MissingPrefix(_)))
+        ));
+    }
+
+    #[test]
+    fn complains_validating_zero_timestamp_and_machine_id() {
+        let mut bytes = *Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        bytes[4..8].copy_from_slice(&[0; 4]);
+        assert!(matches!(
+            Pxid::from(bytes).validate(),
+            Err(Error::ZeroTimestamp(_))
+        ));
+
+        bytes[4..8].copy_from_slice(&[1; 4]);
+        bytes[8..11].copy_from_slice(&[0; 3]);
+        assert!(matches!(
+            Pxid::from(bytes).validate(),
+            Err(Error::ZeroMachineId(_))
+        ));
+        assert!(Pxid::nil().validate().is_err());
+    }
+
+    #[test]
+    fn converts_to_and_from_i128() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(id.to_i128(), 0x6163_6374_4d88_e15b_60f4_86e4_2841_2dc9);
+        assert_eq!(Pxid::from_i128(id.to_i128()), id);
+        assert_eq!(Pxid::from_i128(0), Pxid::nil());
+    }
+
+    #[test]
+    fn i128_preserves_order_within_prefix() {
+        let earlier = Pxid::new_with_time("acct", 1_000).unwrap();
+        let later = Pxid::new_with_time("acct", 2_000).unwrap();
+
+        assert!(earlier.to_i128() < later.to_i128());
+        assert!(
+            Pxid::new_with_time("acct", 2_000).unwrap().to_i128()
+                < Pxid::new_with_time("ordr", 1_000).unwrap().to_i128()
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn new_unchecked_still_creates_ids() {
+        let id = Pxid::new_unchecked("acct");
+
+        assert_eq!(id.prefix().unwrap(), "acct");
+    }
+
+    #[test]
+    fn builds_from_unchecked_parts() {
+        let id = Pxid::from_parts_unchecked(
+            *b"acct",
+            0x4d88_e15b,
+            [0x60, 0xf4, 0x86],
+            0xe428,
+            0x0041_2dc9,
+        );
+
+        assert_eq!(id, Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap());
+        assert_eq!(
+            Pxid::from_parts_unchecked(
+                *b"acct",
+                0x4d88_e15b,
+                [0x60, 0xf4, 0x86],
+                0xe428,
+                0xff41_2dc9
+            ),
+            id
+        );
+        assert_eq!(
+            id,
+            Pxid::from_parts("acct", 0x4d88_e15b, [0x60, 0xf4, 0x86], 0xe428, 0x0041_2dc9).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Failed to create Pxid with prefix \"account\". Failed to decode into a XID. String cannot be decoded into a PXID instance. account length is not valid. Expected length 4, but received 7"
+    )]
+    fn new_or_panic_reports_error() {
+        Pxid::new_or_panic("account");
+    }
+
+    #[test]
+    fn normalizes_prefix_to_lowercase() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(Pxid::from_str("ACCT_9m4e2mr0ui3e8a215n4g"), Ok(id));
+        assert_eq!(Pxid::from_str("AcCt_9m4e2mr0ui3e8a215n4g"), Ok(id));
+        assert_eq!(
+            Pxid::from_str_with_separator("ACCT.9m4e2mr0ui3e8a215n4g", '.'),
+            Ok(id)
+        );
+        assert_eq!(
+            Pxid::from_str("ÑAB_9m4e2mr0ui3e8a215n4g")
+                .unwrap()
+                .prefix()
+                .unwrap(),
+            "Ñab"
+        );
+    }
+
+    #[test]
+    fn preserves_prefix_case_on_request() {
+        let id = Pxid::from_str_preserve_case("ACCT_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(id.prefix_bytes(), [0x41, 0x43, 0x43, 0x54]);
+        assert_eq!(id.to_string(), "ACCT_9m4e2mr0ui3e8a215n4g");
+    }
+
+    #[test]
+    fn expects_matching_prefix() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(
+            Pxid::expect_prefix("acct_9m4e2mr0ui3e8a215n4g", "acct"),
+            Ok(id)
+        );
+    }
+
+    #[test]
+    fn complains_on_unexpected_prefix() {
+        assert_eq!(
+            Pxid::expect_prefix("ordr_9m4e2mr0ui3e8a215n4g", "acct"),
+            Err(Error::PrefixMismatch {
+                expected: String::from("acct"),
+                found: String::from("ordr")
+            })
+        );
+        assert_eq!(
+            Pxid::expect_prefix("acct_9m4e2mr0ui3e8a215n4g", "acc"),
+            Err(Error::PrefixMismatch {
+                expected: String::from("acc"),
+                found: String::from("acct")
+            })
+        );
+        assert!(matches!(
+            Pxid::expect_prefix("acct_9m4e2mr0", "acct"),
+            Err(Error::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn display_ignores_precision() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(format!("{id:.3}"), "acct_9m4e2mr0ui3e8a215n4g");
+        assert_eq!(format!("{id:.10}"), "acct_9m4e2mr0ui3e8a215n4g");
+        assert_eq!(format!("{id:.25}"), "acct_9m4e2mr0ui3e8a215n4g");
+    }
+
+    #[test]
+    fn display_pads_whole_id() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(format!("{id:>30}"), "     acct_9m4e2mr0ui3e8a215n4g");
+        assert_eq!(format!("{id:30}"), "acct_9m4e2mr0ui3e8a215n4g     ");
+        assert_eq!(format!("{id:*^30}"), "**acct_9m4e2mr0ui3e8a215n4g***");
+        assert_eq!(format!("{id:>30.3}"), "     acct_9m4e2mr0ui3e8a215n4g");
+        assert_eq!(format!("{id:<27.10}|"), "acct_9m4e2mr0ui3e8a215n4g  |");
+        assert_eq!(format!("{id:>10}"), "acct_9m4e2mr0ui3e8a215n4g");
+        assert_eq!(format!("{id:·>27}"), "··acct_9m4e2mr0ui3e8a215n4g");
+        assert_eq!(format!("{:>25}", Pxid::nil()), " nil_00000000000000000000");
+    }
+
+    #[test]
+    fn display_full_ignores_flags() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let full = id.display_full();
+
+        for formatted in [
+            format!("{full}"),
+            format!("{full:.3}"),
+            format!("{full:.10}"),
+            format!("{full:>30}"),
+            format!("{full:*^30.5}"),
+            format!("{full:<40}"),
+        ] {
+            assert_eq!(formatted, "acct_9m4e2mr0ui3e8a215n4g");
+        }
+    }
+
+    #[test]
+    fn parses_quoted_ids() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(
+            Pxid::parse_unquoted("\"acct_9m4e2mr0ui3e8a215n4g\""),
+            Ok(id)
+        );
+        assert_eq!(Pxid::parse_unquoted("acct_9m4e2mr0ui3e8a215n4g"), Ok(id));
+        assert!(Pxid::parse_unquoted("\"\"acct_9m4e2mr0ui3e8a215n4g\"\"").is_err());
+        assert!(Pxid::parse_unquoted("\"acct_9m4e2mr0ui3e8a215n4g").is_err());
+        assert!(Pxid::from_str("\"acct_9m4e2mr0ui3e8a215n4g\"").is_err());
+    }
+
+    #[test]
+    fn complains_on_mismatching_separator() {
+        assert_eq!(
+            Pxid::from_str_with_separator("acct_9m4e2mr0ui3e8a215n4g", '.'),
+            Err(Error::Decode(DecodeError::MissingPrefix(
+                "acct_9m4e2mr0ui3e8a215n4g".into()
+            )))
+        );
+    }
+
+    #[test]
+    fn encodes_and_decodes_with_checksum() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let encoded = id.to_string_with_checksum();
+
+        assert_eq!(encoded.len(), CHECKSUM_ENCODED_LENGTH);
+        assert!(encoded.starts_with("acct_9m4e2mr0ui3e8a215n4g"));
+        assert_eq!(Pxid::from_str_with_checksum(&encoded).unwrap(), id);
+    }
+
+    #[test]
+    fn complains_on_single_character_typo_with_checksum() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let encoded = id.to_string_with_checksum();
+        let check = encoded.chars().last().unwrap();
+        let corrupted = encoded.replacen("ui3", "uj3", 1);
+
+        let expected = crate::checksum::generate(b"acct_", b"9m4e2mr0uj3e8a215n4g").unwrap();
+
+        assert_eq!(
+            Pxid::from_str_with_checksum(&corrupted),
+            Err(Error::Decode(DecodeError::InvalidChecksum(
+                corrupted.clone(),
+                expected as char
+            )))
+        );
+        assert_ne!(expected as char, check);
+    }
+
+    #[test]
+    fn complains_on_prefix_typo_with_checksum() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let encoded = id.to_string_with_checksum();
+        let corrupted = encoded.replacen("acct", "acst", 1);
+
+        assert!(matches!(
+            Pxid::from_str_with_checksum(&corrupted),
+            Err(Error::Decode(DecodeError::InvalidChecksum(..)))
+        ));
+        assert_eq!(
+            Pxid::from_str_with_checksum(&encoded.replacen("acct", "ACCT", 1)),
+            Ok(id)
+        );
+
+        for id in [Pxid::new("a").unwrap(), Pxid::nil()] {
+            assert_eq!(
+                Pxid::from_str_with_checksum(&id.to_string_with_checksum()),
+                Ok(id)
+            );
+        }
+    }
+
+    #[test]
+    fn encodes_xid_as_ulid() {
+        let id = Pxid::from_parts_unchecked(*b"acct", 0x0102_0304, [5, 6, 7], 0x0809, 0x0a_0b0c);
+
+        assert_eq!(id.to_ulid_string(), "00000000820C20A1G7104GM2RC");
+        assert_eq!(
+            Pxid::nil().to_ulid_string(),
+            "0".repeat(ULID_ENCODED_LENGTH)
+        );
+    }
+
+    #[test]
+    fn decodes_xid_from_ulid() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let ulid = id.to_ulid_string();
+
+        assert_eq!(ulid.len(), ULID_ENCODED_LENGTH);
+        assert_eq!(Pxid::from_ulid_string("acct", &ulid), Ok(id));
+        assert_eq!(
+            Pxid::from_ulid_string("acct", &ulid.to_ascii_lowercase()),
+            Ok(id)
+        );
+    }
+
+    #[test]
+    fn preserves_order_on_ulid_encoding() {
+        let older = Pxid::from_parts_unchecked(*b"acct", 100, [0xff; 3], 0xffff, 0xff_ffff);
+        let newer = Pxid::from_parts_unchecked(*b"acct", 101, [0; 3], 0, 0);
+
+        assert!(older.to_ulid_string() < newer.to_ulid_string());
+    }
+
+    #[test]
+    fn complains_on_invalid_ulid() {
+        assert_eq!(
+            Pxid::from_ulid_string("acct", "00000000820C20A1G7104GM2R"),
+            Err(Error::Decode(DecodeError::InvalidLength(
+                String::from("00000000820C20A1G7104GM2R"),
+                25
+            )))
+        );
+        assert_eq!(
+            Pxid::from_ulid_string("acct", "00000000820C20A1G7104GM2RU"),
+            Err(Error::Decode(DecodeError::InvalidChar(
+                String::from("00000000820C20A1G7104GM2RU"),
+                'U',
+                25
+            )))
+        );
+        assert_eq!(
+            Pxid::from_ulid_string("acct", "01ARZ3NDEKTSV4RRFFQ69G5FAV"),
+            Err(Error::Decode(DecodeError::InvalidUlid(String::from(
+                "01ARZ3NDEKTSV4RRFFQ69G5FAV"
+            ))))
+        );
+        assert_eq!(
+            Pxid::from_ulid_string("accounts", "00000000820C20A1G7104GM2RC"),
+            Err(Error::Decode(DecodeError::InvalidPrefixLength(
+                String::from("accounts"),
+                8
+            )))
+        );
     }
 
     #[test]
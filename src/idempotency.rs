@@ -0,0 +1,313 @@
+//! Idempotency keys for retried requests, such as `POST` endpoints, built on
+//! IDs generated by clients.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use pxid::{IdempotencyError, IdempotencyKey};
+//!
+//! # let header = "idem_9m4e2mr0ui3e8a215n4g";
+//! match IdempotencyKey::parse(header, "idem", Duration::from_secs(86_400)) {
+//!     Ok(key) => println!("storing response under {key}"),
+//!     Err(IdempotencyError::Malformed(_) | IdempotencyError::WrongPrefix(..)) => {
+//!         // 400 Bad Request
+//!     }
+//!     Err(IdempotencyError::TooOld(..) | IdempotencyError::FutureDated(_)) => {
+//!         // 422 Unprocessable Entity
+//!     }
+//! }
+//! ```
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use thiserror::Error;
+
+use crate::error::Error;
+use crate::id::Pxid;
+use crate::wrapper::check_prefix;
+
+/// Reason an `IdempotencyKey` is rejected, so each one can be answered with
+/// a different status code
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum IdempotencyError {
+    /// Key can't be decoded into a Pxid
+    #[error("Malformed idempotency key. {0}")]
+    Malformed(Error),
+
+    /// Key prefix doesn't match the expected one, holds the expected and
+    /// the received prefixes
+    #[error("Expected idempotency key prefix: {0}, but received {1}.")]
+    WrongPrefix(String, String),
+
+    /// Key Timestamp is behind the current time by more than the max age,
+    /// holds the age of the key and the max age
+    #[error("Idempotency key is {}s old, max allowed age is {}s.", .0.as_secs(), .1.as_secs())]
+    TooOld(Duration, Duration),
+
+    /// Key Timestamp is ahead of the current time, holds how far ahead
+    #[error("Idempotency key is {}s ahead of the current time.", .0.as_secs())]
+    FutureDated(Duration),
+}
+
+/// Pxid accepted as an idempotency key: well-formed, holding the expected
+/// prefix, and generated within the max age.
+///
+/// Keys deserialized through `serde` are only checked to be well-formed,
+/// given the expected prefix and max age are not known there.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey(Pxid);
+
+impl IdempotencyKey {
+    /// Decodes `s` into an `IdempotencyKey` holding `expected_prefix`, whose
+    /// Timestamp is at most `max_age` behind the current time.
+    ///
+    /// Keys exactly `max_age` old are accepted, keys with a Timestamp ahead
+    /// of the current time are rejected.
+    pub fn parse(
+        s: &str,
+        expected_prefix: &str,
+        max_age: Duration,
+    ) -> Result<Self, IdempotencyError> {
+        Self::parse_at(s, expected_prefix, max_age, SystemTime::now())
+    }
+
+    /// Decodes `s` as done by `IdempotencyKey::parse`, using `now` as the
+    /// current time
+    pub fn parse_at(
+        s: &str,
+        expected_prefix: &str,
+        max_age: Duration,
+        now: SystemTime,
+    ) -> Result<Self, IdempotencyError> {
+        let id = Pxid::from_str(s).map_err(IdempotencyError::Malformed)?;
+
+        check_prefix(&id, expected_prefix).map_err(|err| match err {
+            Error::PrefixMismatch { expected, found } => {
+                IdempotencyError::WrongPrefix(expected, found)
+            }
+            err => IdempotencyError::Malformed(err),
+        })?;
+
+        match now.duration_since(id.timestamp()) {
+            Ok(age) if age > max_age => Err(IdempotencyError::TooOld(age, max_age)),
+            Ok(_) => Ok(Self(id)),
+            Err(err) => Err(IdempotencyError::FutureDated(err.duration())),
+        }
+    }
+
+    /// Retrieves the wrapped `Pxid`
+    #[inline]
+    pub fn id(&self) -> Pxid {
+        self.0
+    }
+
+    /// Retrieves the time elapsed since the key Timestamp
+    pub fn age(&self) -> Duration {
+        self.age_at(SystemTime::now())
+    }
+
+    /// Retrieves the time elapsed between the key Timestamp and `now`, zero
+    /// if `now` is before it
+    pub fn age_at(&self, now: SystemTime) -> Duration {
+        now.duration_since(self.0.timestamp()).unwrap_or_default()
+    }
+
+    /// Retrieves the index of the `granularity` long window holding the key
+    /// Timestamp, counted from the Unix epoch, to shard key storage by time.
+    ///
+    /// Granularities are truncated to whole seconds, and those below one
+    /// second are treated as one second.
+    ///
+    /// ```text
+    /// Timestamp 1700000000 with 1h granularity -> bucket 472222
+    /// ```
+    pub fn bucket(&self, granularity: Duration) -> u64 {
+        let secs = self
+            .0
+            .timestamp()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        secs / granularity.as_secs().max(1)
+    }
+}
+
+impl From<IdempotencyKey> for Pxid {
+    fn from(value: IdempotencyKey) -> Self {
+        value.0
+    }
+}
+
+impl Display for IdempotencyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for IdempotencyKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for IdempotencyKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Pxid::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::DecodeError;
+    use crate::factory::Factory;
+
+    use super::*;
+
+    const NOW: u32 = 1_700_000_000;
+
+    const MAX_AGE: Duration = Duration::from_hours(1);
+
+    fn now() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(u64::from(NOW))
+    }
+
+    fn key_at(offset: i64) -> String {
+        let time = u32::try_from(i64::from(NOW) + offset).unwrap();
+
+        Factory::deterministic(7)
+            .new_with_time("idem", time)
+            .unwrap()
+            .to_string()
+    }
+
+    fn parse(s: &str) -> Result<IdempotencyKey, IdempotencyError> {
+        IdempotencyKey::parse_at(s, "idem", MAX_AGE, now())
+    }
+
+    #[test]
+    fn accepts_keys_within_max_age() {
+        for offset in [0, -1, -3_599, -3_600] {
+            let encoded = key_at(offset);
+            let key = parse(&encoded).unwrap();
+
+            assert_eq!(key.to_string(), encoded);
+            assert_eq!(key.id(), Pxid::from_str(&encoded).unwrap());
+            assert_eq!(Pxid::from(key), key.id());
+            assert_eq!(
+                key.age_at(now()),
+                Duration::from_secs(offset.unsigned_abs())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_keys_older_than_max_age() {
+        assert_eq!(
+            parse(&key_at(-3_601)),
+            Err(IdempotencyError::TooOld(
+                Duration::from_secs(3_601),
+                MAX_AGE
+            ))
+        );
+        assert_eq!(
+            IdempotencyKey::parse_at(&key_at(-1), "idem", Duration::ZERO, now()),
+            Err(IdempotencyError::TooOld(
+                Duration::from_secs(1),
+                Duration::ZERO
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_future_dated_keys() {
+        assert_eq!(
+            parse(&key_at(1)),
+            Err(IdempotencyError::FutureDated(Duration::from_secs(1)))
+        );
+        assert_eq!(
+            IdempotencyKey::parse_at(
+                &key_at(0),
+                "idem",
+                MAX_AGE,
+                now() - Duration::from_millis(1)
+            ),
+            Err(IdempotencyError::FutureDated(Duration::from_millis(1)))
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_prefixes() {
+        let encoded = Factory::deterministic(7)
+            .new_with_time("ordr", NOW)
+            .unwrap()
+            .to_string();
+
+        assert_eq!(
+            parse(&encoded),
+            Err(IdempotencyError::WrongPrefix(
+                String::from("idem"),
+                String::from("ordr")
+            ))
+        );
+        assert_eq!(
+            IdempotencyKey::parse_at(&key_at(0), "id", MAX_AGE, now()),
+            Err(IdempotencyError::WrongPrefix(
+                String::from("id"),
+                String::from("idem")
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        assert_eq!(
+            parse("idem"),
+            Err(IdempotencyError::Malformed(Error::Decode(
+                DecodeError::InvalidLength(String::from("idem"), 4)
+            )))
+        );
+        assert!(matches!(
+            parse("idem_9m4e2mr0ui3e8a215n4!"),
+            Err(IdempotencyError::Malformed(Error::Decode(
+                DecodeError::InvalidChar(..)
+            )))
+        ));
+    }
+
+    #[test]
+    fn buckets_by_granularity() {
+        let key = parse(&key_at(0)).unwrap();
+
+        assert_eq!(key.bucket(Duration::from_hours(1)), 472_222);
+        assert_eq!(key.bucket(Duration::from_secs(1)), u64::from(NOW));
+        assert_eq!(key.bucket(Duration::ZERO), u64::from(NOW));
+        assert_eq!(key.bucket(Duration::from_millis(2_500)), u64::from(NOW) / 2);
+        assert_eq!(
+            parse(&key_at(-3_600))
+                .unwrap()
+                .bucket(Duration::from_hours(1)),
+            472_221
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn round_trips_through_serde() {
+        let key = parse(&key_at(0)).unwrap();
+        let json = serde_json::to_string(&key).unwrap();
+
+        assert_eq!(json, serde_json::to_string(&key.id()).unwrap());
+        assert_eq!(serde_json::from_str::<IdempotencyKey>(&json).unwrap(), key);
+        assert!(serde_json::from_str::<IdempotencyKey>("\"idem\"").is_err());
+    }
+}
@@ -0,0 +1,383 @@
+//! Interning of encoded IDs, so long-lived caches share a single encoded
+//! string per ID instead of keeping a copy on each entry.
+//!
+//! ```no_run
+//! use pxid::intern::StringPool;
+//!
+//! # let id = pxid::Pxid::nil();
+//! let pool = StringPool::with_capacity(100_000);
+//! let encoded = pool.get(&id); // Encodes `id` on first access
+//! let shared = pool.get(&id); // Shares the same `Arc<str>`
+//! ```
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::id::Pxid;
+
+/// Max amount of shards, each guarded by its own lock
+const SHARD_COUNT: usize = 16;
+
+/// Encoded ID along with the tick of its last access
+struct Entry {
+    value: Arc<str>,
+    tick: u64,
+}
+
+/// Partition of the pool guarded by a single lock. Bounded shards keep
+/// their entries ordered by last access to evict the least recently used.
+struct Shard {
+    entries: HashMap<Pxid, Entry>,
+    order: BTreeMap<u64, Pxid>,
+    capacity: Option<usize>,
+    tick: u64,
+}
+
+impl Shard {
+    fn new(capacity: Option<usize>) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            capacity,
+            tick: 0,
+        }
+    }
+
+    /// Retrieves the encoded `id` if present, marking it as the most
+    /// recently used
+    fn get(&mut self, id: &Pxid) -> Option<Arc<str>> {
+        self.tick += 1;
+
+        let entry = self.entries.get_mut(id)?;
+
+        if self.capacity.is_some() {
+            self.order.remove(&entry.tick);
+            self.order.insert(self.tick, *id);
+        }
+
+        entry.tick = self.tick;
+        Some(Arc::clone(&entry.value))
+    }
+
+    /// Inserts the encoded `id`, evicting the least recently used entries
+    /// to stay within capacity, and retrieves the amount of evicted entries
+    fn insert(&mut self, id: Pxid, value: Arc<str>) -> u64 {
+        let mut evicted = 0;
+
+        if let Some(capacity) = self.capacity {
+            if capacity == 0 {
+                return 0;
+            }
+
+            while self.entries.len() >= capacity {
+                let Some((_, oldest)) = self.order.pop_first() else {
+                    break;
+                };
+
+                self.entries.remove(&oldest);
+                evicted += 1;
+            }
+
+            self.order.insert(self.tick, id);
+        }
+
+        self.entries.insert(
+            id,
+            Entry {
+                value,
+                tick: self.tick,
+            },
+        );
+
+        evicted
+    }
+}
+
+/// Statistics of a `StringPool`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Lookups served with an already encoded ID
+    pub hits: u64,
+
+    /// Lookups which had to encode the ID
+    pub misses: u64,
+
+    /// Entries evicted to stay within capacity
+    pub evictions: u64,
+
+    /// Estimate of the bytes saved, the length of the encoded IDs shared on
+    /// hits instead of allocating a new copy
+    pub bytes_saved: u64,
+}
+
+/// Thread-safe pool mapping IDs to their shared encoded form, as written by
+/// `Display`.
+///
+/// Entries are partitioned into shards guarded by their own lock, so
+/// concurrent lookups of different IDs rarely contend. Bounded pools split
+/// their capacity among shards and evict the least recently used entry of
+/// a shard when it is full.
+pub struct StringPool {
+    shards: Vec<Mutex<Shard>>,
+    capacity: Option<usize>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    bytes_saved: AtomicU64,
+}
+
+impl Default for StringPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StringPool {
+    /// Creates an unbounded `StringPool`, entries are never evicted
+    pub fn new() -> Self {
+        Self::with_shards((0..SHARD_COUNT).map(|_| Shard::new(None)).collect(), None)
+    }
+
+    /// Creates a `StringPool` holding at most `capacity` entries, evicting
+    /// the least recently used ones when full
+    pub fn with_capacity(capacity: usize) -> Self {
+        let shard_count = capacity.clamp(1, SHARD_COUNT);
+        let shards = (0..shard_count)
+            .map(|idx| {
+                let extra = usize::from(idx < capacity % shard_count);
+
+                Shard::new(Some(capacity / shard_count + extra))
+            })
+            .collect();
+
+        Self::with_shards(shards, Some(capacity))
+    }
+
+    fn with_shards(shards: Vec<Shard>, capacity: Option<usize>) -> Self {
+        Self {
+            shards: shards.into_iter().map(Mutex::new).collect(),
+            capacity,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            bytes_saved: AtomicU64::new(0),
+        }
+    }
+
+    /// Retrieves the encoded form of `id`, encoding it on first access and
+    /// sharing the same `Arc<str>` on following ones while it stays in the
+    /// pool
+    // Shards are bounded by `SHARD_COUNT` so their amount fits in a `u32`
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn get(&self, id: &Pxid) -> Arc<str> {
+        let Some(shard) = self.shards.get(id.shard(self.shards.len() as u32) as usize) else {
+            return Arc::from(id.to_string());
+        };
+        let mut shard = shard.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(value) = shard.get(id) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            self.bytes_saved
+                .fetch_add(value.len() as u64, Ordering::Relaxed);
+
+            return value;
+        }
+
+        let value: Arc<str> = Arc::from(id.to_string());
+        let evicted = shard.insert(*id, Arc::clone(&value));
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        self.evictions.fetch_add(evicted, Ordering::Relaxed);
+        value
+    }
+
+    /// Retrieves the max amount of entries, `None` for unbounded pools
+    #[inline]
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Retrieves the amount of entries in the pool
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| {
+                shard
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .entries
+                    .len()
+            })
+            .sum()
+    }
+
+    /// Checks whether the pool holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retrieves the statistics gathered since the pool was created
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            bytes_saved: self.bytes_saved.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use rand::{Rng, RngCore};
+
+    use super::*;
+
+    fn random_ids(count: usize) -> Vec<Pxid> {
+        let mut rng = rand::thread_rng();
+
+        (0..count)
+            .map(|idx| match idx % 3 {
+                0 => Pxid::new("acct").unwrap(),
+                1 => Pxid::new("dog").unwrap(),
+                _ => {
+                    let mut bytes = [0; 16];
+
+                    rng.fill_bytes(&mut bytes);
+                    Pxid::from(bytes)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shares_encoded_ids() {
+        let pool = StringPool::new();
+        let id = Pxid::new("acct").unwrap();
+        let first = pool.get(&id);
+        let second = pool.get(&id);
+
+        assert_eq!(&*first, id.to_string());
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.capacity(), None);
+        assert_eq!(
+            pool.stats(),
+            PoolStats {
+                hits: 1,
+                misses: 1,
+                evictions: 0,
+                bytes_saved: 25,
+            }
+        );
+    }
+
+    #[test]
+    fn always_retrieves_the_encoded_id() {
+        let ids = random_ids(300);
+        let mut rng = rand::thread_rng();
+
+        for pool in [
+            StringPool::new(),
+            StringPool::with_capacity(0),
+            StringPool::with_capacity(7),
+            StringPool::with_capacity(100),
+        ] {
+            for _ in 0..5_000 {
+                let id = ids[rng.gen_range(0..ids.len())];
+
+                assert_eq!(&*pool.get(&id), id.to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_capacity_bounds() {
+        let ids = random_ids(1_000);
+
+        for capacity in [0, 1, 5, 16, 17, 100] {
+            let pool = StringPool::with_capacity(capacity);
+
+            for id in &ids {
+                pool.get(id);
+                assert!(pool.len() <= capacity);
+            }
+
+            let stats = pool.stats();
+
+            assert_eq!(pool.capacity(), Some(capacity));
+            assert_eq!(stats.misses, 1_000);
+
+            if capacity == 0 {
+                assert_eq!(stats.evictions, 0);
+            } else {
+                assert_eq!(stats.evictions, stats.misses - pool.len() as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used_entries() {
+        let mut shard = Shard::new(Some(2));
+        let ids = random_ids(3);
+        let values = ids
+            .iter()
+            .map(|id| Arc::from(id.to_string()))
+            .collect::<Vec<Arc<str>>>();
+
+        assert_eq!(shard.get(&ids[0]), None);
+        assert_eq!(shard.insert(ids[0], Arc::clone(&values[0])), 0);
+        assert_eq!(shard.get(&ids[1]), None);
+        assert_eq!(shard.insert(ids[1], Arc::clone(&values[1])), 0);
+        assert_eq!(shard.get(&ids[0]), Some(Arc::clone(&values[0])));
+        assert_eq!(shard.get(&ids[2]), None);
+        assert_eq!(shard.insert(ids[2], Arc::clone(&values[2])), 1);
+
+        assert!(shard.entries.contains_key(&ids[0]));
+        assert!(!shard.entries.contains_key(&ids[1]));
+        assert!(shard.entries.contains_key(&ids[2]));
+        assert_eq!(shard.order.len(), 2);
+    }
+
+    #[test]
+    fn serves_concurrent_lookups() {
+        let ids = random_ids(500);
+        let pool = StringPool::with_capacity(200);
+        let unbounded = StringPool::new();
+
+        thread::scope(|scope| {
+            for thread in 0..8 {
+                let ids = &ids;
+                let pool = &pool;
+                let unbounded = &unbounded;
+
+                scope.spawn(move || {
+                    for round in 0..20 {
+                        for id in ids.iter().skip(thread * 50).take(150 + round) {
+                            assert_eq!(&*pool.get(id), id.to_string());
+                            assert_eq!(&*unbounded.get(id), id.to_string());
+                        }
+                    }
+                });
+            }
+        });
+
+        let stats = pool.stats();
+        let lookups = (0..8)
+            .map(|thread| {
+                (0..20)
+                    .map(|round| (150 + round).min(500 - thread * 50))
+                    .sum::<usize>()
+            })
+            .sum::<usize>() as u64;
+
+        assert!(pool.len() <= 200);
+        assert_eq!(stats.hits + stats.misses, lookups);
+        assert_eq!(stats.evictions, stats.misses - pool.len() as u64);
+        assert_eq!(unbounded.len(), 500);
+        assert_eq!(unbounded.stats().misses, 500);
+        assert_eq!(unbounded.stats().evictions, 0);
+    }
+}
@@ -0,0 +1,380 @@
+//! Framing helpers to read and write flat binary files of raw Pxid records.
+//!
+//! Each record is the 16 bytes of a `Pxid` written back to back, optionally
+//! preceded by the 8 bytes `MAGIC` header.
+//!
+//! ```text
+//! P X I D 0 0 0 1 | V V V V W W W W X X X Y Y Z Z Z | ...
+//! └─────────────┘   └─────────────────────────────┘
+//!        |                        |
+//!  Magic Header (Optional)      Record
+//! ```
+use std::io::{self, ErrorKind, Read, Write};
+use std::str::from_utf8;
+
+use thiserror::Error;
+
+use crate::id::{is_canonical_prefix, Bytes, Pxid, BINARY_LENGTH, PREFIX_LENGTH};
+
+/// Magic header written at the start of a framed file
+pub const MAGIC: &[u8; 8] = b"PXID0001";
+
+/// Errors specific to the Pxid binary record framing. These are carried as
+/// the inner error of `io::Error` instances and can be retrieved using
+/// `io::Error::get_ref` and `downcast_ref`.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum RecordError {
+    /// The source ended in the middle of a record
+    #[error("Truncated record found after {0} records. Expected {BINARY_LENGTH} bytes, but received {1}")]
+    Truncated(u64, usize),
+
+    /// The record prefix region doesn't hold a valid prefix
+    #[error("Record {0} has an invalid prefix region {1:?}")]
+    InvalidPrefix(u64, [u8; PREFIX_LENGTH]),
+
+    /// The source doesn't start with the expected `MAGIC` header
+    #[error("Invalid header found {0:?}")]
+    HeaderMismatch(Vec<u8>),
+}
+
+impl From<RecordError> for io::Error {
+    fn from(value: RecordError) -> Self {
+        let kind = match value {
+            RecordError::Truncated(..) => ErrorKind::UnexpectedEof,
+            RecordError::InvalidPrefix(..) | RecordError::HeaderMismatch(..) => {
+                ErrorKind::InvalidData
+            }
+        };
+
+        io::Error::new(kind, value)
+    }
+}
+
+/// Validation performed on each record read
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Validation {
+    /// Checks the prefix region of every record holds a non-empty UTF-8
+    /// prefix padded with trailing NUL bytes
+    #[default]
+    Strict,
+
+    /// Records are returned as read, without checking the prefix region
+    Trusted,
+}
+
+/// Writes every Pxid in `ids` as a raw 16 bytes record into `w`.
+///
+/// Returns the amount of bytes written.
+pub fn write_all<W: Write>(ids: &[Pxid], mut w: W) -> io::Result<u64> {
+    for id in ids {
+        w.write_all(&id.0)?;
+    }
+
+    w.flush()?;
+
+    Ok((ids.len() * BINARY_LENGTH) as u64)
+}
+
+/// Writes the `MAGIC` header followed by every Pxid in `ids` as raw 16 bytes
+/// records into `w`.
+///
+/// Returns the amount of bytes written, including the header.
+pub fn write_all_with_header<W: Write>(ids: &[Pxid], mut w: W) -> io::Result<u64> {
+    w.write_all(MAGIC)?;
+
+    Ok(MAGIC.len() as u64 + write_all(ids, w)?)
+}
+
+/// Reads Pxid records out of a `Read` source handling partial reads.
+///
+/// Iteration stops after the first truncated record or header mismatch,
+/// records with an invalid prefix region yield an error but iteration
+/// continues with the following record.
+pub struct Reader<R: Read> {
+    inner: R,
+    validation: Validation,
+    header: bool,
+    records_read: u64,
+    started: bool,
+    done: bool,
+}
+
+impl<R: Read> Reader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            validation: Validation::default(),
+            header: false,
+            records_read: 0,
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Sets the `Validation` performed on each record
+    #[must_use]
+    pub fn validation(mut self, validation: Validation) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Expects the source to start with the `MAGIC` header
+    #[must_use]
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Retrieves the amount of complete records read from the source
+    #[inline]
+    pub fn records_read(&self) -> u64 {
+        self.records_read
+    }
+
+    /// Retrieves the inner `Read` source
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Fills `buf` retrying on interrupted and partial reads.
+    ///
+    /// Returns the amount of bytes read, which is less than `buf.len()` only
+    /// if the source reached its end.
+    fn fill(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut filled = 0;
+
+        while let Some(rest) = buf.get_mut(filled..).filter(|rest| !rest.is_empty()) {
+            match self.inner.read(rest) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(filled)
+    }
+
+    fn read_header(&mut self) -> io::Result<()> {
+        let mut magic = [0_u8; 8];
+        let filled = self.fill(&mut magic)?;
+        let magic = magic.get(..filled).unwrap_or_default();
+
+        if magic != MAGIC {
+            return Err(RecordError::HeaderMismatch(magic.to_vec()).into());
+        }
+
+        Ok(())
+    }
+
+    fn read_record(&mut self) -> Option<io::Result<Pxid>> {
+        let mut bytes: Bytes = [0; BINARY_LENGTH];
+
+        match self.fill(&mut bytes) {
+            Ok(0) => None,
+            Ok(n) if n < BINARY_LENGTH => {
+                self.done = true;
+                Some(Err(RecordError::Truncated(self.records_read, n).into()))
+            }
+            Ok(_) => {
+                let index = self.records_read;
+                let id = Pxid::from(bytes);
+
+                self.records_read += 1;
+
+                if self.validation == Validation::Strict && !is_valid_prefix(id.prefix_bytes()) {
+                    return Some(Err(
+                        RecordError::InvalidPrefix(index, id.prefix_bytes()).into()
+                    ));
+                }
+
+                Some(Ok(id))
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for Reader<R> {
+    type Item = io::Result<Pxid>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if !self.started {
+            self.started = true;
+
+            if self.header {
+                if let Err(err) = self.read_header() {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+
+        let record = self.read_record();
+
+        if record.is_none() {
+            self.done = true;
+        }
+
+        record
+    }
+}
+
+/// Checks the prefix region holds a non-empty UTF-8 prefix where NUL bytes
+/// are only used as trailing padding
+fn is_valid_prefix(prefix: [u8; PREFIX_LENGTH]) -> bool {
+    is_canonical_prefix(prefix)
+        && from_utf8(&prefix)
+            .is_ok_and(|value| !value.trim_end_matches('\0').chars().any(char::is_control))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::Factory;
+
+    use super::*;
+
+    /// Source which returns at most 3 bytes on each read to simulate
+    /// partial reads
+    struct Trickle<R: Read>(R);
+
+    impl<R: Read> Read for Trickle<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(3);
+
+            self.0.read(&mut buf[..len])
+        }
+    }
+
+    fn make_ids(count: usize) -> Vec<Pxid> {
+        let factory = Factory::new().unwrap();
+
+        (0..count)
+            .map(|_| factory.new_id("acct").unwrap())
+            .collect()
+    }
+
+    fn record_error(err: &io::Error) -> &RecordError {
+        err.get_ref()
+            .and_then(|inner| inner.downcast_ref::<RecordError>())
+            .expect("Expected a RecordError")
+    }
+
+    #[test]
+    fn round_trips_large_vectors() {
+        let ids = make_ids(100_000);
+        let mut buf = Vec::new();
+
+        let written = write_all(&ids, &mut buf).unwrap();
+        assert_eq!(written, (ids.len() * BINARY_LENGTH) as u64);
+
+        let mut reader = Reader::new(Trickle(Cursor::new(buf)));
+        let read = reader.by_ref().collect::<io::Result<Vec<Pxid>>>().unwrap();
+
+        assert_eq!(read, ids);
+        assert_eq!(reader.records_read(), ids.len() as u64);
+    }
+
+    #[test]
+    fn round_trips_with_header() {
+        let ids = make_ids(1_000);
+        let mut buf = Vec::new();
+
+        let written = write_all_with_header(&ids, &mut buf).unwrap();
+        assert_eq!(written, (MAGIC.len() + ids.len() * BINARY_LENGTH) as u64);
+
+        let read = Reader::new(Cursor::new(buf))
+            .header(true)
+            .collect::<io::Result<Vec<Pxid>>>()
+            .unwrap();
+
+        assert_eq!(read, ids);
+    }
+
+    #[test]
+    fn complains_on_truncated_last_record() {
+        let ids = make_ids(3);
+        let mut buf = Vec::new();
+
+        write_all(&ids, &mut buf).unwrap();
+        buf.truncate(buf.len() - 5);
+
+        let mut reader = Reader::new(Trickle(Cursor::new(buf)));
+
+        assert_eq!(reader.next().unwrap().unwrap(), ids[0]);
+        assert_eq!(reader.next().unwrap().unwrap(), ids[1]);
+
+        let err = reader.next().unwrap().unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(record_error(&err), &RecordError::Truncated(2, 11));
+        assert!(reader.next().is_none());
+        assert_eq!(reader.records_read(), 2);
+    }
+
+    #[test]
+    fn complains_on_corrupt_prefix_when_strict() {
+        let ids = make_ids(2);
+        let mut buf = Vec::new();
+
+        write_all(&ids, &mut buf).unwrap();
+        buf[0] = 0;
+        buf[1] = 0xff;
+
+        let mut reader = Reader::new(Cursor::new(buf)).validation(Validation::Strict);
+        let err = reader.next().unwrap().unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            record_error(&err),
+            &RecordError::InvalidPrefix(0, [0, 0xff, b'c', b't'])
+        );
+        assert_eq!(reader.next().unwrap().unwrap(), ids[1]);
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn accepts_corrupt_prefix_when_trusted() {
+        let ids = make_ids(2);
+        let mut buf = Vec::new();
+
+        write_all(&ids, &mut buf).unwrap();
+        buf[0] = 0;
+        buf[1] = 0xff;
+
+        let read = Reader::new(Cursor::new(buf))
+            .validation(Validation::Trusted)
+            .collect::<io::Result<Vec<Pxid>>>()
+            .unwrap();
+
+        assert_eq!(read[0].prefix_bytes(), [0, 0xff, b'c', b't']);
+        assert_eq!(read[1], ids[1]);
+    }
+
+    #[test]
+    fn complains_on_header_mismatch() {
+        let ids = make_ids(2);
+        let mut buf = b"PXID0002".to_vec();
+
+        write_all(&ids, &mut buf).unwrap();
+
+        let mut reader = Reader::new(Cursor::new(buf)).header(true);
+        let err = reader.next().unwrap().unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            record_error(&err),
+            &RecordError::HeaderMismatch(b"PXID0002".to_vec())
+        );
+        assert!(reader.next().is_none());
+    }
+}
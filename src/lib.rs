@@ -13,7 +13,7 @@
 //!
 //! ## Usage
 //!
-//! ```ignore
+//! ```no_run
 //! use pxid::Pxid;
 //!
 //! fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -24,9 +24,11 @@
 //!     // - Getting process id
 //!     //
 //!     // A `Result<Pxid, Error>` is returned.
-//!     let id = Pxid::new("acct".as_bytes())?;
+//!     let id = Pxid::new("acct")?;
 //!
-//!     println!("{}", id); //! acct_9m4e2mr0ui3e8a215n4g
+//!     println!("{}", id); // acct_9m4e2mr0ui3e8a215n4g
+//!
+//!     Ok(())
 //! }
 //! ```
 //!
@@ -36,22 +38,56 @@
 //! This is the recommended way to build `Pxid` instances, given that resources are
 //! initialized once, and then reused.
 //!
-//! ```ignore
+//! ```no_run
 //! use pxid::Factory;
 //!
 //! fn main() -> Result<(), Box<dyn std::error::Error>> {
-//!     let factory = Factory::new_without_prefix()?;
-//!     let id = factory.with_prefix("acct");
-//!
-//!     println!("{}", id); //! acct_9m4e2mr0ui3e8a215n4g
+//!     let factory = Factory::new()?;
+//!     let id = factory.new_id("acct")?;
 //!
-//!     let factory_with_prefix = Factory::new("acct")?;
-//!     let id = factory_with_prefix.generate();
+//!     println!("{}", id); // acct_9m4e2mr0ui3e8a215n4g
 //!
-//!     println!("{}", id); //! acct_9m4e2mr0ui3e8a215n4g
+//!     Ok(())
 //! }
 //! ```
 //!
+//! Newtypes around `Pxid` can derive their boilerplate with `PxidWrapper`
+//! when the `derive` feature is enabled.
+//!
+//! ```no_run
+//! # #[cfg(feature = "derive")]
+//! # fn main() -> pxid::Result<()> {
+//! use pxid::{Pxid, PxidWrapper};
+//!
+//! #[derive(PxidWrapper)]
+//! #[pxid(prefix = "user")]
+//! pub struct UserId(Pxid);
+//!
+//! let id = UserId::new()?;
+//!
+//! assert!("acct_9m4e2mr0ui3e8a215n4g".parse::<UserId>().is_err());
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "derive"))]
+//! # fn main() {}
+//! ```
+//!
+//! IDs are decoded from their string form with `FromStr`, or with `TryFrom`
+//! for `&str` and `String`. There is no `From<&str>` given decoding may fail.
+//!
+//! ```no_run
+//! use std::str::FromStr;
+//!
+//! use pxid::Pxid;
+//!
+//! # fn main() -> pxid::Result<()> {
+//! let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g")?;
+//!
+//! assert_eq!(Pxid::try_from("acct_9m4e2mr0ui3e8a215n4g")?, id);
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! ## Layout
 //!
 //! A prefixed XID fits nicely on a 16 bytes slice thanks to its packed data format.
@@ -83,14 +119,107 @@
 //! This project is licensed under the MIT License
 //!
 //! [1]: https://github.com/rs/xid
+#![deny(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::unreachable,
+    clippy::indexing_slicing
+)]
+#![warn(clippy::pedantic)]
+// Accessors on the `Copy` Pxid are pure, flagging each one adds noise
+// without catching mistakes
+#![allow(clippy::must_use_candidate)]
+// Failure modes are documented inline next to the `Error` variant returned
+#![allow(clippy::missing_errors_doc)]
+
+mod alphabet;
+pub mod bulk;
+pub mod capacity;
+mod catalog;
+mod checksum;
+mod compat;
+pub mod config;
+mod counter;
+mod encoded;
+mod epoch;
 mod error;
 mod factory;
+pub mod forensics;
+mod fuzzy;
 mod host_id;
 mod id;
+mod idempotency;
+pub mod intern;
+mod non_nil;
+mod policy;
+mod pool;
+pub mod prelude;
+mod sequence;
+mod shape;
+mod validate;
+mod wrapper;
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "bytes")]
+mod buf;
+#[cfg(feature = "clickhouse")]
+pub mod clickhouse;
+#[cfg(feature = "test-util")]
+pub mod fixtures;
+#[cfg(feature = "fast-hash")]
+pub mod hash;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod io;
+#[cfg(feature = "otel")]
+pub mod otel;
+#[cfg(feature = "postgres")]
+mod postgres;
+#[cfg(feature = "prost")]
+pub mod proto;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "test-util")]
+pub mod soak;
+#[cfg(feature = "tokio")]
+pub mod task;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
-pub use error::{DecodeError, Error};
-pub use factory::Factory;
+pub use alphabet::Alphabet;
+pub use catalog::{ConflictError, PrefixCatalog, PrefixEntry};
+pub use compat::CompatFixups;
+pub use encoded::EncodedPxid;
+pub use epoch::Epoch;
+pub use error::{DecodeError, DecodeErrorKind, Error};
+pub use factory::{
+    Factory, PrefixPadding, BACKFILL_MARKER, COUNTER_MAX, COUNTER_WARNING_THRESHOLD,
+    DETERMINISTIC_TIMESTAMP, SELF_TEST_PREFIX,
+};
+pub use fuzzy::{FuzzyResult, CONFUSABLES};
 pub use host_id::{host_id, machine_id, MachineIdBytes};
-pub use id::Pxid;
+pub use id::{Pxid, FIXED_PAD, NIL_PREFIX, PREFIX_CHARSET, SEPARATOR, XID_CHARSET};
+pub use idempotency::{IdempotencyError, IdempotencyKey};
+pub use non_nil::NonNilPxid;
+pub use policy::{ParseOptions, TimestampPolicy, TimestampViolation};
+pub use pool::{FactoryPool, RegistrationPolicy};
+pub use sequence::Sequence;
+pub use shape::{classify, InputShape};
+pub use validate::{
+    NotNil, ParseOrValidationError, PrefixAllowList, TimestampAfter, ValidationError, Validator,
+};
+
+#[cfg(feature = "derive")]
+pub use pxid_derive::PxidWrapper;
+
+/// Support code used by the code generated with `PxidWrapper`, not part of
+/// the public API.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "serde")]
+    pub use serde;
+
+    pub use crate::wrapper::{check_prefix, is_lowercase_prefix};
+}
@@ -0,0 +1,120 @@
+//! `Pxid` which is guaranteed not to be the nil Pxid, for fields where a zero
+//! ID must never be stored.
+//!
+//! ```no_run
+//! use pxid::{NonNilPxid, Pxid};
+//!
+//! struct Account {
+//!     id: NonNilPxid,
+//! }
+//!
+//! assert!(NonNilPxid::try_from(Pxid::nil()).is_err());
+//! ```
+use std::fmt::{self, Display};
+use std::ops::Deref;
+
+use crate::error::Error;
+use crate::id::Pxid;
+
+/// `Pxid` other than `Pxid::nil`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NonNilPxid(Pxid);
+
+impl NonNilPxid {
+    /// Retrieves the wrapped `Pxid`
+    #[inline]
+    pub fn get(self) -> Pxid {
+        self.0
+    }
+}
+
+impl TryFrom<Pxid> for NonNilPxid {
+    type Error = Error;
+
+    fn try_from(value: Pxid) -> Result<Self, Self::Error> {
+        if value.is_nil() {
+            return Err(Error::NilPxid);
+        }
+
+        Ok(Self(value))
+    }
+}
+
+impl From<NonNilPxid> for Pxid {
+    fn from(value: NonNilPxid) -> Self {
+        value.0
+    }
+}
+
+impl Deref for NonNilPxid {
+    type Target = Pxid;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for NonNilPxid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for NonNilPxid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for NonNilPxid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = Pxid::deserialize(deserializer)?;
+
+        NonNilPxid::try_from(value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn rejects_nil_pxid() {
+        assert_eq!(NonNilPxid::try_from(Pxid::nil()), Err(Error::NilPxid));
+    }
+
+    #[test]
+    fn wraps_non_nil_pxid() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let non_nil = NonNilPxid::try_from(id).unwrap();
+
+        assert_eq!(non_nil.get(), id);
+        assert_eq!(Pxid::from(non_nil), id);
+        assert_eq!(non_nil.prefix().unwrap(), "acct");
+        assert_eq!(non_nil.to_string(), "acct_9m4e2mr0ui3e8a215n4g");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialization_rejects_nil_pxid() {
+        let json = serde_json::to_string(&Pxid::nil()).unwrap();
+
+        assert!(serde_json::from_str::<NonNilPxid>(&json).is_err());
+        assert_eq!(
+            serde_json::from_str::<NonNilPxid>("\"acct_9m4e2mr0ui3e8a215n4g\"")
+                .unwrap()
+                .to_string(),
+            "acct_9m4e2mr0ui3e8a215n4g"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! [W3C Trace Context][1] identifiers derived from a `Pxid`, so the trace
+//! which created a record can be looked up from the record's ID and the ID
+//! can be recovered from a trace.
+//!
+//! | Identifier | Derivation                                             |
+//! | ---------- | ------------------------------------------------------ |
+//! | Trace ID   | The 16 bytes of the Pxid as is                         |
+//! | Span ID    | Bytes `0..8` XOR bytes `8..16`, see `Pxid::to_span_id` |
+//!
+//! Every identifier is non-zero, as required by W3C Trace Context, as long
+//! as the Pxid is not nil.
+//!
+//! [1]: https://www.w3.org/TR/trace-context/
+use std::fmt::Write;
+
+use crate::id::{Bytes, Pxid};
+use crate::Result;
+
+/// W3C Trace Context version emitted by `Pxid::traceparent_header`
+const TRACEPARENT_VERSION: &str = "00";
+
+/// W3C Trace Context flags emitted by `Pxid::traceparent_header`, marking
+/// the trace as sampled
+const TRACEPARENT_FLAGS: &str = "01";
+
+impl Pxid {
+    /// Retrieves the W3C trace-id for this Pxid, its 16 bytes as is.
+    ///
+    /// The trace-id is valid, non-zero, as long as this Pxid is not nil.
+    #[inline]
+    pub fn to_trace_id(&self) -> [u8; 16] {
+        self.0
+    }
+
+    /// Retrieves the W3C span-id for this Pxid, folding its 16 bytes by
+    /// XOR-ing bytes `0..8`, Prefix and Timestamp, with bytes `8..16`,
+    /// Machine ID, Process ID and Counter.
+    ///
+    /// When both halves are equal, which would render the invalid all-zero
+    /// span-id, bytes `0..8` are used instead, which are non-zero as long as
+    /// this Pxid is not nil.
+    pub fn to_span_id(&self) -> [u8; 8] {
+        let mut high = [0_u8; 8];
+        let mut span_id = [0_u8; 8];
+
+        for ((high, span), (a, b)) in high
+            .iter_mut()
+            .zip(span_id.iter_mut())
+            .zip(self.0.iter().zip(self.0.iter().skip(8)))
+        {
+            *high = *a;
+            *span = a ^ b;
+        }
+
+        if span_id == [0; 8] {
+            return high;
+        }
+
+        span_id
+    }
+
+    /// Recovers the Pxid a W3C trace-id was derived from with `to_trace_id`.
+    ///
+    /// The Prefix region is validated as done by `validate`, so the all-zero
+    /// trace-id, invalid on W3C Trace Context, fails with
+    /// `DecodeError::MissingPrefix`.
+    pub fn from_trace_id(bytes: Bytes) -> Result<Pxid> {
+        let id = Self(bytes);
+
+        id.validate_prefix()?;
+        Ok(id)
+    }
+
+    /// Retrieves a W3C `traceparent` header value for the trace of this
+    /// Pxid, using `to_trace_id` and `to_span_id` and flagged as sampled.
+    ///
+    /// ```text
+    /// 00-616363744d88e15b60f486e428412dc9-0197e59065c9cc92-01
+    /// ```
+    pub fn traceparent_header(&self) -> String {
+        format!(
+            "{}-{}-{}-{}",
+            TRACEPARENT_VERSION,
+            to_hex(&self.to_trace_id()),
+            to_hex(&self.to_span_id()),
+            TRACEPARENT_FLAGS
+        )
+    }
+}
+
+/// Encodes `bytes` as lowercase hexadecimal characters
+fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::error::{DecodeError, Error};
+
+    use super::*;
+
+    const BYTES: Bytes = [
+        0x61, 0x63, 0x63, 0x74, 0x4d, 0x88, 0xe1, 0x5b, 0x60, 0xf4, 0x86, 0xe4, 0x28, 0x41, 0x2d,
+        0xc9,
+    ];
+
+    #[test]
+    fn round_trips_trace_ids() {
+        let id = Pxid::from(BYTES);
+
+        assert_eq!(id.to_trace_id(), BYTES);
+        assert_eq!(Pxid::from_trace_id(id.to_trace_id()), Ok(id));
+
+        let id = Pxid::from_str("dog_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(Pxid::from_trace_id(id.to_trace_id()), Ok(id));
+    }
+
+    #[test]
+    fn rejects_nil_trace_ids() {
+        assert_eq!(Pxid::nil().to_trace_id(), [0; 16]);
+        assert_eq!(
+            Pxid::from_trace_id([0; 16]),
+            Err(Error::Decode(DecodeError::MissingPrefix(
+                Pxid::nil().to_string()
+            )))
+        );
+        assert!(Pxid::from_trace_id([0xff; 16]).is_err());
+    }
+
+    #[test]
+    fn folds_span_ids() {
+        assert_eq!(
+            Pxid::from(BYTES).to_span_id(),
+            [0x01, 0x97, 0xe5, 0x90, 0x65, 0xc9, 0xcc, 0x92]
+        );
+        assert_eq!(
+            Pxid::from([b'a', 0, 0, 0, 0, 0, 0, 1, b'a', 0, 0, 0, 0, 0, 0, 0]).to_span_id(),
+            [0, 0, 0, 0, 0, 0, 0, 1]
+        );
+        assert_eq!(
+            Pxid::from([b'a', 0, 0, 0, 0, 0, 0, 1, b'a', 0, 0, 0, 0, 0, 0, 1]).to_span_id(),
+            [b'a', 0, 0, 0, 0, 0, 0, 1]
+        );
+        assert_eq!(Pxid::nil().to_span_id(), [0; 8]);
+    }
+
+    #[test]
+    fn builds_traceparent_header() {
+        assert_eq!(
+            Pxid::from(BYTES).traceparent_header(),
+            "00-616363744d88e15b60f486e428412dc9-0197e59065c9cc92-01"
+        );
+
+        let header = Pxid::new("acct").unwrap().traceparent_header();
+
+        assert_eq!(header.len(), 55);
+        assert!(header
+            .split('-')
+            .map(str::len)
+            .eq([2, 32, 16, 2].into_iter()));
+    }
+}
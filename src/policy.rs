@@ -0,0 +1,289 @@
+//! Validation policies for IDs parsed in trusted contexts, where the
+//! Timestamp embedded in the ID is used as the event time.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use pxid::{ParseOptions, Pxid, TimestampPolicy};
+//!
+//! # fn main() -> pxid::Result<()> {
+//! let policy = TimestampPolicy::new(Duration::from_secs(60))
+//!     .max_past(Duration::from_secs(86_400));
+//! let options = ParseOptions::new().timestamp_policy(policy);
+//! let id = Pxid::parse_with_options("evnt_9m4e2mr0ui3e8a215n4g", &options)?;
+//! # Ok(())
+//! # }
+//! ```
+use std::time::{Duration, SystemTime};
+
+use thiserror::Error;
+
+use crate::error::Error;
+use crate::id::{Pxid, SEPARATOR};
+use crate::Result;
+
+/// Bounds on how far the Timestamp of an ID can be from the current time,
+/// to reject IDs generated on hosts with skewed clocks.
+///
+/// Timestamps exactly at a bound are accepted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimestampPolicy {
+    /// Max time the Timestamp can be ahead of the current time
+    pub max_future: Duration,
+
+    /// Max time the Timestamp can be behind the current time, unlimited
+    /// when `None`
+    pub max_past: Option<Duration>,
+}
+
+/// Timestamp of an ID out of the bounds of a `TimestampPolicy`, holding how
+/// far the Timestamp is from the current time
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+pub enum TimestampViolation {
+    /// Timestamp is ahead of the current time by more than `max_future`
+    #[error("Timestamp is {}s ahead of the current time.", .0.as_secs())]
+    TooFarFuture(Duration),
+
+    /// Timestamp is behind the current time by more than `max_past`
+    #[error("Timestamp is {}s behind the current time.", .0.as_secs())]
+    TooFarPast(Duration),
+}
+
+impl TimestampPolicy {
+    /// Creates a `TimestampPolicy` accepting Timestamps up to `max_future`
+    /// ahead of the current time, and any Timestamp behind it
+    pub fn new(max_future: Duration) -> Self {
+        Self {
+            max_future,
+            max_past: None,
+        }
+    }
+
+    /// Sets the max time Timestamps can be behind the current time
+    #[must_use]
+    pub fn max_past(mut self, max_past: Duration) -> Self {
+        self.max_past = Some(max_past);
+        self
+    }
+
+    /// Checks the Timestamp of `id` is within the bounds of this policy
+    /// around `now`
+    pub fn check(&self, id: &Pxid, now: SystemTime) -> std::result::Result<(), TimestampViolation> {
+        match id.timestamp().duration_since(now) {
+            Ok(ahead) if ahead > self.max_future => Err(TimestampViolation::TooFarFuture(ahead)),
+            Ok(_) => Ok(()),
+            Err(err) => match self.max_past {
+                Some(max_past) if err.duration() > max_past => {
+                    Err(TimestampViolation::TooFarPast(err.duration()))
+                }
+                _ => Ok(()),
+            },
+        }
+    }
+
+    /// Splits `ids` into the ones within the bounds of this policy around
+    /// `now`, and the ones violating it along with their violation, keeping
+    /// the order of `ids` on both
+    pub fn partition<I>(
+        &self,
+        ids: I,
+        now: SystemTime,
+    ) -> (Vec<Pxid>, Vec<(Pxid, TimestampViolation)>)
+    where
+        I: IntoIterator<Item = Pxid>,
+    {
+        let mut accepted = Vec::new();
+        let mut rejected = Vec::new();
+
+        for id in ids {
+            match self.check(&id, now) {
+                Ok(()) => accepted.push(id),
+                Err(violation) => rejected.push((id, violation)),
+            }
+        }
+
+        (accepted, rejected)
+    }
+}
+
+/// Options for `Pxid::parse_with_options`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseOptions {
+    separator: char,
+    timestamp_policy: Option<TimestampPolicy>,
+    now: Option<SystemTime>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            separator: SEPARATOR,
+            timestamp_policy: None,
+            now: None,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Creates `ParseOptions` decoding IDs as done by `FromStr`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the separator expected between the prefix and the encoded XID
+    #[must_use]
+    pub fn separator(mut self, separator: char) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets the `TimestampPolicy` decoded IDs are checked against
+    #[must_use]
+    pub fn timestamp_policy(mut self, policy: TimestampPolicy) -> Self {
+        self.timestamp_policy = Some(policy);
+        self
+    }
+
+    /// Sets the time `TimestampPolicy` bounds are checked around, instead
+    /// of the current time read on each parse
+    #[must_use]
+    pub fn now(mut self, now: SystemTime) -> Self {
+        self.now = Some(now);
+        self
+    }
+}
+
+impl Pxid {
+    /// Decodes a Pxid as done by `Pxid::from_str_with_separator`, checking
+    /// its Timestamp against the `TimestampPolicy` of `options`, if any.
+    ///
+    /// IDs violating the policy fail with `Error::SkewedTimestamp`.
+    pub fn parse_with_options(s: &str, options: &ParseOptions) -> Result<Pxid> {
+        let id = Self::from_str_with_separator(s, options.separator)?;
+
+        if let Some(policy) = options.timestamp_policy {
+            policy
+                .check(&id, options.now.unwrap_or_else(SystemTime::now))
+                .map_err(|violation| Error::SkewedTimestamp(id.to_string(), violation))?;
+        }
+
+        Ok(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use crate::factory::Factory;
+
+    use super::*;
+
+    const NOW: u32 = 1_700_000_000;
+
+    fn now() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(u64::from(NOW))
+    }
+
+    fn id_at(offset: i64) -> Pxid {
+        let time = u32::try_from(i64::from(NOW) + offset).unwrap();
+
+        Factory::deterministic(7)
+            .new_with_time("evnt", time)
+            .unwrap()
+    }
+
+    fn policy() -> TimestampPolicy {
+        TimestampPolicy::new(Duration::from_mins(1)).max_past(Duration::from_hours(1))
+    }
+
+    #[test]
+    fn accepts_timestamps_within_bounds() {
+        for offset in [-3_600, -3_599, -1, 0, 1, 59, 60] {
+            assert_eq!(policy().check(&id_at(offset), now()), Ok(()), "{offset}");
+        }
+    }
+
+    #[test]
+    fn rejects_timestamps_past_bounds() {
+        assert_eq!(
+            policy().check(&id_at(61), now()),
+            Err(TimestampViolation::TooFarFuture(Duration::from_secs(61)))
+        );
+        assert_eq!(
+            policy().check(&id_at(-3_601), now()),
+            Err(TimestampViolation::TooFarPast(Duration::from_secs(3_601)))
+        );
+        assert_eq!(
+            policy().check(&id_at(-3_601), now() + Duration::from_millis(500)),
+            Err(TimestampViolation::TooFarPast(Duration::from_millis(
+                3_601_500
+            )))
+        );
+    }
+
+    #[test]
+    fn accepts_any_past_timestamp_when_unlimited() {
+        let policy = TimestampPolicy::new(Duration::ZERO);
+
+        assert_eq!(policy.max_past, None);
+        assert_eq!(policy.check(&id_at(-1_000_000_000), now()), Ok(()));
+        assert_eq!(policy.check(&id_at(0), now()), Ok(()));
+        assert_eq!(
+            policy.check(&id_at(1), now()),
+            Err(TimestampViolation::TooFarFuture(Duration::from_secs(1)))
+        );
+    }
+
+    #[test]
+    fn partitions_ids_by_policy() {
+        let ids = [id_at(-3_601), id_at(0), id_at(61), id_at(60)];
+        let (accepted, rejected) = policy().partition(ids, now());
+
+        assert_eq!(accepted, vec![ids[1], ids[3]]);
+        assert_eq!(
+            rejected,
+            vec![
+                (
+                    ids[0],
+                    TimestampViolation::TooFarPast(Duration::from_secs(3_601))
+                ),
+                (
+                    ids[2],
+                    TimestampViolation::TooFarFuture(Duration::from_secs(61))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_with_timestamp_policy() {
+        let options = ParseOptions::new().timestamp_policy(policy()).now(now());
+        let valid = id_at(60);
+        let skewed = id_at(61);
+
+        assert_eq!(
+            Pxid::parse_with_options(&valid.to_string(), &options),
+            Ok(valid)
+        );
+        assert_eq!(
+            Pxid::parse_with_options(&skewed.to_string(), &options),
+            Err(Error::SkewedTimestamp(
+                skewed.to_string(),
+                TimestampViolation::TooFarFuture(Duration::from_secs(61))
+            ))
+        );
+        assert_eq!(
+            Pxid::parse_with_options(&skewed.to_string(), &ParseOptions::new()),
+            Ok(skewed)
+        );
+        assert_eq!(
+            Pxid::parse_with_options(
+                &skewed.to_string().replace('_', "."),
+                &ParseOptions::new().separator('.')
+            ),
+            Ok(skewed)
+        );
+        assert!(Pxid::parse_with_options("evnt", &options).is_err());
+    }
+}
@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{PoisonError, RwLock};
+
+use crate::counter;
+use crate::error::{DecodeError, Error};
+use crate::factory::Factory;
+use crate::host_id::{machine_id, MachineIdBytes};
+use crate::id::{Pxid, PREFIX_LENGTH};
+use crate::Result;
+
+/// Policy used by the `FactoryPool` when generating IDs for a prefix that
+/// is not registered
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RegistrationPolicy {
+    /// Prefixes must be registered with `FactoryPool::register` before
+    /// generating IDs
+    #[default]
+    Explicit,
+
+    /// Prefixes are registered on first use, up to `max_prefixes` to avoid
+    /// unbounded memory usage from arbitrary prefixes
+    Auto { max_prefixes: usize },
+}
+
+/// Per prefix counter and generation statistics
+struct PrefixCounter {
+    counter: AtomicU32,
+    generated: AtomicU64,
+}
+
+impl PrefixCounter {
+    fn new() -> Self {
+        Self {
+            counter: AtomicU32::new(counter::new_seed()),
+            generated: AtomicU64::new(0),
+        }
+    }
+}
+
+/// Pool of Pxid counters partitioned by prefix. Machine ID and Process ID
+/// are shared, but each registered prefix holds its own counter so
+/// generation for one prefix doesn't affect others.
+pub struct FactoryPool {
+    process_id: u16,
+    machine_id: MachineIdBytes,
+    policy: RegistrationPolicy,
+    counters: RwLock<HashMap<String, PrefixCounter>>,
+}
+
+impl FactoryPool {
+    /// Creates a new `FactoryPool` using the `RegistrationPolicy::Explicit`
+    /// policy
+    pub fn new() -> Result<Self> {
+        Self::with_policy(RegistrationPolicy::Explicit)
+    }
+
+    /// Creates a new `FactoryPool` using the provided `RegistrationPolicy`
+    pub fn with_policy(policy: RegistrationPolicy) -> Result<Self> {
+        let process_id = Pxid::read_process_id();
+        let machine_id = machine_id()?;
+
+        Ok(Self {
+            process_id,
+            machine_id,
+            policy,
+            counters: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Registers a prefix with its own counter. Registering an already
+    /// registered prefix has no effect.
+    pub fn register(&self, prefix: &str) -> Result<()> {
+        Self::validate_prefix(prefix)?;
+
+        self.counters
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(prefix.to_string())
+            .or_insert_with(PrefixCounter::new);
+
+        Ok(())
+    }
+
+    /// Creates a new ID for the provided `prefix` using the current timestamp
+    #[inline]
+    pub fn new_id(&self, prefix: &str) -> Result<Pxid> {
+        let current_timestamp = Factory::current_timestamp()?;
+
+        self.new_with_time(prefix, current_timestamp)
+    }
+
+    /// Creates a new ID for the provided `prefix` with the provided `time`
+    pub fn new_with_time(&self, prefix: &str, time: u32) -> Result<Pxid> {
+        {
+            let counters = self.counters.read().unwrap_or_else(PoisonError::into_inner);
+
+            if let Some(counter) = counters.get(prefix) {
+                return self.generate(counter, prefix, time);
+            }
+        }
+
+        match self.policy {
+            RegistrationPolicy::Explicit => Err(Error::UnregisteredPrefix(prefix.to_string())),
+            RegistrationPolicy::Auto { max_prefixes } => {
+                Self::validate_prefix(prefix)?;
+
+                let mut counters = self
+                    .counters
+                    .write()
+                    .unwrap_or_else(PoisonError::into_inner);
+
+                if !counters.contains_key(prefix) && counters.len() >= max_prefixes {
+                    return Err(Error::PrefixLimitReached(prefix.to_string(), max_prefixes));
+                }
+
+                let counter = counters
+                    .entry(prefix.to_string())
+                    .or_insert_with(PrefixCounter::new);
+
+                self.generate(counter, prefix, time)
+            }
+        }
+    }
+
+    /// Retrieves the amount of IDs generated for each registered prefix,
+    /// sorted by prefix
+    pub fn stats(&self) -> Vec<(String, u64)> {
+        let counters = self.counters.read().unwrap_or_else(PoisonError::into_inner);
+        let mut stats = counters
+            .iter()
+            .map(|(prefix, counter)| (prefix.clone(), counter.generated.load(Ordering::SeqCst)))
+            .collect::<Vec<(String, u64)>>();
+
+        stats.sort();
+        stats
+    }
+
+    fn generate(&self, counter: &PrefixCounter, prefix: &str, time: u32) -> Result<Pxid> {
+        let value = counter.counter.fetch_add(1, Ordering::SeqCst);
+        let id = Pxid::from_parts(prefix, time, self.machine_id, self.process_id, value)?;
+
+        counter.generated.fetch_add(1, Ordering::SeqCst);
+
+        Ok(id)
+    }
+
+    fn validate_prefix(prefix: &str) -> Result<()> {
+        if prefix.len() > PREFIX_LENGTH {
+            return Err(Error::Decode(DecodeError::InvalidPrefixLength(
+                prefix.to_string(),
+                prefix.len(),
+            )));
+        }
+
+        if prefix.is_empty() {
+            return Err(Error::Decode(DecodeError::MissingPrefix(
+                prefix.to_string(),
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::thread;
+
+    use super::*;
+
+    const THREADS: usize = 4;
+    const TRYOUTS: usize = 1000;
+
+    #[test]
+    fn pool_never_repeats_across_threads() {
+        let pool = FactoryPool::new().unwrap();
+        let prefixes = ["acct", "ordr", "usr"];
+
+        for prefix in prefixes {
+            pool.register(prefix).unwrap();
+        }
+
+        let ids = thread::scope(|scope| {
+            let handles = (0..THREADS)
+                .map(|i| {
+                    let pool = &pool;
+                    // Two threads share each prefix besides the last one
+                    let prefix = prefixes[i % prefixes.len()];
+
+                    scope.spawn(move || {
+                        (0..TRYOUTS)
+                            .map(|_| pool.new_with_time(prefix, 1).unwrap())
+                            .collect::<Vec<Pxid>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect::<Vec<Pxid>>()
+        });
+
+        let unique = ids.iter().collect::<HashSet<&Pxid>>();
+
+        assert_eq!(unique.len(), THREADS * TRYOUTS);
+        assert_eq!(
+            pool.stats(),
+            vec![
+                (String::from("acct"), (2 * TRYOUTS) as u64),
+                (String::from("ordr"), TRYOUTS as u64),
+                (String::from("usr"), TRYOUTS as u64),
+            ]
+        );
+    }
+
+    #[test]
+    fn complains_on_unregistered_prefix() {
+        let pool = FactoryPool::new().unwrap();
+
+        pool.register("acct").unwrap();
+
+        assert!(pool.new_id("acct").is_ok());
+        assert_eq!(
+            pool.new_id("ordr"),
+            Err(Error::UnregisteredPrefix(String::from("ordr")))
+        );
+        assert_eq!(pool.stats(), vec![(String::from("acct"), 1)]);
+    }
+
+    #[test]
+    fn complains_on_invalid_prefix_registration() {
+        let pool = FactoryPool::new().unwrap();
+
+        assert_eq!(
+            pool.register("account"),
+            Err(Error::Decode(DecodeError::InvalidPrefixLength(
+                String::from("account"),
+                7
+            )))
+        );
+        assert!(pool.stats().is_empty());
+    }
+
+    #[test]
+    fn auto_registers_up_to_max_prefixes() {
+        let pool = FactoryPool::with_policy(RegistrationPolicy::Auto { max_prefixes: 2 }).unwrap();
+
+        assert!(pool.new_id("acct").is_ok());
+        assert!(pool.new_id("ordr").is_ok());
+        assert!(pool.new_id("acct").is_ok());
+        assert_eq!(
+            pool.new_id("usr"),
+            Err(Error::PrefixLimitReached(String::from("usr"), 2))
+        );
+        assert_eq!(
+            pool.stats(),
+            vec![(String::from("acct"), 2), (String::from("ordr"), 1)]
+        );
+    }
+
+    #[test]
+    fn auto_registers_concurrently_within_cap() {
+        let pool = FactoryPool::with_policy(RegistrationPolicy::Auto { max_prefixes: 3 }).unwrap();
+        let prefixes = ["a", "b", "c", "d", "e", "f"];
+
+        thread::scope(|scope| {
+            for prefix in prefixes {
+                let pool = &pool;
+
+                scope.spawn(move || {
+                    for _ in 0..TRYOUTS {
+                        let _ = pool.new_id(prefix);
+                    }
+                });
+            }
+        });
+
+        let stats = pool.stats();
+
+        assert_eq!(stats.len(), 3);
+        assert!(stats.iter().all(|(_, count)| *count == TRYOUTS as u64));
+    }
+}
@@ -0,0 +1,106 @@
+//! `ToSql` and `FromSql` implementations for the [postgres][1] and
+//! [tokio-postgres][2] crates through [postgres-types][3].
+//!
+//! IDs are stored on `TEXT` or `VARCHAR` columns in the string form written
+//! by `Display`, with the NUL padding of short prefixes trimmed given
+//! PostgreSQL text values can't hold NUL characters. Values are read back
+//! as done by `FromStr`.
+//!
+//! [1]: https://github.com/sfackler/rust-postgres
+//! [2]: https://docs.rs/tokio-postgres
+//! [3]: https://docs.rs/postgres-types
+use std::error::Error as StdError;
+use std::str::FromStr;
+
+use bytes::BytesMut;
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+use crate::id::Pxid;
+
+impl ToSql for Pxid {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn StdError + Sync + Send>> {
+        let text = self.to_string().replace('\0', "");
+
+        <&str as ToSql>::to_sql(&text.as_str(), ty, out)
+    }
+
+    accepts!(TEXT, VARCHAR);
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Pxid {
+    /// Decodes the column value as done by `FromStr`, failures are retrieved
+    /// as the boxed `Error`
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn StdError + Sync + Send>> {
+        let text = <&str as FromSql>::from_sql(ty, raw)?;
+
+        Ok(Pxid::from_str(text)?)
+    }
+
+    accepts!(TEXT, VARCHAR);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{DecodeError, Error};
+    use crate::factory::Factory;
+
+    use super::*;
+
+    fn to_sql(id: &Pxid, ty: &Type) -> BytesMut {
+        let mut out = BytesMut::new();
+
+        assert!(matches!(id.to_sql(ty, &mut out), Ok(IsNull::No)));
+        out
+    }
+
+    #[test]
+    fn round_trips_text_columns() {
+        let factory = Factory::deterministic(7);
+
+        for prefix in ["acct", "dog", "a"] {
+            let id = factory.new_id(prefix).unwrap();
+            let raw = to_sql(&id, &Type::TEXT);
+
+            assert_eq!(&raw[..], id.to_string().replace('\0', "").as_bytes());
+            assert_eq!(Pxid::from_sql(&Type::TEXT, &raw).unwrap(), id);
+            assert_eq!(Pxid::from_sql(&Type::VARCHAR, &raw).unwrap(), id);
+        }
+
+        let raw = to_sql(&Pxid::nil(), &Type::VARCHAR);
+
+        assert_eq!(Pxid::from_sql(&Type::VARCHAR, &raw).unwrap(), Pxid::nil());
+    }
+
+    #[test]
+    fn accepts_text_types_only() {
+        assert!(<Pxid as ToSql>::accepts(&Type::TEXT));
+        assert!(<Pxid as ToSql>::accepts(&Type::VARCHAR));
+        assert!(!<Pxid as ToSql>::accepts(&Type::BYTEA));
+        assert!(<Pxid as FromSql>::accepts(&Type::TEXT));
+        assert!(!<Pxid as FromSql>::accepts(&Type::UUID));
+
+        let mut out = BytesMut::new();
+
+        assert!(Pxid::nil().to_sql_checked(&Type::BYTEA, &mut out).is_err());
+    }
+
+    #[test]
+    fn boxes_decode_errors() {
+        let err = Pxid::from_sql(&Type::TEXT, b"acct").unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<Error>(),
+            Some(&Error::Decode(DecodeError::InvalidLength(
+                String::from("acct"),
+                4
+            )))
+        );
+        assert!(Pxid::from_sql(&Type::TEXT, &[0xff, 0xfe]).is_err());
+    }
+}
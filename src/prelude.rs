@@ -0,0 +1,45 @@
+//! Commonly imported items, meant to be glob imported.
+//!
+//! ```no_run
+//! use pxid::prelude::*;
+//!
+//! fn create_account() -> Result<Pxid> {
+//!     Pxid::new("acct")
+//! }
+//! ```
+pub use crate::error::{DecodeError, Error};
+pub use crate::factory::Factory;
+pub use crate::id::{Bytes, Pxid, BINARY_LENGTH, ENCODED_LENGTH, PREFIX_LENGTH};
+pub use crate::Result;
+
+#[cfg(feature = "derive")]
+pub use crate::PxidWrapper;
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn parse(s: &str) -> Result<Pxid> {
+        Pxid::from_str(s)
+    }
+
+    #[test]
+    fn exports_common_items() {
+        let id = parse("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let bytes: Bytes = *id;
+
+        assert_eq!(bytes.len(), BINARY_LENGTH);
+        assert_eq!(id.to_string().len(), ENCODED_LENGTH);
+        assert_eq!(id.prefix_bytes().len(), PREFIX_LENGTH);
+        assert_eq!(
+            parse("acct"),
+            Err(Error::Decode(DecodeError::InvalidLength(
+                String::from("acct"),
+                4
+            )))
+        );
+        assert!(Factory::new().is_ok());
+    }
+}
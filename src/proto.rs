@@ -0,0 +1,89 @@
+//! Protocol Buffers interop through [prost][1].
+//!
+//! `PxidProto` is the message described by `proto/pxid.proto`, holding the
+//! 16 bytes packed binary form of a Pxid on a `bytes` field:
+//!
+//! ```proto
+//! message Pxid {
+//!   bytes id = 1;
+//! }
+//! ```
+//!
+//! [1]: https://github.com/tokio-rs/prost
+use crate::error::Error;
+use crate::id::Pxid;
+
+/// Protocol Buffers message holding a Pxid
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PxidProto {
+    /// Packed binary form of the Pxid, as retrieved by
+    /// `Pxid::to_protobuf_bytes`
+    #[prost(bytes = "vec", tag = "1")]
+    pub id: Vec<u8>,
+}
+
+impl From<Pxid> for PxidProto {
+    fn from(value: Pxid) -> Self {
+        Self {
+            id: value.to_protobuf_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<PxidProto> for Pxid {
+    type Error = Error;
+
+    fn try_from(value: PxidProto) -> Result<Self, Self::Error> {
+        Pxid::from_protobuf_bytes(&value.id)
+    }
+}
+
+impl TryFrom<&PxidProto> for Pxid {
+    type Error = Error;
+
+    fn try_from(value: &PxidProto) -> Result<Self, Self::Error> {
+        Pxid::from_protobuf_bytes(&value.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use prost::Message;
+
+    use super::*;
+
+    #[test]
+    fn encodes_message_with_bytes_field() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let encoded = PxidProto::from(id).encode_to_vec();
+
+        assert_eq!(encoded.len(), 18);
+        assert_eq!(encoded[..2], [0x0a, 0x10]);
+        assert_eq!(encoded[2..], id.to_protobuf_bytes());
+    }
+
+    #[test]
+    fn round_trips_messages() {
+        let id = Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let encoded = PxidProto::from(id).encode_to_vec();
+        let decoded = PxidProto::decode(encoded.as_slice()).unwrap();
+
+        assert_eq!(Pxid::try_from(&decoded), Ok(id));
+        assert_eq!(Pxid::try_from(decoded), Ok(id));
+    }
+
+    #[test]
+    fn rejects_messages_with_invalid_ids() {
+        assert_eq!(
+            Pxid::try_from(PxidProto::default()),
+            Err(Error::InvalidBinaryLength(0))
+        );
+        assert_eq!(
+            Pxid::try_from(PxidProto { id: vec![0; 12] }),
+            Err(Error::InvalidBinaryLength(12))
+        );
+        assert!(Pxid::try_from(PxidProto { id: vec![0xff; 16] }).is_err());
+    }
+}
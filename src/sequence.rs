@@ -0,0 +1,117 @@
+//! Iterator of IDs sharing the same prefix.
+//!
+//! ```no_run
+//! use pxid::Sequence;
+//!
+//! # fn main() -> pxid::Result<()> {
+//! let ids = Sequence::new("evnt")?.take(100).collect::<Vec<_>>();
+//! # Ok(())
+//! # }
+//! ```
+use crate::factory::Factory;
+use crate::id::Pxid;
+use crate::Result;
+
+/// Endless `Iterator` of IDs generated by a `Factory` for a single prefix.
+///
+/// The Machine ID is read and the prefix is validated when the `Sequence`
+/// is created, so generating IDs only fails if the system clock is out of
+/// the Timestamp range. Iteration ends on the first error, use
+/// `Sequence::try_next` to handle them instead.
+pub struct Sequence {
+    factory: Factory,
+    prefix: String,
+}
+
+impl Sequence {
+    /// Creates a `Sequence` of IDs with the provided `prefix` using a new
+    /// `Factory`
+    pub fn new(prefix: &str) -> Result<Self> {
+        Self::with_factory(Factory::new()?, prefix)
+    }
+
+    /// Creates a `Sequence` of IDs with the provided `prefix` using the
+    /// provided `factory`
+    pub fn with_factory(factory: Factory, prefix: &str) -> Result<Self> {
+        factory.peek_next(prefix)?;
+
+        Ok(Self {
+            factory,
+            prefix: prefix.to_string(),
+        })
+    }
+
+    /// Retrieves the prefix of the generated IDs
+    #[inline]
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Generates the next ID, this is never `None` but holds the error
+    /// found generating the ID, if any
+    pub fn try_next(&mut self) -> Option<Result<Pxid>> {
+        Some(self.factory.new_id(&self.prefix))
+    }
+}
+
+impl Iterator for Sequence {
+    type Item = Pxid;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().and_then(Result::ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::error::{DecodeError, Error};
+    use crate::factory::PrefixPadding;
+
+    use super::*;
+
+    #[test]
+    fn generates_ids_with_prefix() {
+        let ids = Sequence::new("evnt")
+            .unwrap()
+            .take(100)
+            .collect::<Vec<Pxid>>();
+
+        assert_eq!(ids.len(), 100);
+        assert_eq!(ids.iter().collect::<HashSet<&Pxid>>().len(), 100);
+        assert!(ids
+            .iter()
+            .all(|id| id.prefix().unwrap() == "evnt" && id.validate().is_ok()));
+    }
+
+    #[test]
+    fn follows_factory_sequence() {
+        let mut sequence = Sequence::with_factory(Factory::deterministic(7), "evnt").unwrap();
+        let factory = Factory::deterministic(7);
+
+        assert_eq!(sequence.prefix(), "evnt");
+        assert_eq!(sequence.try_next(), Some(factory.new_id("evnt")));
+        assert_eq!(sequence.next(), factory.new_id("evnt").ok());
+    }
+
+    #[test]
+    fn validates_prefix_on_creation() {
+        assert_eq!(
+            Sequence::new("events").err(),
+            Some(Error::Decode(DecodeError::InvalidPrefixLength(
+                String::from("events"),
+                6
+            )))
+        );
+        assert!(Sequence::new("").is_err());
+        assert_eq!(
+            Sequence::with_factory(
+                Factory::deterministic(7).padding(PrefixPadding::Strict),
+                "evt"
+            )
+            .err(),
+            Some(Error::PrefixTooShort(String::from("evt")))
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Serde helpers to be used with the `#[serde(with = "...")]` attribute.
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+
+use crate::id::PREFIX_LENGTH;
+
+/// Deserializes a `String` only if it holds a valid Pxid prefix, between 1
+/// and 4 ASCII alphanumeric characters. Useful to catch misconfigured
+/// prefixes when configuration is loaded.
+///
+/// ```no_run
+/// # use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(deserialize_with = "pxid::serde::validate_prefix")]
+///     account_prefix: String,
+/// }
+/// ```
+pub fn validate_prefix<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let prefix = String::deserialize(deserializer)?;
+
+    if prefix.is_empty() || prefix.len() > PREFIX_LENGTH {
+        return Err(D::Error::custom(format!(
+            "Invalid Pxid prefix {:?}. Prefixes must have between 1 and {} characters, but received {}.",
+            prefix,
+            PREFIX_LENGTH,
+            prefix.len()
+        )));
+    }
+
+    if let Some(c) = prefix.chars().find(|c| !c.is_ascii_alphanumeric()) {
+        return Err(D::Error::custom(format!(
+            "Invalid Pxid prefix {prefix:?}. Prefixes only allow ASCII alphanumeric characters, but found {c:?}."
+        )));
+    }
+
+    Ok(prefix)
+}
+
+/// Deserializes a Pxid applying the same cleanup done by
+/// `pxid::config::parse_config_value`.
+///
+/// ```no_run
+/// # use pxid::Pxid;
+/// # use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct Config {
+///     #[serde(with = "pxid::serde::lenient_config")]
+///     admin: Pxid,
+/// }
+/// ```
+pub mod lenient_config {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::config::parse_config_value;
+    use crate::id::Pxid;
+
+    pub fn serialize<S>(value: &Pxid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Pxid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+
+        parse_config_value(&raw).map_err(D::Error::custom)
+    }
+}
+
+/// Serializes `Pxid`, `Option<Pxid>` and `Vec<Pxid>` values using their
+/// string form.
+///
+/// ```no_run
+/// # use pxid::Pxid;
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Deserialize, Serialize)]
+/// struct Post {
+///     #[serde(with = "pxid::serde::as_string")]
+///     author: Option<Pxid>,
+///     #[serde(with = "pxid::serde::as_string")]
+///     tags: Vec<Pxid>,
+/// }
+/// ```
+pub mod as_string {
+    use std::str::FromStr;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::id::Pxid;
+
+    /// Values which can be serialized using the Pxid string form
+    pub trait AsString: Sized {
+        fn serialize_as_string<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer;
+
+        fn deserialize_as_string<'de, D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>;
+    }
+
+    impl AsString for Pxid {
+        fn serialize_as_string<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_str(&self.to_string())
+        }
+
+        fn deserialize_as_string<'de, D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let value = String::deserialize(deserializer)?;
+
+            Pxid::from_str(&value).map_err(D::Error::custom)
+        }
+    }
+
+    impl AsString for Option<Pxid> {
+        fn serialize_as_string<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match self {
+                Some(id) => serializer.serialize_some(&id.to_string()),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        fn deserialize_as_string<'de, D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Option::<String>::deserialize(deserializer)?
+                .map(|value| Pxid::from_str(&value).map_err(D::Error::custom))
+                .transpose()
+        }
+    }
+
+    impl AsString for Vec<Pxid> {
+        fn serialize_as_string<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_seq(self.iter().map(std::string::ToString::to_string))
+        }
+
+        fn deserialize_as_string<'de, D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Vec::<String>::deserialize(deserializer)?
+                .iter()
+                .map(|value| Pxid::from_str(value).map_err(D::Error::custom))
+                .collect()
+        }
+    }
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsString,
+        S: Serializer,
+    {
+        value.serialize_as_string(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: AsString,
+        D: Deserializer<'de>,
+    {
+        T::deserialize_as_string(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    use crate::Pxid;
+
+    #[derive(Debug, Deserialize)]
+    struct Config {
+        #[serde(with = "crate::serde::lenient_config")]
+        admin: Pxid,
+    }
+
+    #[test]
+    fn deserializes_lenient_config_values() {
+        let config: Config =
+            serde_json::from_str(r#"{ "admin": " 'acct_9m4e2mr0ui3e8a215n4g' " }"#).unwrap();
+
+        assert_eq!(
+            config.admin,
+            Pxid::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap()
+        );
+    }
+
+    #[test]
+    fn complains_with_hint_on_lenient_config_values() {
+        let err = serde_json::from_str::<Config>(r#"{ "admin": "acct_9m4e2mr0" }"#).unwrap_err();
+
+        assert!(err.to_string().contains(crate::config::TRUNCATED_HINT));
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct PrefixConfig {
+        #[serde(deserialize_with = "crate::serde::validate_prefix")]
+        account_prefix: String,
+    }
+
+    #[test]
+    fn deserializes_valid_prefixes() {
+        let config: PrefixConfig =
+            serde_json::from_value(json!({ "account_prefix": "acct" })).unwrap();
+
+        assert_eq!(config.account_prefix, "acct");
+    }
+
+    #[test]
+    fn complains_deserializing_invalid_prefixes() {
+        let err = |prefix: &str| {
+            serde_json::from_value::<PrefixConfig>(json!({ "account_prefix": prefix }))
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            err("account"),
+            "Invalid Pxid prefix \"account\". Prefixes must have between 1 and 4 characters, but received 7."
+        );
+        assert_eq!(
+            err(""),
+            "Invalid Pxid prefix \"\". Prefixes must have between 1 and 4 characters, but received 0."
+        );
+        assert_eq!(
+            err("ac-t"),
+            "Invalid Pxid prefix \"ac-t\". Prefixes only allow ASCII alphanumeric characters, but found '-'."
+        );
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Post {
+        #[serde(with = "crate::serde::as_string")]
+        id: Pxid,
+        #[serde(with = "crate::serde::as_string")]
+        author: Option<Pxid>,
+        #[serde(with = "crate::serde::as_string")]
+        tags: Vec<Pxid>,
+    }
+
+    #[test]
+    fn serializes_option_some_as_string() {
+        let post = Post {
+            id: Pxid::from_str("post_9m4e2mr0ui3e8a215n4g").unwrap(),
+            author: Some(Pxid::from_str("user_crdqga007gvfk4a3t0t0").unwrap()),
+            tags: vec![Pxid::from_str("ctgy_9m4e2mr0ui3e8a215n4g").unwrap()],
+        };
+        let value = serde_json::to_value(&post).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "id": "post_9m4e2mr0ui3e8a215n4g",
+                "author": "user_crdqga007gvfk4a3t0t0",
+                "tags": [post.tags[0].to_string()],
+            })
+        );
+        assert_eq!(serde_json::from_value::<Post>(value).unwrap(), post);
+    }
+
+    #[test]
+    fn serializes_option_none_as_null() {
+        let post = Post {
+            id: Pxid::from_str("post_9m4e2mr0ui3e8a215n4g").unwrap(),
+            author: None,
+            tags: Vec::new(),
+        };
+        let value = serde_json::to_value(&post).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "id": "post_9m4e2mr0ui3e8a215n4g",
+                "author": null,
+                "tags": [],
+            })
+        );
+        assert_eq!(serde_json::from_value::<Post>(value).unwrap(), post);
+    }
+}
@@ -0,0 +1,177 @@
+//! Cheap classification of inputs rejected by the decoder, useful to report
+//! why IDs are rejected without parsing error messages.
+//!
+//! ```no_run
+//! use pxid::{classify, InputShape};
+//!
+//! assert_eq!(
+//!     classify("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+//!     InputShape::LooksLikeUuid
+//! );
+//! ```
+use crate::id::{ENCODING_CHARS, ULID_ENCODED_LENGTH, XID_ENCODED_LENGTH};
+
+/// Hyphenated UUID length
+const UUID_HYPHENATED_LENGTH: usize = 36;
+
+/// UUID length without hyphens
+const UUID_SIMPLE_LENGTH: usize = 32;
+
+/// Positions of hyphens on hyphenated UUIDs
+const UUID_HYPHENS: [usize; 4] = [8, 13, 18, 23];
+
+/// Shape of an input which is not a valid Pxid
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputShape {
+    /// UUID either hyphenated or as 32 hexadecimal digits
+    LooksLikeUuid,
+
+    /// ULID using Crockford's Base32
+    LooksLikeUlid,
+
+    /// Encoded XID missing the prefix and separator
+    LooksLikeBareXid,
+
+    /// Empty or whitespace only input
+    Empty,
+
+    /// Input longer than any recognized shape
+    TooLong,
+
+    /// None of the above
+    Other,
+}
+
+impl InputShape {
+    /// Retrieves a stable `snake_case` label for this shape
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InputShape::LooksLikeUuid => "uuid",
+            InputShape::LooksLikeUlid => "ulid",
+            InputShape::LooksLikeBareXid => "bare_xid",
+            InputShape::Empty => "empty",
+            InputShape::TooLong => "too_long",
+            InputShape::Other => "other",
+        }
+    }
+}
+
+/// Categorizes the provided input by looking at its length and characters,
+/// without decoding it. Valid Pxids are classified as `InputShape::Other`.
+pub fn classify(s: &str) -> InputShape {
+    let s = s.trim();
+    let bytes = s.as_bytes();
+
+    if s.is_empty() {
+        return InputShape::Empty;
+    }
+
+    if bytes.len() > UUID_HYPHENATED_LENGTH {
+        return InputShape::TooLong;
+    }
+
+    if is_uuid(bytes) {
+        return InputShape::LooksLikeUuid;
+    }
+
+    if is_ulid(bytes) {
+        return InputShape::LooksLikeUlid;
+    }
+
+    if bytes.len() == XID_ENCODED_LENGTH && bytes.iter().all(|b| ENCODING_CHARS.contains(b)) {
+        return InputShape::LooksLikeBareXid;
+    }
+
+    InputShape::Other
+}
+
+fn is_uuid(bytes: &[u8]) -> bool {
+    match bytes.len() {
+        UUID_HYPHENATED_LENGTH => bytes.iter().enumerate().all(|(idx, b)| {
+            if UUID_HYPHENS.contains(&idx) {
+                *b == b'-'
+            } else {
+                b.is_ascii_hexdigit()
+            }
+        }),
+        UUID_SIMPLE_LENGTH => bytes.iter().all(u8::is_ascii_hexdigit),
+        _ => false,
+    }
+}
+
+/// ULIDs are 26 Crockford's Base32 characters, which excludes `I`, `L`, `O`
+/// and `U`, where the first one is at most `7`
+fn is_ulid(bytes: &[u8]) -> bool {
+    bytes.len() == ULID_ENCODED_LENGTH
+        && matches!(bytes.first(), Some(b'0'..=b'7'))
+        && bytes.iter().all(|b| {
+            b.is_ascii_alphanumeric()
+                && !matches!(b.to_ascii_uppercase(), b'I' | b'L' | b'O' | b'U')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_uuids() {
+        for input in [
+            "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "67E55044-10B1-426F-9247-BB680E5FE0C8",
+            "67e5504410b1426f9247bb680e5fe0c8",
+        ] {
+            assert_eq!(classify(input), InputShape::LooksLikeUuid, "{input}");
+        }
+    }
+
+    #[test]
+    fn classifies_ulids() {
+        for input in ["01ARZ3NDEKTSV4RRFFQ69G5FAV", "01arz3ndektsv4rrffq69g5fav"] {
+            assert_eq!(classify(input), InputShape::LooksLikeUlid, "{input}");
+        }
+    }
+
+    #[test]
+    fn classifies_bare_xids() {
+        assert_eq!(
+            classify("9m4e2mr0ui3e8a215n4g"),
+            InputShape::LooksLikeBareXid
+        );
+    }
+
+    #[test]
+    fn classifies_empty_inputs() {
+        assert_eq!(classify(""), InputShape::Empty);
+        assert_eq!(classify(" \t\n"), InputShape::Empty);
+    }
+
+    #[test]
+    fn classifies_too_long_inputs() {
+        assert_eq!(classify(&"a".repeat(37)), InputShape::TooLong);
+    }
+
+    #[test]
+    fn classifies_other_inputs() {
+        for input in [
+            "acct_9m4e2mr0ui3e8a215n4g",
+            "acct_9m4e2mr0",
+            "9m4e2mr0ui3e8a215n4x",
+            "67e55044-10b1-426f-9247-bb680e5fe0cz",
+            "81ARZ3NDEKTSV4RRFFQ69G5FAV",
+            "01ARZ3NDEKTSV4RRFFQ69G5FAU",
+        ] {
+            assert_eq!(classify(input), InputShape::Other, "{input}");
+        }
+    }
+
+    #[test]
+    fn labels_are_stable() {
+        assert_eq!(InputShape::LooksLikeUuid.as_str(), "uuid");
+        assert_eq!(InputShape::LooksLikeUlid.as_str(), "ulid");
+        assert_eq!(InputShape::LooksLikeBareXid.as_str(), "bare_xid");
+        assert_eq!(InputShape::Empty.as_str(), "empty");
+        assert_eq!(InputShape::TooLong.as_str(), "too_long");
+        assert_eq!(InputShape::Other.as_str(), "other");
+    }
+}
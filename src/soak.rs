@@ -0,0 +1,665 @@
+//! Soak testing for `Factory` instances, available with the `test-util`
+//! feature. Mints IDs from several threads for a while and checks them for
+//! invariants, meant to run before each release.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use pxid::soak::{self, SoakConfig};
+//!
+//! let report = soak::run(SoakConfig::duration(Duration::from_secs(600)).threads(8));
+//!
+//! assert!(report.is_ok(), "{:?}", report.violations);
+//! ```
+//!
+//! # Uniqueness
+//!
+//! Keeping every ID minted during a long run is not feasible, so uniqueness
+//! is checked with bounded memory by two structures:
+//!
+//! 1. A Bloom filter of `bloom_bytes` holding every ID, which flags IDs as
+//!    possibly seen before.
+//! 2. An exact set holding every ID selected by a hash based sample, up to
+//!    `exact_cap` IDs. The sample starts with every ID, and each time the set
+//!    is full the sample is halved and IDs out of it are dropped.
+//!
+//! Both copies of a duplicate share the same hash, so they are either both
+//! in the sample or both out of it. This classifies Bloom filter hits as:
+//!
+//! | Hit                                 | Outcome                            |
+//! | ----------------------------------- | ---------------------------------- |
+//! | Sampled ID found on the exact set   | Confirmed duplicate, a violation   |
+//! | Sampled ID missing on the exact set | False positive of the Bloom filter |
+//! | ID out of the sample                | Unverified, counted on the report  |
+//!
+//! Runs minting less than `exact_cap` IDs check uniqueness exactly.
+use std::collections::HashSet;
+use std::mem::size_of;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+use crate::factory::{splitmix64, Factory};
+use crate::id::Pxid;
+
+/// Amount of hash functions used by the Bloom filter
+const BLOOM_HASHES: u64 = 4;
+
+/// Amount of IDs minted by a thread between uniqueness checks
+const BATCH_SIZE: usize = 1_024;
+
+/// Max amount of violations held by `SoakReport::violations`
+pub const MAX_REPORTED_VIOLATIONS: usize = 1_000;
+
+/// When a soak run stops
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoakLimit {
+    /// Mint IDs for the provided time
+    Duration(Duration),
+
+    /// Mint the provided amount of IDs
+    Count(u64),
+}
+
+/// Configuration for `run`
+#[derive(Clone)]
+pub struct SoakConfig {
+    limit: SoakLimit,
+    threads: usize,
+    prefixes: Vec<String>,
+    factory: Option<Arc<Factory>>,
+    check_uniqueness: bool,
+    check_monotonicity: bool,
+    round_trip_rate: f64,
+    bloom_bytes: usize,
+    exact_cap: usize,
+}
+
+impl SoakConfig {
+    /// Creates a `SoakConfig` minting IDs for the provided `duration`
+    pub fn duration(duration: Duration) -> Self {
+        Self::new(SoakLimit::Duration(duration))
+    }
+
+    /// Creates a `SoakConfig` minting `count` IDs
+    pub fn count(count: u64) -> Self {
+        Self::new(SoakLimit::Count(count))
+    }
+
+    fn new(limit: SoakLimit) -> Self {
+        Self {
+            limit,
+            threads: 4,
+            prefixes: vec![String::from("soak")],
+            factory: None,
+            check_uniqueness: true,
+            check_monotonicity: true,
+            round_trip_rate: 0.01,
+            bloom_bytes: 16 * 1024 * 1024,
+            exact_cap: 1_000_000,
+        }
+    }
+
+    /// Sets the amount of threads minting IDs, at least one thread is used
+    #[must_use]
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Sets the prefixes IDs are minted for, each thread cycles through them
+    #[must_use]
+    pub fn prefixes<I, S>(mut self, prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.prefixes = prefixes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the `Factory` minting IDs, a `Factory` created with
+    /// `Factory::new_or_nil` is used otherwise
+    #[must_use]
+    pub fn factory(mut self, factory: Arc<Factory>) -> Self {
+        self.factory = Some(factory);
+        self
+    }
+
+    /// Sets whether minted IDs are checked to be unique
+    #[must_use]
+    pub fn check_uniqueness(mut self, enabled: bool) -> Self {
+        self.check_uniqueness = enabled;
+        self
+    }
+
+    /// Sets whether IDs minted by each thread for a prefix are checked to be
+    /// strictly increasing
+    #[must_use]
+    pub fn check_monotonicity(mut self, enabled: bool) -> Self {
+        self.check_monotonicity = enabled;
+        self
+    }
+
+    /// Sets the fraction of minted IDs checked to round trip through
+    /// `Display` and `FromStr`, `0.0` disables the check and `1.0` checks
+    /// every ID
+    #[must_use]
+    pub fn round_trip_rate(mut self, rate: f64) -> Self {
+        self.round_trip_rate = rate;
+        self
+    }
+
+    /// Sets the memory used by the uniqueness Bloom filter
+    #[must_use]
+    pub fn bloom_bytes(mut self, bytes: usize) -> Self {
+        self.bloom_bytes = bytes;
+        self
+    }
+
+    /// Sets the max amount of IDs held by the uniqueness exact set
+    #[must_use]
+    pub fn exact_cap(mut self, cap: usize) -> Self {
+        self.exact_cap = cap;
+        self
+    }
+
+    /// Retrieves the amount of minted IDs between round trip checks
+    // The rate is clamped to `(0, 1]`, so the interval is a positive integer
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn round_trip_interval(&self) -> Option<u64> {
+        if self.round_trip_rate > 0.0 {
+            Some((1.0 / self.round_trip_rate.min(1.0)).round() as u64)
+        } else {
+            None
+        }
+    }
+}
+
+/// Invariant broken by a minted ID
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SoakViolation {
+    /// ID was minted more than once
+    Duplicate(Pxid),
+
+    /// ID is not greater than the previous ID minted by the same thread for
+    /// the same prefix, other than by the Counter wrapping within a second
+    NotMonotonic { previous: Pxid, current: Pxid },
+
+    /// ID doesn't decode back from its string form
+    RoundTrip(Pxid),
+
+    /// `Factory` failed to mint an ID for the prefix
+    Generation(String, Error),
+}
+
+/// Outcome of a soak run
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SoakReport {
+    /// Amount of IDs minted
+    pub generated: u64,
+
+    /// Time spent minting and checking IDs
+    pub elapsed: Duration,
+
+    /// Violations found, holds up to `MAX_REPORTED_VIOLATIONS` values
+    pub violations: Vec<SoakViolation>,
+
+    /// Amount of violations found, including the ones not held by
+    /// `violations`
+    pub violation_count: u64,
+
+    /// Amount of IDs checked to round trip
+    pub round_trips: u64,
+
+    /// Amount of Bloom filter hits proven to be false positives
+    pub false_positives: u64,
+
+    /// Amount of Bloom filter hits on IDs out of the exact set sample, which
+    /// may be duplicates but can't be confirmed
+    pub unverified: u64,
+
+    /// Max amount of bytes used by the uniqueness checks
+    pub memory_high_water: usize,
+}
+
+impl SoakReport {
+    /// Retrieves the amount of IDs minted per second
+    #[allow(clippy::cast_precision_loss)]
+    pub fn throughput(&self) -> f64 {
+        self.generated as f64 / self.elapsed.max(Duration::from_nanos(1)).as_secs_f64()
+    }
+
+    /// Checks whether no violation was found
+    pub fn is_ok(&self) -> bool {
+        self.violation_count == 0
+    }
+
+    fn push(&mut self, violation: SoakViolation) {
+        self.violation_count += 1;
+
+        if self.violations.len() < MAX_REPORTED_VIOLATIONS {
+            self.violations.push(violation);
+        }
+    }
+
+    /// Adds the tallies of a single minting thread
+    fn merge(&mut self, other: SoakReport) {
+        let available = MAX_REPORTED_VIOLATIONS.saturating_sub(self.violations.len());
+
+        self.generated += other.generated;
+        self.violation_count += other.violation_count;
+        self.violations
+            .extend(other.violations.into_iter().take(available));
+        self.round_trips += other.round_trips;
+        self.false_positives += other.false_positives;
+        self.unverified += other.unverified;
+    }
+}
+
+/// Outcome of checking an ID with `UniquenessChecker::insert`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Seen {
+    /// ID was not seen before
+    New,
+
+    /// ID was seen before
+    Duplicate,
+
+    /// Bloom filter flagged the ID, but it was not seen before
+    FalsePositive,
+
+    /// Bloom filter flagged the ID, which is out of the exact set sample
+    Unverified,
+}
+
+/// Bloom filter over IDs using double hashing
+struct BloomFilter {
+    words: Vec<u64>,
+}
+
+impl BloomFilter {
+    fn new(bytes: usize) -> Self {
+        Self {
+            words: vec![0; (bytes / size_of::<u64>()).max(1)],
+        }
+    }
+
+    /// Sets the bits for `hash`, retrieving whether every one of them was
+    /// already set
+    fn insert(&mut self, (h1, h2): (u64, u64)) -> bool {
+        let bits = self.words.len() as u64 * 64;
+        let mut present = true;
+
+        for idx in 0..BLOOM_HASHES {
+            let bit = h1.wrapping_add(idx.wrapping_mul(h2)) % bits;
+
+            if let Some(word) = self.words.get_mut((bit / 64) as usize) {
+                let mask = 1 << (bit % 64);
+
+                present &= *word & mask != 0;
+                *word |= mask;
+            }
+        }
+
+        present
+    }
+
+    fn memory(&self) -> usize {
+        self.words.len() * size_of::<u64>()
+    }
+}
+
+/// Uniqueness check with bounded memory, as described on the module docs
+struct UniquenessChecker {
+    bloom: BloomFilter,
+    exact: HashSet<Pxid>,
+    exact_cap: usize,
+    /// Leading zero bits the first hash must have for an ID to be sampled
+    level: u32,
+    memory_high_water: usize,
+}
+
+impl UniquenessChecker {
+    fn new(bloom_bytes: usize, exact_cap: usize) -> Self {
+        let mut checker = Self {
+            bloom: BloomFilter::new(bloom_bytes),
+            exact: HashSet::new(),
+            exact_cap,
+            level: 0,
+            memory_high_water: 0,
+        };
+
+        checker.track_memory();
+        checker
+    }
+
+    // Truncation splits the packed bytes into their 64-bit halves
+    #[allow(clippy::cast_possible_truncation)]
+    fn hash(id: &Pxid) -> (u64, u64) {
+        let bytes = u128::from_be_bytes(id.0);
+        let mut state = (bytes >> 64) as u64;
+        let mut state = splitmix64(&mut state) ^ bytes as u64;
+        let h1 = splitmix64(&mut state);
+
+        (h1, splitmix64(&mut state) | 1)
+    }
+
+    fn sampled(&self, (h1, _): (u64, u64)) -> bool {
+        h1.leading_zeros() >= self.level
+    }
+
+    fn insert(&mut self, id: Pxid) -> Seen {
+        let hash = Self::hash(&id);
+        let flagged = self.bloom.insert(hash);
+
+        if !self.sampled(hash) {
+            return if flagged { Seen::Unverified } else { Seen::New };
+        }
+
+        if self.exact.contains(&id) {
+            return Seen::Duplicate;
+        }
+
+        self.insert_exact(id);
+
+        if flagged {
+            Seen::FalsePositive
+        } else {
+            Seen::New
+        }
+    }
+
+    /// Inserts a sampled ID, halving the sample while the exact set is full
+    fn insert_exact(&mut self, id: Pxid) {
+        while self.exact.len() >= self.exact_cap && self.level < u64::BITS {
+            self.level += 1;
+
+            let level = self.level;
+
+            self.exact
+                .retain(|id| Self::hash(id).0.leading_zeros() >= level);
+        }
+
+        if self.sampled(Self::hash(&id)) && self.exact.len() < self.exact_cap {
+            self.exact.insert(id);
+            self.track_memory();
+        }
+    }
+
+    fn track_memory(&mut self) {
+        // Each `HashSet` slot holds the value along with a control byte
+        let exact = self.exact.capacity() * (size_of::<Pxid>() + 1);
+
+        self.memory_high_water = self.memory_high_water.max(self.bloom.memory() + exact);
+    }
+}
+
+/// Mints IDs as configured by `config` and checks them for invariants
+// Taken by value so configs are built inline on the call
+#[allow(clippy::needless_pass_by_value)]
+pub fn run(config: SoakConfig) -> SoakReport {
+    let factory = config
+        .factory
+        .clone()
+        .unwrap_or_else(|| Arc::new(Factory::new_or_nil()));
+    let uniqueness = Mutex::new(UniquenessChecker::new(config.bloom_bytes, config.exact_cap));
+    let remaining = AtomicU64::new(match config.limit {
+        SoakLimit::Count(count) => count,
+        SoakLimit::Duration(_) => u64::MAX,
+    });
+    let start = Instant::now();
+    let mut report = SoakReport::default();
+
+    thread::scope(|scope| {
+        let handles = (0..config.threads)
+            .map(|_| scope.spawn(|| mint(&config, &factory, &uniqueness, &remaining, start)))
+            .collect::<Vec<_>>();
+
+        for worker in handles.into_iter().filter_map(|handle| handle.join().ok()) {
+            report.merge(worker);
+        }
+    });
+
+    report.elapsed = start.elapsed();
+    report.memory_high_water = uniqueness
+        .into_inner()
+        .unwrap_or_else(PoisonError::into_inner)
+        .memory_high_water;
+
+    report
+}
+
+/// Mints IDs on a single thread until the limit is reached, retrieving the
+/// tallies of this thread
+fn mint(
+    config: &SoakConfig,
+    factory: &Factory,
+    uniqueness: &Mutex<UniquenessChecker>,
+    remaining: &AtomicU64,
+    start: Instant,
+) -> SoakReport {
+    let mut report = SoakReport::default();
+
+    if config.prefixes.is_empty() {
+        return report;
+    }
+
+    let mut previous: Vec<Option<Pxid>> = vec![None; config.prefixes.len()];
+    let mut batch = Vec::with_capacity(BATCH_SIZE);
+    let round_trip_interval = config.round_trip_interval();
+
+    loop {
+        if let SoakLimit::Duration(duration) = config.limit {
+            if start.elapsed() >= duration {
+                break;
+            }
+        }
+
+        let Ok(left) = remaining.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| {
+            (left > 0).then(|| left - left.min(BATCH_SIZE as u64))
+        }) else {
+            break;
+        };
+
+        let batch_prefixes = (0..config.prefixes.len())
+            .cycle()
+            .take(usize::try_from(left).map_or(BATCH_SIZE, |left| left.min(BATCH_SIZE)));
+
+        for idx in batch_prefixes {
+            let (Some(prefix), Some(previous)) = (config.prefixes.get(idx), previous.get_mut(idx))
+            else {
+                continue;
+            };
+            let id = match factory.new_id(prefix) {
+                Ok(id) => id,
+                Err(err) => {
+                    report.push(SoakViolation::Generation(prefix.clone(), err));
+                    continue;
+                }
+            };
+
+            report.generated += 1;
+
+            if config.check_monotonicity {
+                if let Some(prev) =
+                    previous.filter(|prev| *prev >= id && !counter_wrapped(prev, &id))
+                {
+                    report.push(SoakViolation::NotMonotonic {
+                        previous: prev,
+                        current: id,
+                    });
+                }
+
+                *previous = Some(id);
+            }
+
+            if let Some(interval) = round_trip_interval {
+                if report.generated % interval == 0 {
+                    report.round_trips += 1;
+
+                    if Pxid::from_str(&id.to_string()) != Ok(id) {
+                        report.push(SoakViolation::RoundTrip(id));
+                    }
+                }
+            }
+
+            if config.check_uniqueness {
+                batch.push(id);
+            }
+        }
+
+        if !batch.is_empty() {
+            let mut uniqueness = uniqueness.lock().unwrap_or_else(PoisonError::into_inner);
+
+            for id in batch.drain(..) {
+                match uniqueness.insert(id) {
+                    Seen::New => {}
+                    Seen::Duplicate => report.push(SoakViolation::Duplicate(id)),
+                    Seen::FalsePositive => report.false_positives += 1,
+                    Seen::Unverified => report.unverified += 1,
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Checks whether `current` only sorts before `previous` because the shared
+/// Counter wrapped to zero within the same second
+fn counter_wrapped(previous: &Pxid, current: &Pxid) -> bool {
+    previous.prefix_bytes() == current.prefix_bytes()
+        && previous.timestamp() == current.timestamp()
+        && previous.machine_id() == current.machine_id()
+        && previous.process_id() == current.process_id()
+        && current.counter() < previous.counter()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::factory::{Factory, COUNTER_MAX};
+
+    use super::*;
+
+    fn distinct_ids(count: u32) -> Vec<Pxid> {
+        (0..count)
+            .map(|counter| Pxid::from_parts("soak", 1_700_000_000, [1, 2, 3], 7, counter).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn confirms_duplicates_below_exact_cap() {
+        let mut checker = UniquenessChecker::new(64 * 1024, 1_000);
+        let ids = distinct_ids(500);
+
+        for id in &ids {
+            assert_eq!(checker.insert(*id), Seen::New);
+        }
+
+        assert_eq!(checker.insert(ids[42]), Seen::Duplicate);
+        assert_eq!(checker.insert(ids[0]), Seen::Duplicate);
+        assert_eq!(checker.level, 0);
+        assert_eq!(checker.exact.len(), 500);
+    }
+
+    #[test]
+    fn classifies_saturated_bloom_hits_as_false_positives() {
+        let mut checker = UniquenessChecker::new(8, 10_000);
+        let outcomes = distinct_ids(2_000)
+            .into_iter()
+            .map(|id| checker.insert(id))
+            .collect::<Vec<Seen>>();
+
+        assert!(outcomes.contains(&Seen::FalsePositive));
+        assert!(!outcomes.contains(&Seen::Duplicate));
+        assert!(!outcomes.contains(&Seen::Unverified));
+    }
+
+    #[test]
+    fn halves_sample_to_stay_within_exact_cap() {
+        let mut checker = UniquenessChecker::new(1024 * 1024, 100);
+        let ids = distinct_ids(10_000);
+
+        for id in &ids {
+            assert_eq!(checker.insert(*id), Seen::New);
+            assert!(checker.exact.len() <= 100);
+        }
+
+        assert!(checker.level > 0);
+
+        let (sampled, unsampled): (Vec<Pxid>, Vec<Pxid>) = ids
+            .iter()
+            .partition(|id| checker.sampled(UniquenessChecker::hash(id)));
+
+        assert!(!sampled.is_empty());
+        assert_eq!(checker.exact.len(), sampled.len());
+
+        for id in sampled {
+            assert_eq!(checker.insert(id), Seen::Duplicate);
+        }
+
+        for id in unsampled.into_iter().take(100) {
+            assert_eq!(checker.insert(id), Seen::Unverified);
+        }
+    }
+
+    #[test]
+    fn keeps_memory_bounded() {
+        let mut checker = UniquenessChecker::new(4096, 64);
+
+        for id in distinct_ids(50_000) {
+            checker.insert(id);
+        }
+
+        assert_eq!(checker.bloom.memory(), 4096);
+        assert!(checker.memory_high_water < 4096 + 256 * size_of::<Pxid>());
+    }
+
+    #[test]
+    fn tolerates_counter_wrapping_within_a_second() {
+        let last = Pxid::from_parts("soak", 1_700_000_000, [1, 2, 3], 7, COUNTER_MAX).unwrap();
+        let wrapped = Pxid::from_parts("soak", 1_700_000_000, [1, 2, 3], 7, 0).unwrap();
+        let other = Pxid::from_parts("soak", 1_699_999_999, [1, 2, 3], 7, 0).unwrap();
+
+        assert!(counter_wrapped(&last, &wrapped));
+        assert!(!counter_wrapped(&wrapped, &last));
+        assert!(!counter_wrapped(&last, &other));
+    }
+
+    #[test]
+    fn runs_on_provided_factory() {
+        let report = run(SoakConfig::count(5_000)
+            .threads(2)
+            .prefixes(["acct", "dog"])
+            .round_trip_rate(0.1)
+            .factory(Arc::new(Factory::deterministic(7))));
+
+        assert_eq!(report.generated, 5_000);
+        // Each thread samples its own IDs, so up to one sample per thread is
+        // lost to rounding
+        assert!((499..=500).contains(&report.round_trips));
+        assert!(report.is_ok(), "{:?}", report.violations);
+        assert!(report.memory_high_water > 0);
+    }
+
+    #[test]
+    fn reports_violations() {
+        let report = run(SoakConfig::count(10)
+            .threads(1)
+            .prefixes(["acct", "account"])
+            .check_uniqueness(false)
+            .round_trip_rate(0.0));
+
+        assert_eq!(report.generated, 5);
+        assert_eq!(report.violation_count, 5);
+        assert_eq!(report.round_trips, 0);
+        assert!(matches!(
+            &report.violations[0],
+            SoakViolation::Generation(prefix, Error::Decode(_)) if prefix == "account"
+        ));
+    }
+}
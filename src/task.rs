@@ -0,0 +1,115 @@
+//! Task-local Counters for IDs generated from Tokio tasks.
+//!
+//! IDs generated with `generate_id_async` within a `scope` draw their
+//! Counter from a value local to the task instead of the shared Counter,
+//! which avoids contention between tasks generating IDs concurrently.
+//!
+//! # Tradeoff
+//!
+//! The task-local Counter is seeded from the shared Counter on first use
+//! and then incremented locally, so Counters of different tasks are not
+//! coordinated. Once a task advances past the seed of another task, both
+//! can use the same Counter values, and IDs generated within the same
+//! second on the same process can collide. Keep task-local Counters for
+//! tasks generating a small amount of IDs each, and use a `Factory` when
+//! IDs must never collide within a process.
+//!
+//! ```no_run
+//! use pxid::task::{generate_id_async, scope};
+//!
+//! # async fn run() -> pxid::Result<()> {
+//! let id = scope(async { generate_id_async("evnt").await }).await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::cell::Cell;
+use std::future::Future;
+use std::time::SystemTime;
+
+use crate::epoch::Epoch;
+use crate::factory::COUNTER_MAX;
+use crate::id::Pxid;
+use crate::Result;
+
+tokio::task_local! {
+    /// Next Counter for the current task, `None` until it is seeded
+    static COUNTER: Cell<Option<u32>>;
+}
+
+/// Runs `future` with its own task-local Counter, used by
+/// `generate_id_async` calls within it
+pub async fn scope<F: Future>(future: F) -> F::Output {
+    COUNTER.scope(Cell::new(None), future).await
+}
+
+/// Generates a Pxid with the provided `prefix` using the task-local Counter
+/// of the current `scope`, seeding it from the shared Counter on first use.
+///
+/// Outside of a `scope` the shared Counter is used, as done by `Pxid::new`.
+// Nothing is awaited, but the function is async so it composes with the
+// futures run within `scope`, as its task-local Counter is only reachable
+// from them
+#[allow(clippy::unused_async)]
+pub async fn generate_id_async(prefix: &str) -> Result<Pxid> {
+    let counter = COUNTER
+        .try_with(|counter| {
+            let value = counter.get().unwrap_or_else(Pxid::read_counter) & COUNTER_MAX;
+
+            counter.set(Some(value.wrapping_add(1) & COUNTER_MAX));
+            value
+        })
+        .unwrap_or_else(|_| Pxid::read_counter());
+
+    Pxid::from_parts(
+        prefix,
+        Epoch::Unix.timestamp(SystemTime::now())?,
+        Pxid::read_machine_id()?,
+        Pxid::read_process_id(),
+        counter,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn increments_task_local_counter() {
+        let ids = scope(async {
+            let mut ids = Vec::new();
+
+            for _ in 0..100 {
+                ids.push(generate_id_async("evnt").await.unwrap());
+            }
+
+            ids
+        })
+        .await;
+
+        assert_eq!(ids.iter().collect::<HashSet<&Pxid>>().len(), 100);
+
+        for (previous, next) in ids.iter().zip(ids.iter().skip(1)) {
+            assert_eq!(next.counter(), (previous.counter() + 1) & COUNTER_MAX);
+        }
+    }
+
+    #[tokio::test]
+    async fn seeds_each_scope_separately() {
+        let first = scope(async { generate_id_async("evnt").await.unwrap() }).await;
+        let second = scope(async { generate_id_async("evnt").await.unwrap() }).await;
+
+        assert_eq!(first.prefix_bytes(), *b"evnt");
+        assert_eq!(second.prefix_bytes(), *b"evnt");
+        assert_ne!(first, second);
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_shared_counter_outside_scope() {
+        let id = generate_id_async("evnt").await.unwrap();
+
+        assert_eq!(id.prefix_bytes(), *b"evnt");
+        assert!(generate_id_async("events").await.is_err());
+    }
+}
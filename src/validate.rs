@@ -0,0 +1,390 @@
+//! Application specific invariants checked on IDs when parsed or generated.
+//!
+//! ```no_run
+//! use pxid::{NotNil, PrefixAllowList, Pxid, TimestampAfter};
+//!
+//! # fn main() -> Result<(), pxid::ParseOrValidationError> {
+//! # let launch_date = std::time::UNIX_EPOCH;
+//! # let input = "acct_9m4e2mr0ui3e8a215n4g";
+//! let prefixes = PrefixAllowList::new(["acct", "ordr"]);
+//! let launch = TimestampAfter(launch_date);
+//! let id = Pxid::from_str_validated(input, &[&NotNil, &prefixes, &launch])?;
+//! # Ok(())
+//! # }
+//! ```
+use std::str::{from_utf8, FromStr};
+use std::time::SystemTime;
+
+use thiserror::Error;
+
+use crate::error::Error;
+use crate::id::Pxid;
+
+/// Invariant not held by an ID, as reported by a `Validator`
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error("{code}: {message}")]
+pub struct ValidationError {
+    /// Stable identifier of the violated invariant, suitable for matching
+    /// and metric tags
+    pub code: &'static str,
+
+    /// Human readable description of the violation
+    pub message: String,
+}
+
+impl ValidationError {
+    /// Creates a `ValidationError` with the provided `code` and `message`
+    pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Failure of `Pxid::from_str_validated`, either decoding the ID or
+/// validating it
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ParseOrValidationError {
+    /// The provided `String` couldn't be decoded into a Pxid
+    #[error(transparent)]
+    Parse(Error),
+
+    /// The decoded Pxid failed validation, holds every failure collected
+    #[error("Pxid failed validation. {}", display_all(.0))]
+    Validation(Vec<ValidationError>),
+}
+
+impl From<Error> for ParseOrValidationError {
+    fn from(value: Error) -> Self {
+        ParseOrValidationError::Parse(value)
+    }
+}
+
+impl From<ParseOrValidationError> for Error {
+    fn from(value: ParseOrValidationError) -> Self {
+        match value {
+            ParseOrValidationError::Parse(err) => err,
+            ParseOrValidationError::Validation(errors) => Error::Validation(errors),
+        }
+    }
+}
+
+impl From<ValidationError> for Error {
+    fn from(value: ValidationError) -> Self {
+        Error::Validation(vec![value])
+    }
+}
+
+/// Joins the provided `ValidationError` values into a single line
+pub(crate) fn display_all(errors: &[ValidationError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join("; ")
+}
+
+/// Application specific invariant checked on IDs.
+///
+/// Closures taking a `&Pxid` and returning `Result<(), ValidationError>`
+/// are validators as well.
+pub trait Validator {
+    /// Checks `id` holds the invariant of this validator
+    fn validate(&self, id: &Pxid) -> Result<(), ValidationError>;
+}
+
+impl<F> Validator for F
+where
+    F: Fn(&Pxid) -> Result<(), ValidationError>,
+{
+    fn validate(&self, id: &Pxid) -> Result<(), ValidationError> {
+        self(id)
+    }
+}
+
+/// Accepts IDs whose prefix is one of the allowed prefixes
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PrefixAllowList(Vec<String>);
+
+impl PrefixAllowList {
+    /// Creates a `PrefixAllowList` allowing the provided `prefixes`
+    pub fn new<I, S>(prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self(prefixes.into_iter().map(Into::into).collect())
+    }
+
+    /// Retrieves the allowed prefixes
+    pub fn prefixes(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl Validator for PrefixAllowList {
+    fn validate(&self, id: &Pxid) -> Result<(), ValidationError> {
+        let prefix_bytes = id.prefix_bytes();
+        let prefix = from_utf8(&prefix_bytes)
+            .unwrap_or_default()
+            .trim_end_matches('\0');
+
+        if self.0.iter().any(|allowed| allowed == prefix) {
+            return Ok(());
+        }
+
+        Err(ValidationError::new(
+            "prefix_not_allowed",
+            format!("Prefix {:?} is not one of: {}.", prefix, self.0.join(", ")),
+        ))
+    }
+}
+
+/// Accepts IDs with a Timestamp not before the provided time, read as done
+/// by `Pxid::timestamp`.
+///
+/// Timestamps exactly at the provided time are accepted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimestampAfter(pub SystemTime);
+
+impl Validator for TimestampAfter {
+    fn validate(&self, id: &Pxid) -> Result<(), ValidationError> {
+        match self.0.duration_since(id.timestamp()) {
+            Ok(behind) if !behind.is_zero() => Err(ValidationError::new(
+                "timestamp_too_early",
+                format!(
+                    "Timestamp is {}s before the earliest allowed time.",
+                    behind.as_secs()
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Accepts any ID other than the nil Pxid
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NotNil;
+
+impl Validator for NotNil {
+    fn validate(&self, id: &Pxid) -> Result<(), ValidationError> {
+        if id.is_nil() {
+            return Err(ValidationError::new("nil", "Expected a non nil Pxid."));
+        }
+
+        Ok(())
+    }
+}
+
+impl Pxid {
+    /// Runs `validators` in order against this Pxid.
+    ///
+    /// Stops on the first failure unless `collect_all` is set, in which case
+    /// every validator runs and every failure is retrieved.
+    pub fn validate_with(
+        &self,
+        validators: &[&dyn Validator],
+        collect_all: bool,
+    ) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        for validator in validators {
+            if let Err(err) = validator.validate(self) {
+                errors.push(err);
+
+                if !collect_all {
+                    break;
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Decodes a Pxid as done by `FromStr` and runs `validators` in order
+    /// against it, stopping on the first failure
+    pub fn from_str_validated(
+        s: &str,
+        validators: &[&dyn Validator],
+    ) -> Result<Pxid, ParseOrValidationError> {
+        Self::parse_validated(s, validators, false)
+    }
+
+    /// Decodes a Pxid as done by `FromStr` and runs every validator in
+    /// `validators` against it, collecting every failure
+    pub fn from_str_validated_all(
+        s: &str,
+        validators: &[&dyn Validator],
+    ) -> Result<Pxid, ParseOrValidationError> {
+        Self::parse_validated(s, validators, true)
+    }
+
+    fn parse_validated(
+        s: &str,
+        validators: &[&dyn Validator],
+        collect_all: bool,
+    ) -> Result<Pxid, ParseOrValidationError> {
+        let id = Pxid::from_str(s)?;
+
+        id.validate_with(validators, collect_all)
+            .map_err(ParseOrValidationError::Validation)?;
+
+        Ok(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::factory::Factory;
+
+    use super::*;
+
+    const ACCOUNT: &str = "acct_9m4e2mr0ui3e8a215n4g";
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn checks_prefix_allow_list() {
+        let allowed = PrefixAllowList::new(["acct", "dog"]);
+        let dog = Factory::deterministic(7).new_id("dog").unwrap();
+
+        assert_eq!(allowed.validate(&Pxid::from_str(ACCOUNT).unwrap()), Ok(()));
+        assert_eq!(allowed.validate(&dog), Ok(()));
+        assert_eq!(
+            allowed.validate(&Pxid::from_str("user_9m4e2mr0ui3e8a215n4g").unwrap()),
+            Err(ValidationError::new(
+                "prefix_not_allowed",
+                "Prefix \"user\" is not one of: acct, dog."
+            ))
+        );
+    }
+
+    #[test]
+    fn checks_timestamp_after() {
+        let id = Pxid::from_str(ACCOUNT).unwrap();
+        let secs = id.timestamp().duration_since(UNIX_EPOCH).unwrap().as_secs();
+
+        assert_eq!(TimestampAfter(at(secs - 1)).validate(&id), Ok(()));
+        assert_eq!(TimestampAfter(at(secs)).validate(&id), Ok(()));
+        assert_eq!(
+            TimestampAfter(at(secs + 90)).validate(&id),
+            Err(ValidationError::new(
+                "timestamp_too_early",
+                "Timestamp is 90s before the earliest allowed time."
+            ))
+        );
+    }
+
+    #[test]
+    fn checks_not_nil() {
+        assert_eq!(NotNil.validate(&Pxid::from_str(ACCOUNT).unwrap()), Ok(()));
+        assert_eq!(NotNil.validate(&Pxid::nil()).unwrap_err().code, "nil");
+    }
+
+    #[test]
+    fn runs_validators_in_order() {
+        let calls = Cell::new(0);
+        let counting = |_: &Pxid| {
+            calls.set(calls.get() + 1);
+            Ok(())
+        };
+        let nil = Pxid::nil();
+        let allowed = PrefixAllowList::new(["acct"]);
+        let validators: [&dyn Validator; 3] = [&NotNil, &allowed, &counting];
+
+        let errors = nil.validate_with(&validators, false).unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "nil");
+        assert_eq!(calls.get(), 0);
+
+        let errors = nil.validate_with(&validators, true).unwrap_err();
+
+        assert_eq!(
+            errors.iter().map(|err| err.code).collect::<Vec<&str>>(),
+            ["nil", "prefix_not_allowed"]
+        );
+        assert_eq!(calls.get(), 1);
+
+        let id = Pxid::from_str(ACCOUNT).unwrap();
+
+        assert_eq!(id.validate_with(&validators, false), Ok(()));
+        assert_eq!(id.validate_with(&[], true), Ok(()));
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn parses_with_validators() {
+        let closed = |_: &Pxid| Err(ValidationError::new("closed", "Registrations are closed."));
+        let allowed = PrefixAllowList::new(["dog"]);
+
+        assert_eq!(
+            Pxid::from_str_validated(ACCOUNT, &[&NotNil]),
+            Ok(Pxid::from_str(ACCOUNT).unwrap())
+        );
+        assert!(matches!(
+            Pxid::from_str_validated("acct", &[&NotNil]),
+            Err(ParseOrValidationError::Parse(Error::Decode(_)))
+        ));
+
+        let Err(ParseOrValidationError::Validation(errors)) =
+            Pxid::from_str_validated(ACCOUNT, &[&allowed, &closed])
+        else {
+            panic!("Expected a validation error");
+        };
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].code, "prefix_not_allowed");
+
+        let Err(ParseOrValidationError::Validation(errors)) =
+            Pxid::from_str_validated_all(ACCOUNT, &[&allowed, &closed])
+        else {
+            panic!("Expected a validation error");
+        };
+
+        assert_eq!(
+            errors.iter().map(|err| err.code).collect::<Vec<&str>>(),
+            ["prefix_not_allowed", "closed"]
+        );
+    }
+
+    #[test]
+    fn converts_into_crate_error() {
+        let err = ValidationError::new("nil", "Expected a non nil Pxid.");
+
+        assert_eq!(
+            Error::from(err.clone()),
+            Error::Validation(vec![err.clone()])
+        );
+        assert_eq!(
+            Error::from(ParseOrValidationError::Validation(vec![
+                err.clone(),
+                err.clone()
+            ])),
+            Error::Validation(vec![err.clone(), err])
+        );
+
+        let parse = Pxid::from_str_validated("acct", &[]).unwrap_err();
+
+        assert_eq!(Error::from(parse), Pxid::from_str("acct").unwrap_err());
+
+        let err: Error = Pxid::from_str_validated_all(&Pxid::nil().to_string(), &[&NotNil])
+            .unwrap_err()
+            .into();
+
+        assert_eq!(
+            err.to_string(),
+            "Pxid failed validation. nil: Expected a non nil Pxid."
+        );
+    }
+}
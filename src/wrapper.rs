@@ -0,0 +1,350 @@
+//! Newtypes around `Pxid` with a fixed prefix, either through the
+//! `define_pxid_type!` macro or the `PxidWrapper` derive macro.
+//!
+//! Implementations depending on optional features are generated by the
+//! `macro_rules!` macros in this module, given that the derive macro crate
+//! cannot know which features of `pxid` are enabled.
+use crate::error::Error;
+use crate::id::{pad_prefix, Pxid};
+use crate::Result;
+
+/// Checks the prefix of the provided `Pxid` matches the `expected` one,
+/// prefixes shorter than 4 bytes are compared using NUL padding.
+///
+/// `expected` is lowercased before comparing, as done by `FromStr` with
+/// decoded prefixes.
+pub fn check_prefix(id: &Pxid, expected: &str) -> Result<()> {
+    if id.prefix_bytes() != pad_prefix(expected.to_ascii_lowercase().as_bytes()) {
+        let found = String::from_utf8_lossy(&id.prefix_bytes())
+            .trim_end_matches('\0')
+            .to_string();
+
+        return Err(Error::PrefixMismatch {
+            expected: expected.to_string(),
+            found,
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks the provided prefix holds no uppercase ASCII characters, so IDs
+/// created with it are kept as is by `FromStr`
+#[doc(hidden)]
+pub const fn is_lowercase_prefix(prefix: &str) -> bool {
+    let mut rest = prefix.as_bytes();
+
+    while let [first, tail @ ..] = rest {
+        if first.is_ascii_uppercase() {
+            return false;
+        }
+
+        rest = tail;
+    }
+
+    true
+}
+
+/// Defines a newtype around `Pxid` enforcing the provided prefix, so IDs
+/// of different entities can't be mixed up.
+///
+/// The generated type implements `Display`, `FromStr` and `TryFrom<&str>`
+/// checking the prefix, conversions from and into `Pxid`, and `Serialize`
+/// and `Deserialize` when the `serde` feature is enabled.
+///
+/// Prefixes must be lowercase, given `FromStr` lowercases decoded prefixes.
+///
+/// ```no_run
+/// pxid::define_pxid_type!(pub AccountId, "acct");
+/// pxid::define_pxid_type!(pub OrderId, "ordr");
+///
+/// # fn main() -> pxid::Result<()> {
+/// let account = AccountId::new()?;
+///
+/// assert!(account.to_string().parse::<OrderId>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! define_pxid_type {
+    ($(#[$meta:meta])* $vis:vis $name:ident, $prefix:literal) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        $vis struct $name($crate::Pxid);
+
+        const _: () = ::core::assert!(
+            !$prefix.is_empty() && $prefix.len() <= 4,
+            "prefix must have between 1 and 4 bytes"
+        );
+
+        const _: () = ::core::assert!(
+            $crate::__private::is_lowercase_prefix($prefix),
+            "prefix must be lowercase"
+        );
+
+        impl $name {
+            /// Prefix enforced on IDs of this type
+            pub const PREFIX: &'static str = $prefix;
+
+            /// Creates a new ID using the current timestamp and `PREFIX`
+            pub fn new() -> $crate::Result<Self> {
+                $crate::Pxid::new(Self::PREFIX).map(Self)
+            }
+
+            /// Retrieves the wrapped `Pxid`
+            pub fn into_inner(self) -> $crate::Pxid {
+                self.0
+            }
+        }
+
+        impl ::core::convert::TryFrom<$crate::Pxid> for $name {
+            type Error = $crate::Error;
+
+            fn try_from(value: $crate::Pxid) -> ::core::result::Result<Self, Self::Error> {
+                $crate::__private::check_prefix(&value, Self::PREFIX)?;
+
+                ::core::result::Result::Ok(Self(value))
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::Error;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                let value = <$crate::Pxid as ::core::str::FromStr>::from_str(s)?;
+
+                <Self as ::core::convert::TryFrom<$crate::Pxid>>::try_from(value)
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<&'a str> for $name {
+            type Error = $crate::Error;
+
+            fn try_from(value: &'a str) -> ::core::result::Result<Self, Self::Error> {
+                <Self as ::core::str::FromStr>::from_str(value)
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl ::core::convert::From<$name> for $crate::Pxid {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        $crate::__pxid_wrapper_serde!($name);
+        $crate::__pxid_wrapper_graphql!($name);
+    };
+}
+
+/// Implements `Serialize` and `Deserialize` using the string form of the
+/// wrapped `Pxid`
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pxid_wrapper_serde {
+    ($name:ident) => {
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__private::serde::Serializer,
+            {
+                $crate::__private::serde::Serialize::serialize(&self.0, serializer)
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: $crate::__private::serde::Deserializer<'de>,
+            {
+                let value = <$crate::Pxid as $crate::__private::serde::Deserialize>::deserialize(
+                    deserializer,
+                )?;
+
+                <Self as ::core::convert::TryFrom<$crate::Pxid>>::try_from(value)
+                    .map_err(<D::Error as $crate::__private::serde::de::Error>::custom)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pxid_wrapper_serde {
+    ($name:ident) => {};
+}
+
+/// Implements `ScalarType` and `CursorType` delegating on the wrapped `Pxid`.
+///
+/// Wrappers without prefix are marked with `unprefixed`, given they only
+/// implement `From<Pxid>` and decoded cursors are wrapped as is.
+///
+/// The `Scalar` attribute expands to paths on the `async_graphql` crate, so
+/// crates using `PxidWrapper` or `define_pxid_type!` with this feature
+/// enabled must depend on `async-graphql` too.
+#[cfg(feature = "async-graphql")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pxid_wrapper_graphql {
+    ($name:ident) => {
+        $crate::__pxid_wrapper_graphql!(
+            @impl $name,
+            <$name as ::core::convert::TryFrom<$crate::Pxid>>::try_from
+        );
+    };
+    ($name:ident, unprefixed) => {
+        $crate::__pxid_wrapper_graphql!(@impl $name, |value| {
+            ::core::result::Result::Ok(<$name as ::core::convert::From<$crate::Pxid>>::from(value))
+        });
+    };
+    (@impl $name:ident, $convert:expr) => {
+        #[::async_graphql::Scalar]
+        impl ::async_graphql::ScalarType for $name {
+            fn parse(data: ::async_graphql::Value) -> ::async_graphql::InputValueResult<Self> {
+                if let ::async_graphql::Value::String(inner) = &data {
+                    if let ::core::result::Result::Ok(value) =
+                        <Self as ::core::str::FromStr>::from_str(inner)
+                    {
+                        return ::core::result::Result::Ok(value);
+                    }
+                }
+
+                ::core::result::Result::Err(::async_graphql::InputValueError::expected_type(data))
+            }
+
+            fn to_value(&self) -> ::async_graphql::Value {
+                <$crate::Pxid as ::async_graphql::ScalarType>::to_value(&self.0)
+            }
+        }
+
+        impl ::async_graphql::connection::CursorType for $name {
+            type Error = $crate::Error;
+
+            fn decode_cursor(s: &str) -> ::core::result::Result<Self, Self::Error> {
+                let value =
+                    <$crate::Pxid as ::async_graphql::connection::CursorType>::decode_cursor(s)?;
+
+                ($convert)(value)
+            }
+
+            fn encode_cursor(&self) -> ::std::string::String {
+                <$crate::Pxid as ::async_graphql::connection::CursorType>::encode_cursor(&self.0)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "async-graphql"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pxid_wrapper_graphql {
+    ($name:ident $(, unprefixed)?) => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    crate::define_pxid_type!(AccountId, "acct");
+    crate::define_pxid_type!(OrderId, "ordr");
+
+    #[test]
+    fn defined_types_create_ids_with_prefix() {
+        let id = AccountId::new().unwrap();
+
+        assert_eq!(AccountId::PREFIX, "acct");
+        assert!(id.to_string().starts_with("acct_"));
+        assert_eq!(AccountId::from_str(&id.to_string()), Ok(id));
+        assert_eq!(Pxid::from(id), id.into_inner());
+    }
+
+    #[test]
+    fn defined_types_round_trip_mixed_case_prefixes() {
+        let id = AccountId::from_str("AcCt_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(id.to_string(), "acct_9m4e2mr0ui3e8a215n4g");
+        assert_eq!(AccountId::from_str(&id.to_string()), Ok(id));
+        assert_eq!(AccountId::try_from(Pxid::from(id)), Ok(id));
+    }
+
+    #[test]
+    fn defined_types_reject_mismatched_prefix() {
+        let order = OrderId::new().unwrap();
+        let expected = Err(Error::PrefixMismatch {
+            expected: String::from("acct"),
+            found: String::from("ordr"),
+        });
+
+        assert_eq!(AccountId::from_str(&order.to_string()), expected);
+        assert_eq!(AccountId::try_from(order.to_string().as_str()), expected);
+        assert_eq!(AccountId::try_from(order.into_inner()), expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn defined_types_serde_round_trip() {
+        let id = AccountId::from_str("acct_9m4e2mr0ui3e8a215n4g").unwrap();
+        let json = serde_json::to_string(&id).unwrap();
+
+        assert_eq!(json, "\"acct_9m4e2mr0ui3e8a215n4g\"");
+        assert_eq!(serde_json::from_str::<AccountId>(&json).unwrap(), id);
+        assert!(serde_json::from_str::<OrderId>(&json).is_err());
+    }
+
+    #[test]
+    fn checks_prefix() {
+        let id = Pxid::from_str("user_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(check_prefix(&id, "user"), Ok(()));
+        assert_eq!(
+            check_prefix(&id, "acct"),
+            Err(Error::PrefixMismatch {
+                expected: String::from("acct"),
+                found: String::from("user")
+            })
+        );
+    }
+
+    #[test]
+    fn checks_prefix_ignoring_expected_case() {
+        let id = Pxid::from_str("USER_9m4e2mr0ui3e8a215n4g").unwrap();
+
+        assert_eq!(check_prefix(&id, "USER"), Ok(()));
+        assert_eq!(check_prefix(&id, "User"), Ok(()));
+        assert_eq!(
+            Pxid::expect_prefix("USER_9m4e2mr0ui3e8a215n4g", "User"),
+            Ok(id)
+        );
+    }
+
+    #[test]
+    fn detects_lowercase_prefixes() {
+        assert!(is_lowercase_prefix("acct"));
+        assert!(is_lowercase_prefix("a_1"));
+        assert!(!is_lowercase_prefix("USER"));
+        assert!(!is_lowercase_prefix("usEr"));
+    }
+
+    #[test]
+    fn checks_short_prefix_with_nul_padding() {
+        let id = Pxid::new("usr").unwrap();
+
+        assert_eq!(check_prefix(&id, "usr"), Ok(()));
+        assert_eq!(
+            check_prefix(&id, "user"),
+            Err(Error::PrefixMismatch {
+                expected: String::from("user"),
+                found: String::from("usr")
+            })
+        );
+    }
+}
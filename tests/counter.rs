@@ -0,0 +1,167 @@
+//! Checks on the Counter shared by `Pxid::new` and every `Factory`.
+//!
+//! These tests pin the process wide Counter, so they run on their own test
+//! binary and hold `LOCK` to run one at a time.
+use std::collections::HashSet;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use pxid::{Factory, Pxid, COUNTER_MAX};
+
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn lock() -> MutexGuard<'static, ()> {
+    LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[test]
+fn shares_counter_between_pxid_new_and_factories() {
+    let _lock = lock();
+    let factory = Factory::new().unwrap();
+    let other = Factory::new().unwrap();
+    let first = Pxid::new("acct").unwrap().counter();
+    let counters = [
+        factory.new_id("acct").unwrap().counter(),
+        Pxid::new("acct").unwrap().counter(),
+        other.new_id("acct").unwrap().counter(),
+        factory.new_sorted_ids("acct", 2).unwrap()[0].counter(),
+        Pxid::new("acct").unwrap().counter(),
+    ];
+
+    assert_eq!(
+        counters,
+        [1, 2, 3, 4, 6].map(|offset| first.wrapping_add(offset) & COUNTER_MAX)
+    );
+    assert_eq!(factory.counter(), other.counter());
+}
+
+#[test]
+fn backfilled_ids_never_collide_with_live_ids() {
+    const TIMES: [u32; 3] = [1_600_000_000, 1_600_000_001, 1_700_000_000];
+
+    let _lock = lock();
+
+    for factory in [
+        Factory::new().unwrap(),
+        Factory::new_or_nil(),
+        Factory::new()
+            .unwrap()
+            .backfill_machine_id([0xff, 0xff, 0xfe]),
+    ] {
+        let mut live = HashSet::new();
+        let mut backfilled = HashSet::new();
+
+        for time in TIMES {
+            factory.set_counter(COUNTER_MAX - 500);
+
+            for _ in 0..1_000 {
+                live.insert(<[u8; 16]>::from(
+                    factory.new_with_time("acct", time).unwrap(),
+                ));
+            }
+
+            factory.set_counter(COUNTER_MAX - 500);
+
+            for _ in 0..1_000 {
+                backfilled.insert(<[u8; 16]>::from(factory.backfill("acct", time).unwrap()));
+            }
+        }
+
+        assert_eq!(live.len(), 3_000);
+        assert_eq!(backfilled.len(), 3_000);
+        assert_eq!(live.intersection(&backfilled).count(), 0);
+    }
+}
+
+#[test]
+fn factory_reports_counter_exhaustion() {
+    let _lock = lock();
+    let factory = Factory::new().unwrap();
+
+    factory.set_counter(COUNTER_MAX);
+    assert!(!factory.counter_exhausted());
+
+    factory.new_id("acct").unwrap();
+    assert!(factory.counter_exhausted());
+}
+
+#[test]
+fn generates_sorted_ids_when_counter_wraps() {
+    let _lock = lock();
+    let factory = Factory::new().unwrap();
+
+    factory.set_counter(COUNTER_MAX - 1);
+
+    let ids = factory.new_sorted_ids("acct", 5).unwrap();
+    let mut sorted = ids.clone();
+
+    sorted.sort();
+
+    assert_eq!(ids, sorted);
+    assert_eq!(
+        ids.iter().map(Pxid::counter).collect::<Vec<u32>>(),
+        vec![0, 1, 2, COUNTER_MAX - 1, COUNTER_MAX]
+    );
+    assert_eq!(factory.counter(), COUNTER_MAX + 4);
+}
+
+#[test]
+fn generates_batches_when_counter_wraps() {
+    let _lock = lock();
+
+    Factory::new().unwrap().set_counter(COUNTER_MAX - 1);
+
+    let ids = Pxid::new_batch("acct", 5).unwrap();
+
+    assert_eq!(
+        ids.iter().map(Pxid::counter).collect::<Vec<u32>>(),
+        vec![0, 1, 2, COUNTER_MAX - 1, COUNTER_MAX]
+    );
+    assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn factory_uses_pinned_counter() {
+    let _lock = lock();
+    let factory = Factory::new().unwrap();
+
+    factory.set_counter(0x00AB_CDEF);
+    assert_eq!(factory.counter(), 0x00AB_CDEF);
+
+    let id = factory.new_id("acct").unwrap();
+
+    assert_eq!(id.counter(), 0x00AB_CDEF);
+    assert_eq!(factory.counter(), 0x00AB_CDF0);
+    assert_eq!(Pxid::new("acct").unwrap().counter(), 0x00AB_CDF0);
+}
+
+#[test]
+fn reseed_replaces_counter() {
+    let _lock = lock();
+    let factory = Factory::new().unwrap();
+
+    // Seeds never exceed `COUNTER_MAX`, so the reseeded value always
+    // differs from this one
+    factory.set_counter(COUNTER_MAX + 1);
+    factory.reseed();
+
+    let seed = factory.counter();
+
+    assert!(seed <= COUNTER_MAX);
+    assert_eq!(factory.new_id("acct").unwrap().counter(), seed);
+}
+
+#[test]
+fn peek_next_does_not_consume_counter() {
+    let _lock = lock();
+    let factory = Factory::new().unwrap();
+    let counter = factory.counter();
+    let peeked = factory.peek_next("acct").unwrap();
+
+    assert_eq!(peeked.counter(), counter & COUNTER_MAX);
+    assert_eq!(
+        factory.peek_next("acct").unwrap().counter(),
+        peeked.counter()
+    );
+    assert_eq!(factory.counter(), counter);
+    assert_eq!(factory.new_id("acct").unwrap().counter(), peeked.counter());
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "derive")]
+
+use std::str::FromStr;
+
+use pxid::{Error, Pxid, PxidWrapper};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PxidWrapper)]
+#[pxid(prefix = "user")]
+struct UserId(Pxid);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PxidWrapper)]
+struct AnyId(Pxid);
+
+const USER_ID: &str = "user_9m4e2mr0ui3e8a215n4g";
+const ACCOUNT_ID: &str = "acct_9m4e2mr0ui3e8a215n4g";
+
+#[test]
+fn creates_ids_with_prefix() {
+    let id = UserId::new().unwrap();
+
+    assert_eq!(UserId::PREFIX, "user");
+    assert!(id.to_string().starts_with("user_"));
+}
+
+#[test]
+fn parses_ids_with_expected_prefix() {
+    let id = UserId::from_str(USER_ID).unwrap();
+
+    assert_eq!(id.to_string(), USER_ID);
+    assert_eq!(UserId::try_from(USER_ID), Ok(id));
+    assert_eq!(Pxid::from(id), Pxid::from_str(USER_ID).unwrap());
+}
+
+#[test]
+fn rejects_ids_with_other_prefix() {
+    let expected = Err(Error::PrefixMismatch {
+        expected: String::from("user"),
+        found: String::from("acct"),
+    });
+
+    assert_eq!(UserId::from_str(ACCOUNT_ID), expected);
+    assert_eq!(UserId::try_from(ACCOUNT_ID), expected);
+    assert_eq!(
+        UserId::try_from(Pxid::from_str(ACCOUNT_ID).unwrap()),
+        expected
+    );
+}
+
+#[test]
+fn accepts_any_prefix_without_attribute() {
+    let pxid = Pxid::from_str(ACCOUNT_ID).unwrap();
+
+    assert_eq!(AnyId::from(pxid), AnyId::from_str(ACCOUNT_ID).unwrap());
+    assert_eq!(AnyId::from(pxid).to_string(), ACCOUNT_ID);
+}
+
+#[test]
+fn forwards_decode_errors() {
+    assert!(matches!(
+        UserId::from_str("user_9m4e2mr0ui3e8a215n4x"),
+        Err(Error::Decode(_))
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trips_as_string() {
+    let id = UserId::from_str(USER_ID).unwrap();
+    let json = serde_json::to_string(&id).unwrap();
+
+    assert_eq!(json, format!("\"{}\"", USER_ID));
+    assert_eq!(serde_json::from_str::<UserId>(&json).unwrap(), id);
+
+    let any = AnyId::from_str(ACCOUNT_ID).unwrap();
+    let json = serde_json::to_string(&any).unwrap();
+
+    assert_eq!(serde_json::from_str::<AnyId>(&json).unwrap(), any);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_rejects_ids_with_other_prefix() {
+    let err = serde_json::from_str::<UserId>(&format!("\"{}\"", ACCOUNT_ID)).unwrap_err();
+
+    assert_eq!(err.to_string(), "Expected prefix: user, but received acct.");
+}
+
+#[cfg(feature = "async-graphql")]
+#[test]
+fn graphql_cursors_round_trip() {
+    use async_graphql::connection::CursorType;
+
+    let user = UserId::from_str(USER_ID).unwrap();
+    let any = AnyId::from_str(ACCOUNT_ID).unwrap();
+
+    assert_eq!(UserId::decode_cursor(&user.encode_cursor()), Ok(user));
+    assert_eq!(AnyId::decode_cursor(&any.encode_cursor()), Ok(any));
+    assert_eq!(
+        UserId::decode_cursor(ACCOUNT_ID),
+        Err(Error::PrefixMismatch {
+            expected: String::from("user"),
+            found: String::from("acct"),
+        })
+    );
+    assert!(matches!(
+        AnyId::decode_cursor("acct"),
+        Err(Error::Decode(_))
+    ));
+}
+
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+
+    t.compile_fail("tests/ui/*.rs");
+}
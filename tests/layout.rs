@@ -0,0 +1,45 @@
+//! Verifies each part passed to `Pxid::from_parts_unchecked` lands on its
+//! place of the packed data layout, and is read back by its accessor.
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use pxid::Pxid;
+
+fn layout_id() -> Pxid {
+    Pxid::from_parts_unchecked(*b"acct", 0x0102_0304, [0xAA, 0xBB, 0xCC], 0xDDEE, 0x00FF00)
+}
+
+#[test]
+fn packs_each_part_at_its_offset() {
+    assert_eq!(
+        <[u8; 16]>::from(layout_id()),
+        [
+            b'a', b'c', b'c', b't', // Prefix
+            0x01, 0x02, 0x03, 0x04, // Timestamp
+            0xAA, 0xBB, 0xCC, // Machine ID
+            0xDD, 0xEE, // Process ID
+            0x00, 0xFF, 0x00, // Counter
+        ]
+    );
+}
+
+#[test]
+fn reads_back_each_part() {
+    let id = layout_id();
+
+    assert_eq!(id.prefix_str(), Ok("acct"));
+    assert_eq!(id.prefix_bytes(), *b"acct");
+    assert_eq!(
+        id.timestamp(),
+        UNIX_EPOCH + Duration::from_secs(0x0102_0304)
+    );
+    assert_eq!(id.machine_id(), [0xAA, 0xBB, 0xCC]);
+    assert_eq!(id.process_id(), 0xDDEE);
+    assert_eq!(id.process_id_bytes(), [0xDD, 0xEE]);
+    assert_eq!(id.counter(), 0x00FF00);
+    assert_eq!(id.counter_bytes(), [0x00, 0xFF, 0x00]);
+    assert_eq!(
+        id.xid_bytes(),
+        [0x01, 0x02, 0x03, 0x04, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0x00, 0xFF, 0x00]
+    );
+}
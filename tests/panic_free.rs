@@ -0,0 +1,146 @@
+//! Regression corpus for inputs which used to panic or are likely to panic
+//! on parsing and encoding paths. Every call must return, either `Ok` or
+//! `Err`, without panicking.
+
+use std::str::FromStr;
+
+use pxid::{Error, Pxid};
+
+const STRINGS: &[&str] = &[
+    "",
+    "_",
+    "__",
+    "a",
+    "acct",
+    "acct_",
+    "nil_",
+    "nil_0000",
+    "\"",
+    "\"\"",
+    "_9m4e2mr0ui3e8a215n4g",
+    "a_9m4e2mr0ui3e8a215n4g",
+    "dog_9m4e2mr0ui3e8a215n4g",
+    "acct_9m4e2mr0ui3e8a215n4g",
+    "acct_9m4e2mr0ui3e8a215n4gg",
+    "acct_9m4e2mr0ui3e8a215n4",
+    "acct-9m4e2mr0ui3e8a215n4g",
+    "acct_9m4e_mr0ui3e8a215n4g",
+    "acct__m4e2mr0ui3e8a215n4g",
+    "acct_zzzzzzzzzzzzzzzzzzzz",
+    "acct_ü4e2mr0ui3e8a215n4g",
+    "acct_9m4e2mr0ui3e8a215n🦀",
+    "ñ_9m4e2mr0ui3e8a215n4g",
+    "ññ_9m4e2mr0ui3e8a215n4g",
+    "ñññ_9m4e2mr0ui3e8a215n4g",
+    "🦀_9m4e2mr0ui3e8a215n4g",
+    "\0\0\0\0_9m4e2mr0ui3e8a215n4g",
+    "\0_9m4e2mr0ui3e8a215n4g",
+    "9m4e2mr0ui3e8a215n4g",
+    "01ARZ3NDEKTSV4RRFFQ69G5FAV",
+    "ZZZZZZZZZZZZZZZZZZZZZZZZZZ",
+    "0000000000000000000000000ü",
+    "acct_9m4e2mr0ui3e8a215n4gacct_9m4e2mr0ui3e8a215n4g",
+];
+
+const BYTES: &[[u8; 16]] = &[
+    [0; 16],
+    [0xff; 16],
+    [0x80; 16],
+    [0xf0, 0x9f, 0xa6, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    [0, b'a', 0, b'b', 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+    [
+        0xc3, 0xb1, 0xc3, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    ],
+];
+
+#[test]
+fn parses_string_corpus_without_panicking() {
+    for s in STRINGS {
+        let parsed = [
+            Pxid::from_str(s),
+            Pxid::from_str_preserve_case(s),
+            Pxid::from_str_with_separator(s, '.'),
+            Pxid::from_str_with_checksum(s),
+            Pxid::parse_unquoted(s),
+            Pxid::expect_prefix(s, "acct"),
+            Pxid::from_ulid_string("acct", s),
+        ];
+
+        for id in parsed.into_iter().flatten() {
+            let _ = id.to_string();
+        }
+
+        let _ = Pxid::decode_xid(s);
+    }
+}
+
+#[test]
+fn encodes_bytes_corpus_without_panicking() {
+    for bytes in BYTES {
+        let id = Pxid::from(*bytes);
+        let mut appended = String::new();
+
+        id.append_to(&mut appended);
+
+        assert_eq!(appended, id.to_string());
+        assert_eq!(id.encoded().as_str(), id.to_string());
+        assert_eq!(format!("{}", id.display_full()), id.to_string());
+
+        let _ = format!("{:>40}", id);
+        let _ = id.short();
+        let _ = id.short_with(usize::MAX, usize::MAX);
+        let _ = id.to_string_with_separator('.');
+        let _ = id.to_string_with_checksum();
+        let _ = id.to_ulid_string();
+        let _ = id.try_to_string();
+        let _ = id.prefix();
+        let _ = id.split();
+        let _ = id.validate();
+        let _ = id.shard(0);
+        let _ = id.write_encoded(&mut String::new());
+        let _ = id.write_encoded_bytes(&mut Vec::new());
+    }
+}
+
+#[test]
+fn parses_short_prefixes_with_nul_padding() {
+    let id = Pxid::from_str("dog_9m4e2mr0ui3e8a215n4g").unwrap();
+
+    assert_eq!(id.prefix_bytes(), *b"dog\0");
+    assert_eq!(id.to_string(), "dog\0_9m4e2mr0ui3e8a215n4g");
+    assert_eq!(
+        Pxid::from_str("a_9m4e2mr0ui3e8a215n4g")
+            .unwrap()
+            .prefix_bytes(),
+        *b"a\0\0\0"
+    );
+}
+
+#[test]
+fn rejects_empty_prefixes() {
+    assert!(matches!(
+        Pxid::from_str("_9m4e2mr0ui3e8a215n4g"),
+        Err(Error::Decode(_))
+    ));
+}
+
+#[test]
+fn rejects_short_checksum_strings() {
+    for s in ["", "g", "_g", "acct_g"] {
+        assert!(Pxid::from_str_with_checksum(s).is_err(), "{:?}", s);
+    }
+}
+
+#[test]
+fn reports_invalid_binary_prefixes() {
+    let id = Pxid::from([0xff; 16]);
+
+    assert_eq!(
+        id.try_to_string(),
+        Err(Error::InvalidBinaryPrefix([0xff; 4]))
+    );
+    assert_eq!(
+        id.to_string(),
+        "\u{fffd}\u{fffd}\u{fffd}\u{fffd}_vvvvvvvvvvvvvvvvvvvg"
+    );
+}
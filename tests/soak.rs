@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use pxid::soak::{self, SoakConfig};
+
+#[test]
+fn soak_smoke_test() {
+    let report = soak::run(
+        SoakConfig::duration(Duration::from_secs(1))
+            .threads(4)
+            .prefixes(["acct", "ordr", "dog"]),
+    );
+
+    assert!(report.generated > 0);
+    assert!(report.round_trips > 0);
+    assert!(report.is_ok(), "{:?}", report.violations);
+}
+
+/// Release check minting IDs for ten minutes, run with
+/// `cargo test --features test-util --test soak -- --ignored`
+#[test]
+#[ignore]
+fn soak_ten_minutes() {
+    let report = soak::run(
+        SoakConfig::duration(Duration::from_secs(600))
+            .threads(8)
+            .prefixes(["acct", "ordr", "dog", "user"])
+            .exact_cap(10_000_000),
+    );
+
+    println!(
+        "Minted {} IDs at {:.0} IDs/s, {} unverified Bloom filter hits, {} bytes high-water mark",
+        report.generated,
+        report.throughput(),
+        report.unverified,
+        report.memory_high_water
+    );
+
+    assert!(report.is_ok(), "{:?}", report.violations);
+}
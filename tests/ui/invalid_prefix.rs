@@ -0,0 +1,23 @@
+use pxid::{Pxid, PxidWrapper};
+
+#[derive(PxidWrapper)]
+#[pxid(prefix = "account")]
+struct LongPrefixId(Pxid);
+
+#[derive(PxidWrapper)]
+#[pxid(prefix = "")]
+struct EmptyPrefixId(Pxid);
+
+#[derive(PxidWrapper)]
+#[pxid(prefix = 42)]
+struct NumericPrefixId(Pxid);
+
+#[derive(PxidWrapper)]
+#[pxid(prefix = "USER")]
+struct UppercasePrefixId(Pxid);
+
+#[derive(PxidWrapper)]
+#[pxid(separator = "-")]
+struct UnknownAttributeId(Pxid);
+
+fn main() {}
@@ -0,0 +1,16 @@
+use pxid::{Pxid, PxidWrapper};
+
+#[derive(PxidWrapper)]
+struct NamedId {
+    id: Pxid,
+}
+
+#[derive(PxidWrapper)]
+struct PairId(Pxid, Pxid);
+
+#[derive(PxidWrapper)]
+enum EnumId {
+    User(Pxid),
+}
+
+fn main() {}